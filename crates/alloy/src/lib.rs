@@ -56,6 +56,10 @@ macro_rules! sol {
 
 /* --------------------------------------- Main re-exports -------------------------------------- */
 
+#[cfg(feature = "beacon-client")]
+#[doc(inline)]
+pub use alloy_beacon_client as beacon_client;
+
 #[cfg(feature = "contract")]
 #[doc(inline)]
 pub use alloy_contract as contract;
@@ -64,6 +68,10 @@ pub use alloy_contract as contract;
 #[doc(inline)]
 pub use alloy_consensus as consensus;
 
+#[cfg(feature = "eip5792")]
+#[doc(inline)]
+pub use alloy_eip5792 as eip5792;
+
 #[cfg(feature = "eips")]
 #[doc(inline)]
 pub use alloy_eips as eips;
@@ -0,0 +1,223 @@
+use crate::{
+    events::{SseDecoder, SseEvent},
+    http::{append_list, endpoint, send, send_json},
+    BeaconEvent, BlockId, StateId, ValidatorId,
+};
+use alloy_primitives::B256;
+use alloy_rpc_types_beacon::{
+    events::BeaconNodeEventTopic,
+    header::{HeaderResponse, HeadersResponse},
+    sidecar::BeaconBlobBundle,
+    validator::{ValidatorStatus, ValidatorsResponse},
+};
+use alloy_transport::{TransportErrorKind, TransportResult};
+use alloy_transport_http::reqwest::{header, Client};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use tracing::{debug, trace};
+use url::Url;
+
+/// A client for the HTTP API of a beacon node.
+///
+/// See the [Beacon API specification](https://ethereum.github.io/beacon-APIs) for details on
+/// each endpoint.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_beacon_client::{BeaconClient, BlockId};
+///
+/// let client = BeaconClient::new("http://localhost:5052".parse()?);
+/// let sidecars = client.blob_sidecars(BlockId::Head, &[]).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BeaconClient {
+    client: Client,
+    url: Url,
+}
+
+impl BeaconClient {
+    /// Create a new [`BeaconClient`] for the beacon node at the given URL.
+    pub fn new(url: Url) -> Self {
+        Self { client: Default::default(), url }
+    }
+
+    /// Create a new [`BeaconClient`] with the given client, for the beacon node at the given URL.
+    pub const fn with_client(client: Client, url: Url) -> Self {
+        Self { client, url }
+    }
+
+    /// Get a reference to the client.
+    pub const fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get a reference to the URL of the beacon node.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the header of the given block.
+    ///
+    /// `GET /eth/v1/beacon/headers/{block_id}`
+    pub async fn header(&self, block: BlockId) -> TransportResult<HeaderResponse> {
+        let url = self.endpoint(&["eth", "v1", "beacon", "headers", &block.to_string()])?;
+        self.get(url).await
+    }
+
+    /// Returns the headers matching the given slot and parent root, or the canonical head header
+    /// if none is given.
+    ///
+    /// `GET /eth/v1/beacon/headers`
+    pub async fn headers(
+        &self,
+        slot: Option<u64>,
+        parent_root: Option<B256>,
+    ) -> TransportResult<HeadersResponse> {
+        let mut url = self.endpoint(&["eth", "v1", "beacon", "headers"])?;
+        if let Some(slot) = slot {
+            url.query_pairs_mut().append_pair("slot", &slot.to_string());
+        }
+        if let Some(parent_root) = parent_root {
+            url.query_pairs_mut().append_pair("parent_root", &format!("{parent_root:?}"));
+        }
+        self.get(url).await
+    }
+
+    /// Returns the blob sidecars of the given block, restricted to the given indices if any.
+    ///
+    /// The sidecar of a blob transaction can be extracted from the response with
+    /// [`BeaconBlobBundle::sidecar_for_versioned_hashes`].
+    ///
+    /// `GET /eth/v1/beacon/blob_sidecars/{block_id}`
+    pub async fn blob_sidecars(
+        &self,
+        block: BlockId,
+        indices: &[u64],
+    ) -> TransportResult<BeaconBlobBundle> {
+        let mut url =
+            self.endpoint(&["eth", "v1", "beacon", "blob_sidecars", &block.to_string()])?;
+        append_list(&mut url, "indices", indices);
+        self.get(url).await
+    }
+
+    /// Returns the validators of the given state, filtered by id and status if any.
+    ///
+    /// `GET /eth/v1/beacon/states/{state_id}/validators`
+    pub async fn validators(
+        &self,
+        state: StateId,
+        ids: &[ValidatorId],
+        statuses: &[ValidatorStatus],
+    ) -> TransportResult<ValidatorsResponse> {
+        let mut url =
+            self.endpoint(&["eth", "v1", "beacon", "states", &state.to_string(), "validators"])?;
+        append_list(&mut url, "id", ids);
+        append_list(&mut url, "status", statuses.iter().map(|status| status.query_value()));
+        self.get(url).await
+    }
+
+    /// Subscribes to the event stream of the beacon node, for the given topics.
+    ///
+    /// The stream ends when the node closes the connection, or after yielding an error.
+    ///
+    /// `GET /eth/v1/events`
+    pub async fn events(
+        &self,
+        topics: &[BeaconNodeEventTopic],
+    ) -> TransportResult<impl Stream<Item = TransportResult<BeaconEvent>> + Send + 'static> {
+        let mut url = self.endpoint(&["eth", "v1", "events"])?;
+        append_list(&mut url, "topics", topics.iter().map(|topic| topic.query_value()));
+
+        let resp = send(self.client.get(url).header(header::ACCEPT, "text/event-stream")).await?;
+
+        let state = (resp, SseDecoder::default(), VecDeque::<SseEvent>::new());
+        Ok(futures::stream::try_unfold(state, |(mut resp, mut decoder, mut pending)| async move {
+            loop {
+                if let Some(SseEvent { event, data }) = pending.pop_front() {
+                    trace!(%event, %data, "received beacon event");
+                    let event = BeaconEvent::decode(&event, &data)?;
+                    return Ok(Some((event, (resp, decoder, pending))));
+                }
+
+                match resp.chunk().await.map_err(TransportErrorKind::custom)? {
+                    Some(chunk) => pending.extend(decoder.push(&chunk)),
+                    None => {
+                        debug!("beacon event stream closed");
+                        return Ok(None);
+                    }
+                }
+            }
+        }))
+    }
+
+    fn endpoint(&self, segments: &[&str]) -> TransportResult<Url> {
+        endpoint(&self.url, segments)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: Url) -> TransportResult<T> {
+        send_json(self.client.get(url)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test::serve;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn events_stream() {
+        let data = r#"{"slot":"10","block":"0x9a2fefd2fdb57f74993c7780ea5b9030d2897b615b89f808011ca5aebed54eaf","execution_optimistic":false}"#;
+        let body =
+            format!(": keep-alive\n\nevent: block\ndata: {data}\n\nevent: block\ndata: {data}\n\n");
+        let (url, server) = serve(vec![("200 OK", "text/event-stream", body)]).await;
+
+        let client = BeaconClient::new(url);
+        let events: Vec<_> = client
+            .events(&[BeaconNodeEventTopic::Block])
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], BeaconEvent::Block(block) if block.slot == 10));
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("GET /eth/v1/events?topics=block HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn http_error() {
+        let body = r#"{"code":404,"message":"Block not found"}"#.to_string();
+        let (url, _server) = serve(vec![("404 Not Found", "application/json", body.clone())]).await;
+
+        let client = BeaconClient::new(url);
+        let err = client.header(BlockId::Slot(1)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            alloy_transport::RpcError::Transport(TransportErrorKind::HttpError(err))
+                if err.status == 404 && err.body == body
+        ));
+    }
+
+    #[test]
+    fn endpoint_urls() {
+        for base in ["http://localhost:5052", "http://localhost:5052/"] {
+            let client = BeaconClient::new(base.parse().unwrap());
+            let url = client.endpoint(&["eth", "v1", "beacon", "headers", "head"]).unwrap();
+            assert_eq!(url.as_str(), "http://localhost:5052/eth/v1/beacon/headers/head");
+        }
+
+        let client = BeaconClient::new("http://localhost/beacon/".parse().unwrap());
+        let mut url = client.endpoint(&["eth", "v1", "events"]).unwrap();
+        append_list(&mut url, "topics", [BeaconNodeEventTopic::Head.query_value(), "block"]);
+        append_list(&mut url, "indices", Vec::<u64>::new());
+        assert_eq!(url.as_str(), "http://localhost/beacon/eth/v1/events?topics=head%2Cblock");
+    }
+}
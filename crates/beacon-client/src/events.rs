@@ -0,0 +1,187 @@
+use alloy_rpc_types_beacon::events::{
+    AttestationEvent, BeaconNodeEventTopic, BlobSidecarEvent, BlockEvent,
+    BlsToExecutionChangeEvent, ChainReorgEvent, ContributionAndProofEvent,
+    FinalizedCheckpointEvent, HeadEvent, LightClientFinalityUpdateEvent,
+    LightClientOptimisticUpdateEvent, PayloadAttributesEvent, VoluntaryExitEvent,
+};
+use alloy_transport::{TransportError, TransportErrorKind, TransportResult};
+use serde::de::DeserializeOwned;
+
+/// An event of the beacon node event stream, decoded according to its
+/// [`BeaconNodeEventTopic`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BeaconEvent {
+    /// A `payload_attributes` event.
+    PayloadAttributes(PayloadAttributesEvent),
+    /// A `head` event.
+    Head(HeadEvent),
+    /// A `block` event.
+    Block(BlockEvent),
+    /// An `attestation` event.
+    Attestation(AttestationEvent),
+    /// A `voluntary_exit` event.
+    VoluntaryExit(VoluntaryExitEvent),
+    /// A `bls_to_execution_change` event.
+    BlsToExecutionChange(BlsToExecutionChangeEvent),
+    /// A `finalized_checkpoint` event.
+    FinalizedCheckpoint(FinalizedCheckpointEvent),
+    /// A `chain_reorg` event.
+    ChainReorg(ChainReorgEvent),
+    /// A `contribution_and_proof` event.
+    ContributionAndProof(ContributionAndProofEvent),
+    /// A `light_client_finality_update` event.
+    LightClientFinalityUpdate(LightClientFinalityUpdateEvent),
+    /// A `light_client_optimistic_update` event.
+    LightClientOptimisticUpdate(LightClientOptimisticUpdateEvent),
+    /// A `blob_sidecar` event.
+    BlobSidecar(BlobSidecarEvent),
+}
+
+impl BeaconEvent {
+    /// Returns the topic of the event.
+    pub const fn topic(&self) -> BeaconNodeEventTopic {
+        match self {
+            Self::PayloadAttributes(_) => BeaconNodeEventTopic::PayloadAttributes,
+            Self::Head(_) => BeaconNodeEventTopic::Head,
+            Self::Block(_) => BeaconNodeEventTopic::Block,
+            Self::Attestation(_) => BeaconNodeEventTopic::Attestation,
+            Self::VoluntaryExit(_) => BeaconNodeEventTopic::VoluntaryExit,
+            Self::BlsToExecutionChange(_) => BeaconNodeEventTopic::BlsToExecutionChange,
+            Self::FinalizedCheckpoint(_) => BeaconNodeEventTopic::FinalizedCheckpoint,
+            Self::ChainReorg(_) => BeaconNodeEventTopic::ChainReorg,
+            Self::ContributionAndProof(_) => BeaconNodeEventTopic::ContributionAndProof,
+            Self::LightClientFinalityUpdate(_) => BeaconNodeEventTopic::LightClientFinalityUpdate,
+            Self::LightClientOptimisticUpdate(_) => {
+                BeaconNodeEventTopic::LightClientOptimisticUpdate
+            }
+            Self::BlobSidecar(_) => BeaconNodeEventTopic::BlobSidecar,
+        }
+    }
+
+    /// Decode an event from the name of its topic and its JSON payload.
+    pub fn decode(topic: &str, data: &str) -> TransportResult<Self> {
+        fn de<T: DeserializeOwned>(data: &str) -> TransportResult<T> {
+            serde_json::from_str(data).map_err(|err| TransportError::deser_err(err, data))
+        }
+
+        Ok(match topic {
+            "payload_attributes" => Self::PayloadAttributes(de(data)?),
+            "head" => Self::Head(de(data)?),
+            "block" => Self::Block(de(data)?),
+            "attestation" => Self::Attestation(de(data)?),
+            "voluntary_exit" => Self::VoluntaryExit(de(data)?),
+            "bls_to_execution_change" => Self::BlsToExecutionChange(de(data)?),
+            "finalized_checkpoint" => Self::FinalizedCheckpoint(de(data)?),
+            "chain_reorg" => Self::ChainReorg(de(data)?),
+            "contribution_and_proof" => Self::ContributionAndProof(de(data)?),
+            "light_client_finality_update" => Self::LightClientFinalityUpdate(de(data)?),
+            "light_client_optimistic_update" => Self::LightClientOptimisticUpdate(de(data)?),
+            "blob_sidecar" => Self::BlobSidecar(de(data)?),
+            _ => {
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "unknown beacon event topic: {topic}"
+                )))
+            }
+        })
+    }
+}
+
+/// A raw server-sent event.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SseEvent {
+    /// The event name, `message` if unset.
+    pub(crate) event: String,
+    /// The event data, with multiple `data` lines joined by newlines.
+    pub(crate) data: String,
+}
+
+/// An incremental decoder of `text/event-stream` bodies.
+///
+/// See <https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation>
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    /// Bytes of the current, incomplete line.
+    line: Vec<u8>,
+    /// The name of the event being built.
+    event: Option<String>,
+    /// The data of the event being built.
+    data: Option<String>,
+}
+
+impl SseDecoder {
+    /// Feed a chunk of the body to the decoder, returning the events it completes.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            match byte {
+                b'\n' => {
+                    let line = std::mem::take(&mut self.line);
+                    let line = line.strip_suffix(b"\r").unwrap_or(&line);
+                    events.extend(self.process_line(&String::from_utf8_lossy(line)));
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            let event = self.event.take();
+            let data = self.data.take()?;
+            return Some(SseEvent { event: event.unwrap_or_else(|| "message".to_string()), data });
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            // Comment, used as keep-alive.
+            "" => {}
+            "event" => self.event = Some(value.to_string()),
+            "data" => match &mut self.data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_string()),
+            },
+            _ => {}
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEAD: &str = r#"{"slot":"10","block":"0x9a2fefd2fdb57f74993c7780ea5b9030d2897b615b89f808011ca5aebed54eaf","state":"0x600e852a08c1200654ddf11025f1ceacb3c2e74bdd5c630cde0838b2591b69f9","epoch_transition":false,"previous_duty_dependent_root":"0x5e0043f107cb57913498fbf2f99ff55e730bf1e151f02f221e977c91a90a0e91","current_duty_dependent_root":"0x5e0043f107cb57913498fbf2f99ff55e730bf1e151f02f221e977c91a90a0e91","execution_optimistic":false}"#;
+
+    #[test]
+    fn decode_sse_chunks() {
+        let body = format!(": keep-alive\n\nevent: head\r\ndata: {HEAD}\r\n\r\nevent: block\ndata: {{\"a\":\ndata: 1}}\n\n");
+        let mut decoder = SseDecoder::default();
+
+        // Feed the body in small chunks, splitting lines.
+        let events: Vec<_> =
+            body.as_bytes().chunks(7).flat_map(|chunk| decoder.push(chunk)).collect();
+        assert_eq!(
+            events,
+            vec![
+                SseEvent { event: "head".to_string(), data: HEAD.to_string() },
+                SseEvent { event: "block".to_string(), data: "{\"a\":\n1}".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_head_event() {
+        let event = BeaconEvent::decode("head", HEAD).unwrap();
+        assert_eq!(event.topic(), BeaconNodeEventTopic::Head);
+        let BeaconEvent::Head(head) = event else { unreachable!() };
+        assert_eq!(head.slot, 10);
+
+        assert!(BeaconEvent::decode("blob_sidecar", HEAD).is_err());
+        assert!(BeaconEvent::decode("unknown", HEAD).is_err());
+    }
+}
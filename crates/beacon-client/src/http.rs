@@ -0,0 +1,115 @@
+//! HTTP helpers shared by the beacon node and relay clients.
+
+use alloy_transport::{TransportError, TransportErrorKind, TransportResult};
+use alloy_transport_http::reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use std::fmt::Display;
+use tracing::{debug, trace};
+use url::Url;
+
+/// Builds the URL of the given endpoint, appending the path segments to the base URL.
+pub(crate) fn endpoint(base: &Url, segments: &[&str]) -> TransportResult<Url> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|_| TransportErrorKind::custom_str("URL cannot be a base"))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+/// Appends a comma-separated list to the query of the URL, if it is not empty.
+pub(crate) fn append_list<T: Display>(
+    url: &mut Url,
+    key: &str,
+    values: impl IntoIterator<Item = T>,
+) {
+    let value = values.into_iter().map(|value| value.to_string()).collect::<Vec<_>>().join(",");
+    if !value.is_empty() {
+        url.query_pairs_mut().append_pair(key, &value);
+    }
+}
+
+/// Sends the request, returning an HTTP error carrying the body if the response is not
+/// successful.
+pub(crate) async fn send(req: RequestBuilder) -> TransportResult<Response> {
+    let resp = req.send().await.map_err(TransportErrorKind::custom)?;
+    let status = resp.status();
+
+    debug!(%status, url = %resp.url(), "received response from server");
+
+    if status.is_success() {
+        return Ok(resp);
+    }
+
+    let body = resp.text().await.unwrap_or_default();
+    Err(TransportErrorKind::http_error(status.as_u16(), body))
+}
+
+/// Sends the request, and deserializes the JSON body of the response.
+pub(crate) async fn send_json<T: DeserializeOwned>(req: RequestBuilder) -> TransportResult<T> {
+    let body = send(req).await?.bytes().await.map_err(TransportErrorKind::custom)?;
+
+    debug!(bytes = body.len(), "retrieved response body. Use `trace` for full body");
+    trace!(body = %String::from_utf8_lossy(&body), "response body");
+
+    serde_json::from_slice(&body)
+        .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+    use url::Url;
+
+    /// Serves the given HTTP responses, as `(status, content type, body)`, one per connection.
+    ///
+    /// Returns the URL of the server, and a handle resolving to the requests it received.
+    pub(crate) async fn serve(
+        responses: Vec<(&'static str, &'static str, String)>,
+    ) -> (Url, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for (status, content_type, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                requests.push(read_request(&mut socket).await);
+
+                let response = format!(
+                    "HTTP/1.1 {status}\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    /// Reads a full HTTP request, including its body.
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buf = [0; 4096];
+        loop {
+            let len = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..len]);
+
+            let text = String::from_utf8_lossy(&request);
+            let complete = len == 0
+                || text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                    let content_length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |len| len.parse().unwrap());
+                    body.len() >= content_length
+                });
+            if complete {
+                return text.into_owned();
+            }
+        }
+    }
+}
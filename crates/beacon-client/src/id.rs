@@ -0,0 +1,136 @@
+use alloy_primitives::B256;
+use alloy_rpc_types_beacon::BlsPublicKey;
+use std::fmt;
+
+/// Identifier of a beacon block, the `block_id` of the beacon API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlockId {
+    /// The canonical head of the node.
+    #[default]
+    Head,
+    /// The genesis block.
+    Genesis,
+    /// The latest finalized block.
+    Finalized,
+    /// The block at the given slot.
+    Slot(u64),
+    /// The block with the given root.
+    Root(B256),
+}
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Head => f.write_str("head"),
+            Self::Genesis => f.write_str("genesis"),
+            Self::Finalized => f.write_str("finalized"),
+            Self::Slot(slot) => write!(f, "{slot}"),
+            Self::Root(root) => write!(f, "{root:?}"),
+        }
+    }
+}
+
+impl From<u64> for BlockId {
+    fn from(slot: u64) -> Self {
+        Self::Slot(slot)
+    }
+}
+
+impl From<B256> for BlockId {
+    fn from(root: B256) -> Self {
+        Self::Root(root)
+    }
+}
+
+/// Identifier of a beacon state, the `state_id` of the beacon API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StateId {
+    /// The state of the canonical head of the node.
+    #[default]
+    Head,
+    /// The genesis state.
+    Genesis,
+    /// The latest finalized state.
+    Finalized,
+    /// The latest justified state.
+    Justified,
+    /// The state at the given slot.
+    Slot(u64),
+    /// The state with the given root.
+    Root(B256),
+}
+
+impl fmt::Display for StateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Head => f.write_str("head"),
+            Self::Genesis => f.write_str("genesis"),
+            Self::Finalized => f.write_str("finalized"),
+            Self::Justified => f.write_str("justified"),
+            Self::Slot(slot) => write!(f, "{slot}"),
+            Self::Root(root) => write!(f, "{root:?}"),
+        }
+    }
+}
+
+impl From<u64> for StateId {
+    fn from(slot: u64) -> Self {
+        Self::Slot(slot)
+    }
+}
+
+impl From<B256> for StateId {
+    fn from(root: B256) -> Self {
+        Self::Root(root)
+    }
+}
+
+/// Identifier of a validator, either its index in the registry or its public key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValidatorId {
+    /// The index of the validator in the registry.
+    Index(u64),
+    /// The BLS public key of the validator.
+    Pubkey(BlsPublicKey),
+}
+
+impl fmt::Display for ValidatorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Pubkey(pubkey) => write!(f, "{pubkey:?}"),
+        }
+    }
+}
+
+impl From<u64> for ValidatorId {
+    fn from(index: u64) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl From<BlsPublicKey> for ValidatorId {
+    fn from(pubkey: BlsPublicKey) -> Self {
+        Self::Pubkey(pubkey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_ids() {
+        assert_eq!(BlockId::Head.to_string(), "head");
+        assert_eq!(BlockId::from(42).to_string(), "42");
+        assert_eq!(
+            BlockId::from(B256::repeat_byte(0xab)).to_string(),
+            format!("0x{}", "ab".repeat(32))
+        );
+        assert_eq!(StateId::Justified.to_string(), "justified");
+        assert_eq!(
+            ValidatorId::from(BlsPublicKey::repeat_byte(0x01)).to_string(),
+            format!("0x{}", "01".repeat(48))
+        );
+    }
+}
@@ -0,0 +1,23 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/favicon.ico"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+pub use client::BeaconClient;
+
+mod events;
+pub use events::BeaconEvent;
+
+mod http;
+
+mod id;
+pub use id::{BlockId, StateId, ValidatorId};
+
+mod relay;
+pub use relay::{RelayClient, MAX_RELAY_PAGE_SIZE};
+
+pub use alloy_rpc_types_beacon as types;
@@ -0,0 +1,209 @@
+use crate::http::{endpoint, send, send_json};
+use alloy_rpc_types_beacon::{
+    relay::{
+        BidTrace, BuilderBlocksReceivedQuery, ProposerPayloadsDeliveredQuery,
+        SubmitBlockRequestQuery, Validator, ValidatorRegistration,
+    },
+    BlsPublicKey,
+};
+use alloy_transport::TransportResult;
+use alloy_transport_http::reqwest::Client;
+use futures::Stream;
+use serde::Serialize;
+use std::collections::VecDeque;
+use url::Url;
+
+/// The maximum number of entries returned by the relay data API in a single page.
+pub const MAX_RELAY_PAGE_SIZE: u64 = 200;
+
+/// A client for the data and builder APIs of a MEV-boost relay.
+///
+/// See the [relay specification](https://flashbots.github.io/relay-specs/) for details on each
+/// endpoint.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_beacon_client::{types::relay::ProposerPayloadsDeliveredQuery, RelayClient};
+/// use futures::TryStreamExt;
+///
+/// let client = RelayClient::new("https://boost-relay.flashbots.net".parse()?);
+/// let query = ProposerPayloadsDeliveredQuery::default().cursor(10_000_000);
+/// let traces: Vec<_> = client.proposer_payloads_delivered_stream(query).try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RelayClient {
+    client: Client,
+    url: Url,
+}
+
+impl RelayClient {
+    /// Create a new [`RelayClient`] for the relay at the given URL.
+    pub fn new(url: Url) -> Self {
+        Self { client: Default::default(), url }
+    }
+
+    /// Create a new [`RelayClient`] with the given client, for the relay at the given URL.
+    pub const fn with_client(client: Client, url: Url) -> Self {
+        Self { client, url }
+    }
+
+    /// Get a reference to the client.
+    pub const fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get a reference to the URL of the relay.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the payloads delivered to proposers matching the query.
+    ///
+    /// `GET /relay/v1/data/bidtraces/proposer_payload_delivered`
+    pub async fn proposer_payloads_delivered(
+        &self,
+        query: &ProposerPayloadsDeliveredQuery,
+    ) -> TransportResult<Vec<BidTrace>> {
+        let url =
+            self.endpoint(&["relay", "v1", "data", "bidtraces", "proposer_payload_delivered"])?;
+        send_json(self.client.get(url).query(query)).await
+    }
+
+    /// Returns a stream of all the payloads delivered to proposers matching the query, walking
+    /// back from the query `cursor` (or the latest slot) by pages of `limit` entries.
+    ///
+    /// Pages are walked by slot, so the `order_by` of the query is ignored. The stream ends after
+    /// the first page shorter than the limit, or after yielding an error.
+    pub fn proposer_payloads_delivered_stream(
+        &self,
+        mut query: ProposerPayloadsDeliveredQuery,
+    ) -> impl Stream<Item = TransportResult<BidTrace>> + Send + 'static {
+        let limit = query.limit.unwrap_or(MAX_RELAY_PAGE_SIZE);
+        query.limit = Some(limit);
+        query.order_by = None;
+
+        let state = (self.clone(), Some(query), VecDeque::<BidTrace>::new());
+        futures::stream::try_unfold(state, move |(this, mut query, mut pending)| async move {
+            loop {
+                if let Some(trace) = pending.pop_front() {
+                    return Ok(Some((trace, (this, query, pending))));
+                }
+
+                let Some(current) = query.take() else { return Ok(None) };
+                let page = this.proposer_payloads_delivered(&current).await?;
+
+                // The cursor is inclusive, so the next page starts at the slot before the oldest
+                // entry of this one.
+                let next = page.last().and_then(|trace| trace.slot.checked_sub(1));
+                if page.len() as u64 >= limit && current.slot.is_none() {
+                    query = next.map(|cursor| current.cursor(cursor));
+                }
+                pending.extend(page);
+            }
+        })
+    }
+
+    /// Returns the builder block submissions matching the query, which were verified
+    /// successfully.
+    ///
+    /// `GET /relay/v1/data/bidtraces/builder_blocks_received`
+    pub async fn builder_blocks_received(
+        &self,
+        query: &BuilderBlocksReceivedQuery,
+    ) -> TransportResult<Vec<BidTrace>> {
+        let url =
+            self.endpoint(&["relay", "v1", "data", "bidtraces", "builder_blocks_received"])?;
+        send_json(self.client.get(url).query(query)).await
+    }
+
+    /// Returns the latest registration of the validator with the given public key.
+    ///
+    /// `GET /relay/v1/data/validator_registration`
+    pub async fn validator_registration(
+        &self,
+        pubkey: BlsPublicKey,
+    ) -> TransportResult<ValidatorRegistration> {
+        let url = self.endpoint(&["relay", "v1", "data", "validator_registration"])?;
+        send_json(self.client.get(url).query(&[("pubkey", format!("{pubkey:?}"))])).await
+    }
+
+    /// Returns the registrations of the validators proposing in the current and next epochs.
+    ///
+    /// `GET /relay/v1/builder/validators`
+    pub async fn validators(&self) -> TransportResult<Vec<Validator>> {
+        let url = self.endpoint(&["relay", "v1", "builder", "validators"])?;
+        send_json(self.client.get(url)).await
+    }
+
+    /// Submits a block to the relay, as one of the `SignedBidSubmission` types.
+    ///
+    /// `POST /relay/v1/builder/blocks`
+    pub async fn submit_block<T: Serialize + ?Sized>(
+        &self,
+        submission: &T,
+        query: &SubmitBlockRequestQuery,
+    ) -> TransportResult<()> {
+        let url = self.endpoint(&["relay", "v1", "builder", "blocks"])?;
+        send(self.client.post(url).query(query).json(submission)).await?;
+        Ok(())
+    }
+
+    fn endpoint(&self, segments: &[&str]) -> TransportResult<Url> {
+        endpoint(&self.url, segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test::serve;
+    use futures::TryStreamExt;
+
+    fn traces(slots: impl IntoIterator<Item = u64>) -> String {
+        let traces: Vec<_> =
+            slots.into_iter().map(|slot| BidTrace { slot, ..Default::default() }).collect();
+        serde_json::to_string(&traces).unwrap()
+    }
+
+    #[tokio::test]
+    async fn proposer_payloads_delivered_stream() {
+        let (url, server) = serve(vec![
+            ("200 OK", "application/json", traces([100, 99])),
+            ("200 OK", "application/json", traces([97, 95])),
+            ("200 OK", "application/json", traces([90])),
+        ])
+        .await;
+
+        let client = RelayClient::new(url);
+        let query = ProposerPayloadsDeliveredQuery::default().limit(2).order_by_desc();
+        let traces: Vec<_> =
+            client.proposer_payloads_delivered_stream(query).try_collect().await.unwrap();
+        assert_eq!(
+            traces.iter().map(|trace| trace.slot).collect::<Vec<_>>(),
+            [100, 99, 97, 95, 90]
+        );
+
+        let requests = server.await.unwrap();
+        let path = "GET /relay/v1/data/bidtraces/proposer_payload_delivered";
+        assert!(requests[0].starts_with(&format!("{path}?limit=2 ")));
+        assert!(requests[1].starts_with(&format!("{path}?cursor=98&limit=2 ")));
+        assert!(requests[2].starts_with(&format!("{path}?cursor=94&limit=2 ")));
+    }
+
+    #[tokio::test]
+    async fn submit_block_with_cancellations() {
+        let (url, server) = serve(vec![("200 OK", "application/json", String::new())]).await;
+
+        let client = RelayClient::new(url);
+        let trace = BidTrace { slot: 1, ..Default::default() };
+        client.submit_block(&trace, &SubmitBlockRequestQuery::cancellations()).await.unwrap();
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /relay/v1/builder/blocks?cancellations=1 "));
+        assert!(requests[0].ends_with(&serde_json::to_string(&trace).unwrap()));
+    }
+}
@@ -79,4 +79,4 @@ pub const EIP4844_TX_TYPE_ID: u8 = 3;
 pub const EIP7702_TX_TYPE_ID: u8 = 4;
 
 /// Identifier for an Sponsored transaction.
-pub const SPONSORED_TX_TYPE_ID: u8 = 100;
\ No newline at end of file
+pub const SPONSORED_TX_TYPE_ID: u8 = 100;
@@ -36,8 +36,9 @@ pub mod transaction;
 #[cfg(feature = "kzg")]
 pub use transaction::BlobTransactionValidationError;
 pub use transaction::{
-    SignableTransaction, Transaction, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant,
-    TxEip4844WithSidecar, TxEip7702, TxEnvelope, TxLegacy, TxType, Typed2718, TypedTransaction, TxSponsored
+    SignableTransaction, SponsoredTxExpired, Transaction, TxEip1559, TxEip2930, TxEip4844,
+    TxEip4844Variant, TxEip4844WithSidecar, TxEip7702, TxEnvelope, TxLegacy, TxSponsored,
+    TxSponsoredPayer, TxType, Typed2718, TypedTransaction,
 };
 
 pub use alloy_eips::eip4844::{
@@ -43,7 +43,7 @@ pub enum ReceiptEnvelope<T = Log> {
     /// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
     #[cfg_attr(feature = "serde", serde(rename = "0x4", alias = "0x04"))]
     Eip7702(ReceiptWithBloom<Receipt<T>>),
-     /// Receipt envelope with type flag 100, containing a [Sponsored] receipt.
+    /// Receipt envelope with type flag 100, containing a [Sponsored] receipt.
     ///
     /// [Sponsored]: Sponsored
     #[cfg_attr(feature = "serde", serde(rename = "0x64", alias = "0x64"))]
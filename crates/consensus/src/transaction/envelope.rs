@@ -15,7 +15,7 @@ use alloy_primitives::{
 use alloy_rlp::{Decodable, Encodable};
 use core::fmt;
 
-use super::TxSponsored;
+use super::{SponsoredTxExpired, TxSponsored};
 
 /// Ethereum `TransactionType` flags as specified in EIPs [2718], [1559], [2930],
 /// [4844], and [7702].
@@ -268,6 +268,12 @@ impl TxEnvelope {
         matches!(self, Self::Eip7702(_))
     }
 
+    /// Returns true if the transaction is a sponsored transaction.
+    #[inline]
+    pub const fn is_sponsored(&self) -> bool {
+        matches!(self, Self::Sponsored(_))
+    }
+
     /// Returns true if the transaction is replay protected.
     ///
     /// All non-legacy transactions are replay protected, as the chain id is
@@ -324,6 +330,24 @@ impl TxEnvelope {
         }
     }
 
+    /// Returns the [`TxSponsored`] variant if the transaction is a sponsored transaction.
+    pub const fn as_sponsored(&self) -> Option<&Signed<TxSponsored>> {
+        match self {
+            Self::Sponsored(tx) => Some(tx),
+            _ => None,
+        }
+    }
+
+    /// Checks that the transaction can still be included in a block with the given timestamp.
+    ///
+    /// Only sponsored transactions expire, this always succeeds for other transactions.
+    pub const fn validate_expiry(&self, timestamp: u64) -> Result<(), SponsoredTxExpired> {
+        match self {
+            Self::Sponsored(tx) => tx.tx().validate_expiry(timestamp),
+            _ => Ok(()),
+        }
+    }
+
     /// Recover the signer of the transaction.
     #[cfg(feature = "k256")]
     pub fn recover_signer(
@@ -339,6 +363,19 @@ impl TxEnvelope {
         }
     }
 
+    /// Recover the address paying for the gas of the transaction.
+    ///
+    /// This is the payer for sponsored transactions, and the signer for all other transactions.
+    #[cfg(feature = "k256")]
+    pub fn recover_payer(
+        &self,
+    ) -> Result<alloy_primitives::Address, alloy_primitives::SignatureError> {
+        match self {
+            Self::Sponsored(tx) => tx.recover_payer(),
+            _ => self.recover_signer(),
+        }
+    }
+
     /// Calculate the signing hash for the transaction.
     pub fn signature_hash(&self) -> B256 {
         match self {
@@ -420,9 +457,8 @@ impl Decodable for TxEnvelope {
 
 impl Decodable2718 for TxEnvelope {
     fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
-
         let ty = ty.try_into().map_err(|_| alloy_rlp::Error::Custom("unexpected tx type"))?;
-   
+
         match ty {
             TxType::Eip2930 => Ok(TxEip2930::rlp_decode_signed(buf)?.into()),
             TxType::Eip1559 => Ok(TxEip1559::rlp_decode_signed(buf)?.into()),
@@ -716,7 +752,10 @@ mod serde_from {
     //!
     //! We serialize via [`TaggedTxEnvelope`] and deserialize via
     //! [`MaybeTaggedTxEnvelope`].
-    use crate::{transaction::TxSponsored, Signed, TxEip1559, TxEip2930, TxEip4844Variant, TxEip7702, TxEnvelope, TxLegacy};
+    use crate::{
+        transaction::TxSponsored, Signed, TxEip1559, TxEip2930, TxEip4844Variant, TxEip7702,
+        TxEnvelope, TxLegacy,
+    };
 
     #[derive(Debug, serde::Deserialize)]
     pub(crate) struct UntaggedLegacy {
@@ -16,7 +16,7 @@ mod eip7702;
 pub use eip7702::TxEip7702;
 
 mod sponsored;
-pub use sponsored::TxSponsored;
+pub use sponsored::{SponsoredTxExpired, TxSponsored, TxSponsoredPayer};
 
 /// [EIP-4844] constants, helpers, and types.
 pub mod eip4844;
@@ -60,6 +60,7 @@ pub mod serde_bincode_compat {
     pub use super::{
         eip1559::serde_bincode_compat::*, eip2930::serde_bincode_compat::*,
         eip7702::serde_bincode_compat::*, legacy::serde_bincode_compat::*,
+        sponsored::serde_bincode_compat::*,
     };
 }
 
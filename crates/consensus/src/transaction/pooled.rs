@@ -336,7 +336,6 @@ impl Encodable2718 for PooledTransaction {
 
 impl Decodable2718 for PooledTransaction {
     fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
-
         let ty = ty.try_into().map_err(|_| alloy_rlp::Error::Custom("unexpected tx type"))?;
 
         match ty {
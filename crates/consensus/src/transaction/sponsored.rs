@@ -1,8 +1,11 @@
 use crate::{transaction::RlpEcdsaTx, SignableTransaction, Signed, Transaction, TxType, Typed2718};
 use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization};
-use alloy_primitives::{Bytes, ChainId, PrimitiveSignature as Signature, TxKind, B256, U256};
-use alloy_rlp::{BufMut, Decodable, Encodable};
-use core::mem;
+use alloy_primitives::{
+    keccak256, Address, Bytes, ChainId, PrimitiveSignature as Signature, SignatureError, TxKind,
+    B256, U256,
+};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+use core::{fmt, mem};
 
 /// A Sponsored transaction.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -64,10 +67,14 @@ pub struct TxSponsored {
     /// data: An unlimited size byte array specifying the
     /// input data of the message call, formally Td.
     pub input: Bytes,
-    /// Sponsored transaction fields.
+    /// Timestamp from which the transaction can no longer be included in a block.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub expired_time: u64,
+    /// The `v` value (y-parity) of the gas payer's signature.
     pub payer_v: U256,
+    /// The `r` value of the gas payer's signature.
     pub payer_r: U256,
+    /// The `s` value of the gas payer's signature.
     pub payer_s: U256,
 }
 
@@ -97,6 +104,92 @@ impl TxSponsored {
     }
 }
 
+impl TxSponsored {
+    /// Returns `true` if the transaction can no longer be included in a block with the given
+    /// timestamp.
+    pub const fn is_expired(&self, timestamp: u64) -> bool {
+        self.expired_time <= timestamp
+    }
+
+    /// Checks that the transaction can still be included in a block with the given timestamp.
+    pub const fn validate_expiry(&self, timestamp: u64) -> Result<(), SponsoredTxExpired> {
+        if self.is_expired(timestamp) {
+            return Err(SponsoredTxExpired { expired_time: self.expired_time, timestamp });
+        }
+        Ok(())
+    }
+
+    /// Returns the signature of the gas payer.
+    ///
+    /// Returns an error if `payer_v` is not a valid y-parity value.
+    pub fn payer_signature(&self) -> Result<Signature, SignatureError> {
+        let y_parity = match u64::try_from(self.payer_v) {
+            Ok(0) => false,
+            Ok(1) => true,
+            Ok(v) => return Err(SignatureError::InvalidParity(v)),
+            Err(_) => return Err(SignatureError::InvalidParity(u64::MAX)),
+        };
+        Ok(Signature::new(self.payer_r, self.payer_s, y_parity))
+    }
+
+    /// Sets the signature of the gas payer.
+    pub fn set_payer_signature(&mut self, signature: &Signature) {
+        self.payer_v = U256::from(signature.v() as u8);
+        self.payer_r = signature.r();
+        self.payer_s = signature.s();
+    }
+
+    /// Returns the payload the gas payer signs for the given `sender`.
+    ///
+    /// See [`TxSponsoredPayer`].
+    pub fn payer_signing_tx(&self, sender: Address) -> TxSponsoredPayer {
+        TxSponsoredPayer { tx: self.clone(), sender }
+    }
+
+    /// Calculates the hash the gas payer signs for the given `sender`.
+    pub fn payer_signature_hash(&self, sender: Address) -> B256 {
+        self.payer_signing_tx(sender).signature_hash()
+    }
+
+    /// Recovers the address of the gas payer, given the `sender` of the transaction.
+    #[cfg(feature = "k256")]
+    pub fn recover_payer(&self, sender: Address) -> Result<Address, SignatureError> {
+        self.payer_signature()?.recover_address_from_prehash(&self.payer_signature_hash(sender))
+    }
+}
+
+/// Error returned when a sponsored transaction has expired relative to a block timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SponsoredTxExpired {
+    /// The expiry of the transaction.
+    pub expired_time: u64,
+    /// The block timestamp the transaction was validated against.
+    pub timestamp: u64,
+}
+
+impl fmt::Display for SponsoredTxExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sponsored transaction expired at {}, block timestamp is {}",
+            self.expired_time, self.timestamp
+        )
+    }
+}
+
+impl core::error::Error for SponsoredTxExpired {}
+
+#[cfg(feature = "k256")]
+impl Signed<TxSponsored> {
+    /// Recovers the address of the gas payer.
+    ///
+    /// The payer signature commits to the sender, which is recovered first.
+    pub fn recover_payer(&self) -> Result<Address, SignatureError> {
+        let sender = self.recover_signer()?;
+        self.tx().recover_payer(sender)
+    }
+}
+
 impl RlpEcdsaTx for TxSponsored {
     const DEFAULT_TX_TYPE: u8 = { Self::tx_type() as u8 };
 
@@ -306,11 +399,173 @@ impl Decodable for TxSponsored {
     }
 }
 
+/// The payload signed by the gas payer of a [`TxSponsored`].
+///
+/// The payer signs over every field of the transaction except the signatures, as well as the
+/// address of the sender. This type implements [`SignableTransaction`], which allows any
+/// transaction signer to produce the payer signature.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[doc(alias = "SponsoredPayerTx")]
+pub struct TxSponsoredPayer {
+    /// The sponsored transaction.
+    pub tx: TxSponsored,
+    /// The sender of the transaction, whose gas is paid for.
+    pub sender: Address,
+}
+
+impl TxSponsoredPayer {
+    /// Outputs the length of the payer signing fields, without a RLP header.
+    fn fields_len(&self) -> usize {
+        let tx = &self.tx;
+        tx.chain_id.length()
+            + self.sender.length()
+            + tx.nonce.length()
+            + tx.max_priority_fee_per_gas.length()
+            + tx.max_fee_per_gas.length()
+            + tx.gas_limit.length()
+            + tx.to.length()
+            + tx.value.length()
+            + tx.input.0.length()
+            + tx.expired_time.length()
+    }
+
+    /// Consumes the type and returns the transaction with the payer signature set.
+    pub fn into_tx_with_signature(mut self, signature: &Signature) -> TxSponsored {
+        self.tx.set_payer_signature(signature);
+        self.tx
+    }
+}
+
+impl Transaction for TxSponsoredPayer {
+    #[inline]
+    fn chain_id(&self) -> Option<ChainId> {
+        self.tx.chain_id()
+    }
+
+    #[inline]
+    fn nonce(&self) -> u64 {
+        self.tx.nonce()
+    }
+
+    #[inline]
+    fn gas_limit(&self) -> u64 {
+        self.tx.gas_limit()
+    }
+
+    #[inline]
+    fn gas_price(&self) -> Option<u128> {
+        self.tx.gas_price()
+    }
+
+    #[inline]
+    fn max_fee_per_gas(&self) -> u128 {
+        self.tx.max_fee_per_gas()
+    }
+
+    #[inline]
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.tx.max_priority_fee_per_gas()
+    }
+
+    #[inline]
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.tx.max_fee_per_blob_gas()
+    }
+
+    #[inline]
+    fn priority_fee_or_price(&self) -> u128 {
+        self.tx.priority_fee_or_price()
+    }
+
+    fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        self.tx.effective_gas_price(base_fee)
+    }
+
+    #[inline]
+    fn is_dynamic_fee(&self) -> bool {
+        self.tx.is_dynamic_fee()
+    }
+
+    #[inline]
+    fn kind(&self) -> TxKind {
+        self.tx.kind()
+    }
+
+    #[inline]
+    fn is_create(&self) -> bool {
+        self.tx.is_create()
+    }
+
+    #[inline]
+    fn value(&self) -> U256 {
+        self.tx.value()
+    }
+
+    #[inline]
+    fn input(&self) -> &Bytes {
+        self.tx.input()
+    }
+
+    #[inline]
+    fn access_list(&self) -> Option<&AccessList> {
+        None
+    }
+
+    #[inline]
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        None
+    }
+
+    #[inline]
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        None
+    }
+}
+
+impl Typed2718 for TxSponsoredPayer {
+    fn ty(&self) -> u8 {
+        TxType::Sponsored as u8
+    }
+}
+
+impl SignableTransaction<Signature> for TxSponsoredPayer {
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        self.tx.chain_id = chain_id;
+    }
+
+    /// Encodes the payer signing payload:
+    /// `0x64 || rlp([chain_id, sender, nonce, max_priority_fee_per_gas, max_fee_per_gas,
+    /// gas_limit, to, value, input, expired_time])`.
+    fn encode_for_signing(&self, out: &mut dyn alloy_rlp::BufMut) {
+        let tx = &self.tx;
+        out.put_u8(TxSponsored::tx_type() as u8);
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        tx.chain_id.encode(out);
+        self.sender.encode(out);
+        tx.nonce.encode(out);
+        tx.max_priority_fee_per_gas.encode(out);
+        tx.max_fee_per_gas.encode(out);
+        tx.gas_limit.encode(out);
+        tx.to.encode(out);
+        tx.value.encode(out);
+        tx.input.0.encode(out);
+        tx.expired_time.encode(out);
+    }
+
+    fn payload_len_for_signature(&self) -> usize {
+        Header { list: true, payload_length: self.fields_len() }.length_with_payload() + 1
+    }
+
+    fn into_signed(self, signature: Signature) -> Signed<Self> {
+        let hash = keccak256(self.encoded_for_signing());
+        Signed::new_unchecked(self, signature, hash)
+    }
+}
+
 /// Bincode-compatible [`TxSponsored`] serde implementation.
 #[cfg(all(feature = "serde", feature = "serde-bincode-compat"))]
 pub(super) mod serde_bincode_compat {
     use alloc::borrow::Cow;
-    use alloy_eips::eip2930::AccessList;
     use alloy_primitives::{Bytes, ChainId, TxKind, U256};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
     use serde_with::{DeserializeAs, SerializeAs};
@@ -437,21 +692,82 @@ pub(super) mod serde_bincode_compat {
 
 #[cfg(test)]
 mod tests {
-    use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::hex;
-
-    use crate::{transaction::{PooledTransaction, RlpEcdsaTx}, TxSponsored};
-
-    use super::*;
-
- 
-
-
-#[test]
-fn test_decode_sponsored_tx() {
-    let tx_bytes = hex!("64f8fb8207e4808504a817c8008504a817c8008301aa12940b7007c13325c48911f73a2dad5fa5dcbf808adc80b844095ea7b30000000000000000000000008a28c188a067dfa6aaec36e2b67b34d2c3042df90fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff8466d29dd201a0a874e9dbb63262b7c3500c3130480c4877cebe91203098b3febc701db4940ff8a05dc5f279f16ae525781b938a71935525f5baa42a7e2207a02f45a0d84e72357501a0697557c9fff6a7a781c4d96c9f9fa7ce6f6e3f8bb37d02544925b9d4e4ae83f7a016cb0fcfcfbfd2d05bde7aac1a7a9b28faa0434851d6a7257f53f6936d72e0b5");
-    let decoded = TxSponsored::eip2718_decode(&mut &tx_bytes[..]).unwrap();
-    dbg!(decoded);
+    use super::SponsoredTxExpired;
+    use crate::{transaction::RlpEcdsaTx, TxSponsored};
+    use alloy_primitives::{hex, U256};
+
+    #[test]
+    fn test_validate_expiry() {
+        let tx = TxSponsored { expired_time: 1_700_000_000, ..Default::default() };
+        assert!(tx.validate_expiry(1_699_999_999).is_ok());
+        assert_eq!(
+            tx.validate_expiry(1_700_000_000),
+            Err(SponsoredTxExpired { expired_time: 1_700_000_000, timestamp: 1_700_000_000 })
+        );
+        assert!(tx.is_expired(1_700_000_001));
+    }
+
+    #[test]
+    fn test_decode_sponsored_tx() {
+        let tx_bytes = hex!("64f8fb8207e4808504a817c8008504a817c8008301aa12940b7007c13325c48911f73a2dad5fa5dcbf808adc80b844095ea7b30000000000000000000000008a28c188a067dfa6aaec36e2b67b34d2c3042df90fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff8466d29dd201a0a874e9dbb63262b7c3500c3130480c4877cebe91203098b3febc701db4940ff8a05dc5f279f16ae525781b938a71935525f5baa42a7e2207a02f45a0d84e72357501a0697557c9fff6a7a781c4d96c9f9fa7ce6f6e3f8bb37d02544925b9d4e4ae83f7a016cb0fcfcfbfd2d05bde7aac1a7a9b28faa0434851d6a7257f53f6936d72e0b5");
+        let decoded = TxSponsored::eip2718_decode(&mut &tx_bytes[..]).unwrap();
+        assert_eq!(decoded.tx().chain_id, 2020);
+        assert_eq!(decoded.tx().expired_time, 0x66d29dd2);
+    }
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn test_recover_payer() {
+        use crate::SignableTransaction;
+        use alloy_primitives::{
+            address, b256, Address, PrimitiveSignature as Signature, TxKind, B256,
+        };
+        use k256::ecdsa::SigningKey;
+
+        let sender_key = SigningKey::from_slice(
+            &b256!("0000000000000000000000000000000000000000000000000000000000000001")[..],
+        )
+        .unwrap();
+        let payer_key = SigningKey::from_slice(
+            &b256!("0000000000000000000000000000000000000000000000000000000000000002")[..],
+        )
+        .unwrap();
+        let sender = address!("7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+        let payer = address!("2B5AD5c4795c026514f8317c7a215E218DcCD6cF");
+
+        let mut tx = TxSponsored {
+            chain_id: 2021,
+            nonce: 1,
+            max_priority_fee_per_gas: 20_000_000_000,
+            max_fee_per_gas: 20_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::repeat_byte(0xde)),
+            expired_time: 1_700_000_000,
+            ..Default::default()
+        };
+
+        let sign = |hash: B256, key: &SigningKey| {
+            let (sig, recid) = key.sign_prehash_recoverable(hash.as_slice()).unwrap();
+            Signature::from((sig, recid))
+        };
+
+        let payer_sig = sign(tx.payer_signature_hash(sender), &payer_key);
+        tx.set_payer_signature(&payer_sig);
+        assert_eq!(tx.payer_signature().unwrap(), payer_sig);
+        assert_eq!(tx.recover_payer(sender).unwrap(), payer);
+
+        let sender_sig = sign(tx.signature_hash(), &sender_key);
+        let signed = tx.into_signed(sender_sig);
+        assert_eq!(signed.recover_signer().unwrap(), sender);
+        assert_eq!(signed.recover_payer().unwrap(), payer);
+
+        // the payer signature is bound to the sender
+        assert_ne!(signed.tx().recover_payer(Address::ZERO).unwrap(), payer);
+    }
+
+    #[test]
+    fn test_invalid_payer_parity() {
+        let tx = TxSponsored { payer_v: U256::from(27), ..Default::default() };
+        assert!(tx.payer_signature().is_err());
+    }
 }
-
-}
\ No newline at end of file
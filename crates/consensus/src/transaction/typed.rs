@@ -89,6 +89,12 @@ impl From<TxEip7702> for TypedTransaction {
     }
 }
 
+impl From<TxSponsored> for TypedTransaction {
+    fn from(tx: TxSponsored) -> Self {
+        Self::Sponsored(tx)
+    }
+}
+
 impl From<TxEnvelope> for TypedTransaction {
     fn from(envelope: TxEnvelope) -> Self {
         match envelope {
@@ -98,7 +104,6 @@ impl From<TxEnvelope> for TypedTransaction {
             TxEnvelope::Eip4844(tx) => Self::Eip4844(tx.strip_signature()),
             TxEnvelope::Eip7702(tx) => Self::Eip7702(tx.strip_signature()),
             TxEnvelope::Sponsored(tx) => Self::Sponsored(tx.strip_signature()),
-
         }
     }
 }
@@ -395,7 +400,10 @@ mod serde_from {
     //!
     //! We serialize via [`TaggedTypedTransaction`] and deserialize via
     //! [`MaybeTaggedTypedTransaction`].
-    use crate::{transaction::TxSponsored, TxEip1559, TxEip2930, TxEip4844Variant, TxEip7702, TxLegacy, TypedTransaction};
+    use crate::{
+        transaction::TxSponsored, TxEip1559, TxEip2930, TxEip4844Variant, TxEip7702, TxLegacy,
+        TypedTransaction,
+    };
 
     #[derive(Debug, serde::Deserialize)]
     #[serde(untagged)]
@@ -466,6 +466,11 @@ impl<T, P: Provider<N>, D: CallDecoder, N: Network> CallBuilder<T, P, D, N> {
         call.into()
     }
 
+    /// Returns the decoder of the call output.
+    pub(crate) const fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
     /// Decodes the output of a contract function using the provided decoder.
     #[inline]
     pub fn decode_output(&self, data: Bytes, validate: bool) -> Result<D::CallOutput> {
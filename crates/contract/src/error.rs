@@ -1,5 +1,5 @@
 use alloy_dyn_abi::Error as AbiError;
-use alloy_primitives::Selector;
+use alloy_primitives::{Bytes, Selector};
 use alloy_provider::PendingTransactionError;
 use alloy_transport::TransportError;
 use thiserror::Error;
@@ -25,6 +25,16 @@ pub enum Error {
     /// The contract returned no data.
     #[error("contract call to `{0}` returned no data (\"0x\"); the called address might not be a contract")]
     ZeroData(String, #[source] AbiError),
+    /// A call of a multicall failed. Contains its index, and the data it reverted with.
+    #[error("multicall call at index {0} failed")]
+    MulticallFailure(usize, Bytes),
+    /// Added a deployment to a multicall, which can only aggregate calls.
+    #[error("cannot aggregate a deployment transaction in a multicall")]
+    MulticallDeployment,
+    /// Decoded a multicall handle which has no result, as it was returned by another builder.
+    /// Contains its index.
+    #[error("multicall has no result for the call at index {0}")]
+    MulticallMissingResult(usize),
     /// An error occurred ABI encoding or decoding.
     #[error(transparent)]
     AbiError(#[from] AbiError),
@@ -1,7 +1,9 @@
 use crate::Error;
 use alloy_network::Ethereum;
 use alloy_primitives::{Address, LogData, B256};
-use alloy_provider::{FilterPollerBuilder, Network, Provider};
+use alloy_provider::{
+    CanonicalLogs, ChainEvent, DeliveryPolicy, FilterPollerBuilder, LogStream, Network, Provider,
+};
 use alloy_rpc_types_eth::{BlockNumberOrTag, Filter, FilterBlockOption, Log, Topic, ValueOrArray};
 use alloy_sol_types::SolEvent;
 use alloy_transport::TransportResult;
@@ -64,6 +66,14 @@ impl<T, P: Provider<N>, E: SolEvent, N: Network> Event<T, P, E, N> {
         self.provider.get_logs(&self.filter).await
     }
 
+    /// Queries the blockchain for the selected filter in chunks of its block range, for providers
+    /// capping the block range or the number of results of `eth_getLogs`.
+    ///
+    /// Returns a stream of decoded events and raw logs. See [`LogStream`] for details.
+    pub fn query_paginated(&self) -> EventLogStream<E> {
+        self.provider.get_logs_paginated(&self.filter).into()
+    }
+
     /// Watches for events that match the filter.
     ///
     /// Returns a stream of decoded events and raw logs.
@@ -74,6 +84,15 @@ impl<T, P: Provider<N>, E: SolEvent, N: Network> Event<T, P, E, N> {
         Ok(poller.into())
     }
 
+    /// Watches for events of the canonical chain that match the filter, delivering them as
+    /// required by the given [`DeliveryPolicy`].
+    ///
+    /// Returns a stream of decoded events and raw logs, and of reorganisations. See
+    /// [`CanonicalLogs`] for details.
+    pub fn watch_with_policy(&self, policy: DeliveryPolicy) -> CanonicalEventStream<E, N> {
+        self.provider.watch_logs_with_policy(&self.filter, policy).into()
+    }
+
     /// Subscribes to the stream of events that match the filter.
     ///
     /// Returns a stream of decoded events and raw logs.
@@ -170,6 +189,105 @@ impl<T, P: Clone, E, N> Event<T, &P, E, N> {
     }
 }
 
+/// A stream of the events matching a filter, queried in chunks of its block range.
+///
+/// Pagination configuration is available through the [`logs`](Self::logs) field.
+pub struct EventLogStream<E> {
+    /// The inner log stream.
+    pub logs: LogStream,
+    _phantom: PhantomData<E>,
+}
+
+impl<E> fmt::Debug for EventLogStream<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventLogStream")
+            .field("logs", &self.logs)
+            .field("event_type", &format_args!("{}", std::any::type_name::<E>()))
+            .finish()
+    }
+}
+
+impl<E> From<LogStream> for EventLogStream<E> {
+    fn from(logs: LogStream) -> Self {
+        Self { logs, _phantom: PhantomData }
+    }
+}
+
+impl<E: SolEvent> EventLogStream<E> {
+    /// Sets the number of blocks queried by a single request.
+    ///
+    /// See [`LogStream::with_chunk_size`].
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.logs = self.logs.with_chunk_size(chunk_size);
+        self
+    }
+
+    /// Sets the maximum number of requests in flight.
+    ///
+    /// See [`LogStream::with_concurrency`].
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.logs = self.logs.with_concurrency(concurrency);
+        self
+    }
+
+    /// Returns a stream that yields the decoded event and the raw log, in order.
+    ///
+    /// The stream ends after the first error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<(E, Log), Error>> + Unpin {
+        self.logs.map(|log| {
+            let log = log?;
+            Ok((decode_log(&log)?, log))
+        })
+    }
+}
+
+/// A stream of the events of the canonical chain matching a filter, reporting reorganisations.
+///
+/// Configuration is available through the [`logs`](Self::logs) field.
+pub struct CanonicalEventStream<E, N: Network = Ethereum> {
+    /// The inner log stream.
+    pub logs: CanonicalLogs<N>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E, N: Network> fmt::Debug for CanonicalEventStream<E, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalEventStream")
+            .field("logs", &self.logs)
+            .field("event_type", &format_args!("{}", std::any::type_name::<E>()))
+            .finish()
+    }
+}
+
+impl<E, N: Network> From<CanonicalLogs<N>> for CanonicalEventStream<E, N> {
+    fn from(logs: CanonicalLogs<N>) -> Self {
+        Self { logs, _phantom: PhantomData }
+    }
+}
+
+impl<E: SolEvent, N: Network> CanonicalEventStream<E, N> {
+    /// Sets the number of blocks tracked to detect reorganisations.
+    ///
+    /// See [`CanonicalLogs::with_window`].
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.logs = self.logs.with_window(window);
+        self
+    }
+
+    /// Returns a stream that yields the decoded event and the raw log, and reorganisations.
+    ///
+    /// Logs of dropped blocks are yielded again with `removed` set. The stream yields the error
+    /// of a failed request, and resumes with the next block.
+    pub fn into_stream(self) -> impl Stream<Item = Result<ChainEvent<(E, Log)>, Error>> + Unpin {
+        self.logs.map(|event| {
+            Ok(match event? {
+                ChainEvent::Item(log) => ChainEvent::Item((decode_log(&log)?, log)),
+                ChainEvent::Reorg(reorg) => ChainEvent::Reorg(reorg),
+            })
+        })
+    }
+}
+
 /// An event poller.
 ///
 /// Polling configuration is available through the [`poller`](Self::poller) field.
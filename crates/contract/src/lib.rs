@@ -16,7 +16,7 @@ mod error;
 pub use error::*;
 
 mod event;
-pub use event::{Event, EventPoller};
+pub use event::{CanonicalEventStream, Event, EventLogStream, EventPoller};
 
 #[cfg(feature = "pubsub")]
 pub use event::subscription::EventSubscription;
@@ -30,6 +30,11 @@ pub use instance::*;
 mod call;
 pub use call::*;
 
+mod multicall;
+pub use multicall::{
+    IMulticall3, MulticallBuilder, MulticallHandle, MulticallResults, MULTICALL3_ADDRESS,
+};
+
 // Not public API.
 // NOTE: please avoid changing the API of this module due to its use in the `sol!` macro.
 #[doc(hidden)]
@@ -0,0 +1,447 @@
+use crate::{CallBuilder, CallDecoder, Error, Result};
+use alloy_network::{Ethereum, Network, TransactionBuilder};
+use alloy_primitives::{address, Address, Bytes};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::BlockId;
+use alloy_sol_types::SolCall;
+use futures::future::try_join_all;
+use std::{marker::PhantomData, ops::Range};
+
+/// The address of the canonical [Multicall3](https://www.multicall3.com) deployment, which is the
+/// same on most chains.
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// The default maximum size of the calldata of a single `aggregate3` call.
+const DEFAULT_MAX_CALLDATA_SIZE: usize = 128 * 1024;
+
+alloy_sol_types::sol! {
+    /// The `aggregate3` function of the Multicall3 contract.
+    #[derive(Debug, PartialEq, Eq)]
+    interface IMulticall3 {
+        /// A call to aggregate.
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        /// The result of an aggregated call.
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        /// Aggregates calls, reverting if any call which does not allow failure fails.
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+    }
+}
+
+/// A handle to a call added to a [`MulticallBuilder`], used to decode its output from the
+/// [`MulticallResults`].
+#[derive(Clone, Debug)]
+pub struct MulticallHandle<D> {
+    index: usize,
+    decoder: D,
+}
+
+impl<D> MulticallHandle<D> {
+    /// Returns the index of the call in the multicall.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// The results of the calls of a [`MulticallBuilder`], in the order they were added.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MulticallResults {
+    results: Vec<IMulticall3::Result>,
+}
+
+impl MulticallResults {
+    /// Returns the number of results.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns true if there are no results.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Returns the raw result of the call at the given index.
+    pub fn get(&self, index: usize) -> Option<&IMulticall3::Result> {
+        self.results.get(index)
+    }
+
+    /// Returns an iterator over the raw results.
+    pub fn iter(&self) -> std::slice::Iter<'_, IMulticall3::Result> {
+        self.results.iter()
+    }
+
+    /// Decodes the output of the call of the given handle.
+    ///
+    /// Returns [`Error::MulticallFailure`] if the call failed, which is only possible if it was
+    /// added with [`MulticallBuilder::add_allow_failure`], and
+    /// [`Error::MulticallMissingResult`] if the handle was not returned by the builder which
+    /// produced these results.
+    pub fn decode<D: CallDecoder>(&self, handle: &MulticallHandle<D>) -> Result<D::CallOutput> {
+        let result =
+            self.results.get(handle.index).ok_or(Error::MulticallMissingResult(handle.index))?;
+        if !result.success {
+            return Err(Error::MulticallFailure(handle.index, result.returnData.clone()));
+        }
+        handle.decoder.abi_decode_output(result.returnData.clone(), true)
+    }
+}
+
+impl IntoIterator for MulticallResults {
+    type Item = IMulticall3::Result;
+    type IntoIter = std::vec::IntoIter<IMulticall3::Result>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+/// A builder for aggregating contract calls into `aggregate3` calls to a
+/// [Multicall3](https://www.multicall3.com) contract, to read many values in a few `eth_call`s.
+///
+/// Calls are added from [`CallBuilder`]s of any type, each returning a [`MulticallHandle`] with
+/// which its typed output is decoded from the [`MulticallResults`]. Only the target and calldata
+/// of the call builders are used, their block and state overrides being ignored.
+///
+/// The calls are split into chunks, each sent as its own `eth_call`, so that the calldata of a
+/// chunk does not exceed `max_calldata_size`, and, if `max_gas` is set, the sum of the gas limits
+/// of its calls does not exceed it. Calls without a gas limit are not counted towards `max_gas`.
+/// The chunks are sent concurrently.
+///
+/// A failing call which does not allow failure reverts its whole chunk, failing
+/// [`call`](Self::call).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn test<P: alloy_provider::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_contract::MulticallBuilder;
+/// use alloy_primitives::{address, Address};
+/// use alloy_sol_types::sol;
+///
+/// sol! {
+///     #[sol(rpc)]
+///     contract ERC20 {
+///         function balanceOf(address owner) external view returns (uint256);
+///         function symbol() external view returns (string);
+///     }
+/// }
+///
+/// let token = ERC20::new(address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"), &provider);
+/// let owners = [Address::ZERO, Address::repeat_byte(1)];
+///
+/// let mut multicall = MulticallBuilder::new(&provider);
+/// let symbol = multicall.add(&token.symbol())?;
+/// let balances = owners
+///     .iter()
+///     .map(|owner| multicall.add_allow_failure(&token.balanceOf(*owner)))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let results = multicall.call().await?;
+/// println!("symbol: {}", results.decode(&symbol)?._0);
+/// for (owner, balance) in owners.iter().zip(&balances) {
+///     println!("{owner}: {:?}", results.decode(balance).map(|balance| balance._0));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "multicall builders do nothing unless you `.call` them"]
+pub struct MulticallBuilder<P, N: Network = Ethereum> {
+    provider: P,
+    address: Address,
+    block: BlockId,
+    max_calldata_size: usize,
+    max_gas: Option<u64>,
+    calls: Vec<IMulticall3::Call3>,
+    /// The gas limit of each call, if set.
+    gas: Vec<Option<u64>>,
+    _network: PhantomData<N>,
+}
+
+impl<P: Provider<N>, N: Network> MulticallBuilder<P, N> {
+    /// Creates a new builder sending the calls to the canonical Multicall3 contract.
+    pub const fn new(provider: P) -> Self {
+        Self {
+            provider,
+            address: MULTICALL3_ADDRESS,
+            block: BlockId::latest(),
+            max_calldata_size: DEFAULT_MAX_CALLDATA_SIZE,
+            max_gas: None,
+            calls: Vec::new(),
+            gas: Vec::new(),
+            _network: PhantomData,
+        }
+    }
+
+    /// Sets the address of the Multicall3 contract, for chains where it is not deployed at the
+    /// canonical address.
+    pub const fn address(mut self, address: Address) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Sets the block to call at. Defaults to the latest block.
+    pub const fn block(mut self, block: BlockId) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Sets the maximum size of the calldata of a single `aggregate3` call. Defaults to 128 KiB.
+    pub const fn max_calldata_size(mut self, max_calldata_size: usize) -> Self {
+        self.max_calldata_size = max_calldata_size;
+        self
+    }
+
+    /// Sets the maximum sum of the gas limits of the calls of a single `aggregate3` call.
+    pub const fn max_gas(mut self, max_gas: u64) -> Self {
+        self.max_gas = Some(max_gas);
+        self
+    }
+
+    /// Returns the number of calls added.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns true if no call was added.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Adds a call which must succeed, returning the handle to decode its output.
+    ///
+    /// Returns [`Error::MulticallDeployment`] if the call builder is a deployment.
+    pub fn add<T, Q, D>(&mut self, call: &CallBuilder<T, Q, D, N>) -> Result<MulticallHandle<D>>
+    where
+        Q: Provider<N>,
+        D: CallDecoder + Clone,
+    {
+        self.push(call, false)
+    }
+
+    /// Adds a call which is allowed to fail without failing the multicall, returning the handle to
+    /// decode its output.
+    ///
+    /// Returns [`Error::MulticallDeployment`] if the call builder is a deployment.
+    pub fn add_allow_failure<T, Q, D>(
+        &mut self,
+        call: &CallBuilder<T, Q, D, N>,
+    ) -> Result<MulticallHandle<D>>
+    where
+        Q: Provider<N>,
+        D: CallDecoder + Clone,
+    {
+        self.push(call, true)
+    }
+
+    fn push<T, Q, D>(
+        &mut self,
+        call: &CallBuilder<T, Q, D, N>,
+        allow_failure: bool,
+    ) -> Result<MulticallHandle<D>>
+    where
+        Q: Provider<N>,
+        D: CallDecoder + Clone,
+    {
+        let request = call.as_ref();
+        let target = request.to().ok_or(Error::MulticallDeployment)?;
+        self.calls.push(IMulticall3::Call3 {
+            target,
+            allowFailure: allow_failure,
+            callData: call.calldata().clone(),
+        });
+        self.gas.push(request.gas_limit());
+        Ok(MulticallHandle { index: self.calls.len() - 1, decoder: call.decoder().clone() })
+    }
+
+    /// Returns the ranges of the calls sent in each `aggregate3` call.
+    fn chunks(&self) -> Vec<Range<usize>> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let (mut size, mut gas) = (0, 0);
+        for (i, call) in self.calls.iter().enumerate() {
+            // The offset and head of the call, followed by its padded calldata.
+            let call_size = 5 * 32 + call.callData.len().div_ceil(32) * 32;
+            let call_gas = self.gas[i].unwrap_or_default();
+            let exceeds = size + call_size > self.max_calldata_size
+                || self.max_gas.is_some_and(|max_gas| gas + call_gas > max_gas);
+            if i > start && exceeds {
+                chunks.push(start..i);
+                start = i;
+                (size, gas) = (0, 0);
+            }
+            size += call_size;
+            gas += call_gas;
+        }
+        if start < self.calls.len() {
+            chunks.push(start..self.calls.len());
+        }
+        chunks
+    }
+
+    /// Sends the calls with `eth_call`, returning their results in the order they were added.
+    pub async fn call(&self) -> Result<MulticallResults> {
+        let calls = self.chunks().into_iter().map(|chunk| self.call_chunk(chunk));
+        let results = try_join_all(calls).await?.into_iter().flatten().collect();
+        Ok(MulticallResults { results })
+    }
+
+    async fn call_chunk(&self, chunk: Range<usize>) -> Result<Vec<IMulticall3::Result>> {
+        let len = chunk.len();
+        let input = IMulticall3::aggregate3Call { calls: self.calls[chunk].to_vec() }.abi_encode();
+        let request = N::TransactionRequest::default().with_to(self.address).with_input(input);
+        let data: Bytes = self.provider.call(&request).block(self.block).await?;
+        let results = IMulticall3::aggregate3Call::abi_decode_returns(&data, true)
+            .map_err(|e| Error::decode(IMulticall3::aggregate3Call::SIGNATURE, &data, e.into()))?
+            .returnData;
+        if results.len() != len {
+            return Err(Error::AbiError(alloy_dyn_abi::Error::custom(format!(
+                "multicall returned {} results for {len} calls",
+                results.len()
+            ))));
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Uint, U256};
+    use alloy_provider::ProviderBuilder;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_sol_types::{sol, SolValue};
+    use alloy_transport::mock::{MockResponse, MockTransport};
+
+    sol! {
+        interface IToken {
+            function balanceOf(address owner) external view returns (uint256);
+            function symbol() external view returns (string);
+        }
+    }
+
+    /// Executes an `aggregate3` call. The balance of an owner is its last byte, and the calls to
+    /// `Address::ZERO` fail.
+    fn aggregate3(input: &[u8]) -> Vec<IMulticall3::Result> {
+        let calls = IMulticall3::aggregate3Call::abi_decode(input, true).unwrap().calls;
+        calls
+            .into_iter()
+            .map(|call| {
+                let return_data = if call.target.is_zero() {
+                    Vec::new()
+                } else if call.callData.starts_with(&IToken::symbolCall::SELECTOR) {
+                    "TKN".to_string().abi_encode()
+                } else {
+                    let owner =
+                        IToken::balanceOfCall::abi_decode(&call.callData, true).unwrap().owner;
+                    U256::from(owner[19]).abi_encode()
+                };
+                IMulticall3::Result {
+                    success: !call.target.is_zero(),
+                    returnData: return_data.into(),
+                }
+            })
+            .collect()
+    }
+
+    /// A transport executing `aggregate3` calls.
+    fn mock_multicall() -> MockTransport {
+        MockTransport::new(|req| {
+            let (tx, _): (TransactionRequest, BlockId) =
+                serde_json::from_str(req.params().unwrap().get()).unwrap();
+            assert_eq!(tx.to, Some(MULTICALL3_ADDRESS.into()));
+            let results = aggregate3(tx.input.input().unwrap());
+            let output = Bytes::from(IMulticall3::aggregate3Call::abi_encode_returns(&(results,)));
+            MockResponse::success(&output)
+        })
+    }
+
+    /// Returns the number of calls aggregated by each request.
+    fn aggregated_calls(transport: &MockTransport) -> Vec<usize> {
+        transport
+            .requests()
+            .iter()
+            .map(|req| {
+                let (tx, _): (TransactionRequest, BlockId) =
+                    serde_json::from_str(req.params().unwrap().get()).unwrap();
+                let input = tx.input.input().unwrap();
+                IMulticall3::aggregate3Call::abi_decode(input, true).unwrap().calls.len()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn aggregates_typed_calls() {
+        let transport = mock_multicall();
+        let provider = ProviderBuilder::new().on_client(RpcClient::new(transport.clone(), true));
+        let token = Address::repeat_byte(0xaa);
+        let call = |input: Vec<u8>, target: Address| {
+            crate::RawCallBuilder::<(), _>::new_raw(&provider, input.into()).to(target)
+        };
+
+        let mut multicall = MulticallBuilder::new(&provider).max_calldata_size(1000);
+        let symbol = multicall
+            .add(
+                &call(IToken::symbolCall {}.abi_encode(), token)
+                    .with_sol_decoder::<IToken::symbolCall>(),
+            )
+            .unwrap();
+        let balances: Vec<_> = (0..10u8)
+            .map(|i| {
+                let owner = Address::with_last_byte(i);
+                let call = call(IToken::balanceOfCall { owner }.abi_encode(), token)
+                    .with_sol_decoder::<IToken::balanceOfCall>();
+                multicall.add(&call).unwrap()
+            })
+            .collect();
+        let failing = multicall.add_allow_failure(&call(vec![1, 2, 3], Address::ZERO)).unwrap();
+
+        let results = multicall.call().await.unwrap();
+        assert_eq!(results.len(), 12);
+        assert_eq!(results.decode(&symbol).unwrap()._0, "TKN");
+        for (i, balance) in balances.iter().enumerate() {
+            assert_eq!(results.decode(balance).unwrap()._0, Uint::from(i));
+        }
+        assert!(matches!(results.decode(&failing), Err(Error::MulticallFailure(11, _))));
+
+        // The calls are split in chunks of at most 1000 bytes of calldata.
+        assert_eq!(aggregated_calls(&transport), [4, 4, 4]);
+    }
+
+    #[tokio::test]
+    async fn rejects_deployments() {
+        let provider = ProviderBuilder::new().on_client(RpcClient::new(mock_multicall(), true));
+
+        let mut multicall = MulticallBuilder::new(&provider);
+        let deploy = crate::RawCallBuilder::<(), _>::new_raw_deploy(&provider, vec![1].into());
+        assert!(matches!(multicall.add(&deploy), Err(Error::MulticallDeployment)));
+        assert!(matches!(multicall.add_allow_failure(&deploy), Err(Error::MulticallDeployment)));
+        assert!(multicall.is_empty());
+    }
+
+    #[tokio::test]
+    async fn decodes_foreign_handles() {
+        let provider = ProviderBuilder::new().on_client(RpcClient::new(mock_multicall(), true));
+        let input = IToken::symbolCall {}.abi_encode();
+        let call = crate::RawCallBuilder::<(), _>::new_raw(&provider, input.into())
+            .to(Address::repeat_byte(0xaa));
+
+        let mut other = MulticallBuilder::new(&provider);
+        other.add(&call).unwrap();
+        let handle = other.add(&call).unwrap();
+
+        let mut multicall = MulticallBuilder::new(&provider);
+        multicall.add(&call).unwrap();
+        let results = multicall.call().await.unwrap();
+        assert!(matches!(results.decode(&handle), Err(Error::MulticallMissingResult(1))));
+    }
+}
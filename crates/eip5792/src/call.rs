@@ -1,4 +1,4 @@
-use alloy_primitives::{map::HashMap, Address, Bytes, ChainId, U256};
+use alloy_primitives::{map::HashMap, Address, BlockHash, Bytes, ChainId, Log, TxHash, U256};
 use std::vec::Vec;
 
 /// Request that a wallet submits a batch of calls in `wallet_sendCalls`
@@ -33,6 +33,66 @@ pub struct CallParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub chain_id: Option<ChainId>,
 }
+
+/// Status of a batch of calls, as returned by `wallet_getCallsStatus`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallsStatus {
+    /// Whether the calls have been included onchain
+    pub status: CallStatus,
+    /// Receipts of the transactions which included the calls, once confirmed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipts: Option<Vec<CallReceipt>>,
+}
+
+impl CallsStatus {
+    /// Returns true if the calls have been included onchain.
+    pub const fn is_confirmed(&self) -> bool {
+        matches!(self.status, CallStatus::Confirmed)
+    }
+}
+
+/// Status of a batch of calls
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CallStatus {
+    /// The calls have not been included onchain yet
+    Pending,
+    /// The calls have been included onchain
+    Confirmed,
+}
+
+/// Receipt of a transaction which included calls submitted with `wallet_sendCalls`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallReceipt {
+    /// Logs emitted by the transaction
+    pub logs: Vec<Log>,
+    /// Transaction status, `1` for success and `0` for failure
+    #[serde(with = "alloy_serde::quantity")]
+    pub status: u64,
+    /// Id of the chain the transaction was included on
+    #[serde(with = "alloy_serde::quantity")]
+    pub chain_id: ChainId,
+    /// Hash of the block the transaction was included in
+    pub block_hash: BlockHash,
+    /// Number of the block the transaction was included in
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Gas used by the transaction
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Hash of the transaction
+    pub transaction_hash: TxHash,
+}
+
+impl CallReceipt {
+    /// Returns true if the transaction succeeded.
+    pub const fn is_success(&self) -> bool {
+        self.status == 1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +127,39 @@ mod tests {
         let deserialized: SendCallsRequest = serde_json::from_str(&serialized).unwrap();
         assert_eq!(sample_request, deserialized);
     }
+
+    #[test]
+    fn test_calls_status() {
+        let pending: CallsStatus = serde_json::from_str(r#"{"status":"PENDING"}"#).unwrap();
+        assert!(!pending.is_confirmed());
+        assert!(pending.receipts.is_none());
+
+        let confirmed: CallsStatus = serde_json::from_str(
+            r#"{
+                "status": "CONFIRMED",
+                "receipts": [{
+                    "logs": [{
+                        "address": "0xa922b54716264130634d6ff183747a8ead91a40b",
+                        "topics": ["0x5a2a90727cc9d000dd060b1132a5c977c9702bb3a52afe360c9c22f0e9451a68"],
+                        "data": "0xabcd"
+                    }],
+                    "status": "0x1",
+                    "chainId": "0x1",
+                    "blockHash": "0xf19bbafd9fd0124ec110b848e8de4ab4f62bf60c189524e54213285e7f540d4a",
+                    "blockNumber": "0xabcd",
+                    "gasUsed": "0xdef",
+                    "transactionHash": "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaff"
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert!(confirmed.is_confirmed());
+        let receipt = &confirmed.receipts.as_ref().unwrap()[0];
+        assert!(receipt.is_success());
+        assert_eq!(receipt.block_number, 0xabcd);
+        assert_eq!(receipt.logs[0].data.data, Bytes::from_static(&[0xab, 0xcd]));
+
+        let serialized = serde_json::to_string(&confirmed).unwrap();
+        assert_eq!(serde_json::from_str::<CallsStatus>(&serialized).unwrap(), confirmed);
+    }
 }
@@ -0,0 +1,136 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+};
+
+/// A value stored in [`Extensions`].
+trait AnyClone: Any + Send + Sync {
+    fn clone_box(&self) -> Box<dyn AnyClone>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Clone + Send + Sync + 'static> AnyClone for T {
+    fn clone_box(&self) -> Box<dyn AnyClone> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl Clone for Box<dyn AnyClone> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+/// A type map of transport-specific data attached to a request.
+///
+/// Extensions are never serialized. Transports look up the types they
+/// understand, and ignore the others. For instance, HTTP transports send the
+/// `http::HeaderMap` attached to a request as additional headers.
+#[derive(Clone, Default)]
+pub struct Extensions {
+    map: Option<HashMap<TypeId, Box<dyn AnyClone>>>,
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions").field("len", &self.len()).finish()
+    }
+}
+
+impl Extensions {
+    /// Create an empty `Extensions`.
+    pub const fn new() -> Self {
+        Self { map: None }
+    }
+
+    /// Insert a value, returning the previous value of the same type, if any.
+    pub fn insert<T: Clone + Send + Sync + 'static>(&mut self, val: T) -> Option<T> {
+        self.map
+            .get_or_insert_with(Default::default)
+            .insert(TypeId::of::<T>(), Box::new(val))
+            .and_then(|prev| prev.into_any().downcast().ok().map(|prev| *prev))
+    }
+
+    /// Get a reference to the value of type `T`, if any.
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.map.as_ref()?.get(&TypeId::of::<T>())?.as_ref().as_any().downcast_ref()
+    }
+
+    /// Get a mutable reference to the value of type `T`, if any.
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.map.as_mut()?.get_mut(&TypeId::of::<T>())?.as_mut().as_any_mut().downcast_mut()
+    }
+
+    /// Get a mutable reference to the value of type `T`, inserting its default
+    /// value if there is none.
+    pub fn get_or_insert_default<T: Default + Clone + Send + Sync + 'static>(&mut self) -> &mut T {
+        self.map
+            .get_or_insert_with(Default::default)
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::default()))
+            .as_mut()
+            .as_any_mut()
+            .downcast_mut()
+            .expect("extension stored under the type ID of another type")
+    }
+
+    /// Remove the value of type `T`, returning it, if any.
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        self.map
+            .as_mut()?
+            .remove(&TypeId::of::<T>())
+            .and_then(|val| val.into_any().downcast().ok().map(|val| *val))
+    }
+
+    /// Returns `true` if there are no extensions.
+    pub fn is_empty(&self) -> bool {
+        self.map.as_ref().map_or(true, |map| map.is_empty())
+    }
+
+    /// Returns the number of extensions.
+    pub fn len(&self) -> usize {
+        self.map.as_ref().map_or(0, |map| map.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_values() {
+        let mut ext = Extensions::new();
+        assert!(ext.is_empty());
+
+        assert_eq!(ext.insert(1u32), None);
+        assert_eq!(ext.insert(2u32), Some(1));
+        ext.insert(String::from("a"));
+        ext.get_or_insert_default::<Vec<u8>>().push(3);
+        assert_eq!(ext.len(), 3);
+
+        let cloned = ext.clone();
+        *ext.get_mut::<u32>().unwrap() = 4;
+        assert_eq!(ext.get::<u32>(), Some(&4));
+        assert_eq!(cloned.get::<u32>(), Some(&2));
+        assert_eq!(cloned.get::<Vec<u8>>(), Some(&vec![3]));
+
+        assert_eq!(ext.remove::<String>().as_deref(), Some("a"));
+        assert_eq!(ext.get::<String>(), None);
+        assert_eq!(ext.get::<u64>(), None);
+    }
+}
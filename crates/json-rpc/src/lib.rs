@@ -85,6 +85,9 @@ pub use common::Id;
 mod error;
 pub use error::RpcError;
 
+mod extensions;
+pub use extensions::Extensions;
+
 mod notification;
 pub use notification::{EthNotification, PubSubItem, SubId};
 
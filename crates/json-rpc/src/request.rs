@@ -1,4 +1,4 @@
-use crate::{common::Id, RpcObject, RpcParam};
+use crate::{common::Id, Extensions, RpcObject, RpcParam};
 use alloy_primitives::{keccak256, B256};
 use serde::{
     de::{DeserializeOwned, MapAccess},
@@ -9,7 +9,7 @@ use serde_json::value::RawValue;
 use std::{borrow::Cow, marker::PhantomData, mem::MaybeUninit};
 
 /// `RequestMeta` contains the [`Id`] and method name of a request.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub struct RequestMeta {
     /// The method name.
     pub method: Cow<'static, str>,
@@ -17,12 +17,36 @@ pub struct RequestMeta {
     pub id: Id,
     /// Whether the request is a subscription, other than `eth_subscribe`.
     is_subscription: bool,
+    /// Transport-specific data attached to the request.
+    extensions: Extensions,
+}
+
+/// Extensions are not compared.
+impl PartialEq for RequestMeta {
+    fn eq(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.id == other.id
+            && self.is_subscription == other.is_subscription
+    }
 }
 
+impl Eq for RequestMeta {}
+
 impl RequestMeta {
     /// Create a new `RequestMeta`.
     pub const fn new(method: Cow<'static, str>, id: Id) -> Self {
-        Self { method, id, is_subscription: false }
+        Self { method, id, is_subscription: false, extensions: Extensions::new() }
+    }
+
+    /// Returns the transport-specific data attached to the request.
+    pub const fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    /// Returns a mutable reference to the transport-specific data attached to
+    /// the request.
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
     }
 
     /// Returns `true` if the request is a subscription.
@@ -406,4 +430,16 @@ mod test {
         test_inner(Request::<String>::new("test", Id::None, "test".to_string()));
         test_inner(Request::<Vec<u64>>::new("test", u64::MAX.into(), vec![1, 2, 3]));
     }
+
+    #[test]
+    fn test_extensions_not_serialized() {
+        let mut req = Request::<()>::new("test", 1.into(), ());
+        let expected = serde_json::to_string(&req).unwrap();
+
+        req.meta.extensions_mut().insert(String::from("value"));
+        assert_eq!(serde_json::to_string(&req).unwrap(), expected);
+
+        let ser = req.serialize().unwrap();
+        assert_eq!(ser.meta().extensions().get::<String>().unwrap(), "value");
+    }
 }
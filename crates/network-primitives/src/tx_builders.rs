@@ -1,8 +1,9 @@
 use core::ops::{Deref, DerefMut};
 
 use alloc::vec::Vec;
-use alloy_consensus::BlobTransactionSidecar;
+use alloy_consensus::{BlobTransactionSidecar, TxSponsoredPayer};
 use alloy_eips::eip7702::SignedAuthorization;
+use alloy_primitives::PrimitiveSignature as Signature;
 use alloy_serde::WithOtherFields;
 
 /// Transaction builder type supporting EIP-4844 transaction fields.
@@ -50,6 +51,39 @@ pub trait TransactionBuilder7702: Default + Sized + Send + Sync + 'static {
     }
 }
 
+/// Transaction builder type supporting sponsored (type 100) transaction fields.
+pub trait TransactionBuilderSponsored: Default + Sized + Send + Sync + 'static {
+    /// Get the timestamp after which the sponsored transaction expires.
+    fn expired_time(&self) -> Option<u64>;
+
+    /// Set the timestamp after which the sponsored transaction expires.
+    fn set_expired_time(&mut self, expired_time: u64);
+
+    /// Builder-pattern method for setting the expiry timestamp.
+    fn with_expired_time(mut self, expired_time: u64) -> Self {
+        self.set_expired_time(expired_time);
+        self
+    }
+
+    /// Get the signature of the gas payer, if all of its components are set.
+    fn payer_signature(&self) -> Option<Signature>;
+
+    /// Sets the signature of the gas payer.
+    fn set_payer_signature(&mut self, signature: Signature);
+
+    /// Builder-pattern method for setting the signature of the gas payer.
+    fn with_payer_signature(mut self, signature: Signature) -> Self {
+        self.set_payer_signature(signature);
+        self
+    }
+
+    /// Builds the payload signed by the gas payer.
+    ///
+    /// The payer signature commits to the sender, so `from` must be set. Returns the list of
+    /// missing keys if the payload cannot be built.
+    fn payer_signing_tx(&self) -> Result<TxSponsoredPayer, Vec<&'static str>>;
+}
+
 impl<T> TransactionBuilder4844 for WithOtherFields<T>
 where
     T: TransactionBuilder4844,
@@ -83,3 +117,28 @@ where
         self.deref_mut().set_authorization_list(authorization_list)
     }
 }
+
+impl<T> TransactionBuilderSponsored for WithOtherFields<T>
+where
+    T: TransactionBuilderSponsored,
+{
+    fn expired_time(&self) -> Option<u64> {
+        self.deref().expired_time()
+    }
+
+    fn set_expired_time(&mut self, expired_time: u64) {
+        self.deref_mut().set_expired_time(expired_time)
+    }
+
+    fn payer_signature(&self) -> Option<Signature> {
+        self.deref().payer_signature()
+    }
+
+    fn set_payer_signature(&mut self, signature: Signature) {
+        self.deref_mut().set_payer_signature(signature)
+    }
+
+    fn payer_signing_tx(&self) -> Result<TxSponsoredPayer, Vec<&'static str>> {
+        self.deref().payer_signing_tx()
+    }
+}
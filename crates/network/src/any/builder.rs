@@ -102,6 +102,10 @@ impl TransactionBuilder<AnyNetwork> for WithOtherFields<TransactionRequest> {
         self.deref_mut().set_access_list(access_list)
     }
 
+    fn expiry(&self) -> Option<u64> {
+        self.deref().expiry()
+    }
+
     fn complete_type(&self, ty: <AnyNetwork as Network>::TxType) -> Result<(), Vec<&'static str>> {
         self.deref().complete_type(ty.try_into().map_err(|_| vec!["supported tx type"])?)
     }
@@ -1,7 +1,7 @@
 use crate::{UnknownTxEnvelope, UnknownTypedTransaction};
 use alloy_consensus::{
     Signed, Transaction as TransactionTrait, TxEip1559, TxEip2930, TxEip4844Variant, TxEip7702,
-    TxEnvelope, TxLegacy, Typed2718, TypedTransaction,
+    TxEnvelope, TxLegacy, TxSponsored, TxType, Typed2718, TypedTransaction,
 };
 use alloy_eips::{
     eip2718::{Decodable2718, Encodable2718},
@@ -225,9 +225,17 @@ impl AnyTxEnvelope {
 
     /// Returns the inner Ethereum transaction envelope, if it is an Ethereum transaction.
     /// If the transaction is not an Ethereum transaction, it is returned as an error.
+    ///
+    /// Sponsored transactions which were deserialized as unknown transactions are converted
+    /// from their fields, if possible.
     pub fn try_into_envelope(self) -> Result<TxEnvelope, Self> {
         match self {
             Self::Ethereum(inner) => Ok(inner),
+            Self::Unknown(inner) if inner.ty() == TxType::Sponsored as u8 => {
+                serde_json::to_value(&inner)
+                    .and_then(serde_json::from_value)
+                    .map_err(|_| Self::Unknown(inner))
+            }
             this => Err(this),
         }
     }
@@ -271,6 +279,14 @@ impl AnyTxEnvelope {
             _ => None,
         }
     }
+
+    /// Returns the [`TxSponsored`] variant if the transaction is a sponsored transaction.
+    pub const fn as_sponsored(&self) -> Option<&Signed<TxSponsored>> {
+        match self.as_envelope() {
+            Some(TxEnvelope::Sponsored(tx)) => Some(tx),
+            _ => None,
+        }
+    }
 }
 
 impl Typed2718 for AnyTxEnvelope {
@@ -3,7 +3,9 @@ use std::sync::OnceLock;
 
 use alloy_consensus::{TxType, Typed2718};
 use alloy_eips::{eip2718::Eip2718Error, eip7702::SignedAuthorization};
-use alloy_primitives::{Address, Bytes, ChainId, TxKind, B256, U128, U256, U64, U8};
+use alloy_primitives::{
+    Address, Bytes, ChainId, PrimitiveSignature as Signature, TxKind, B256, U128, U256, U64, U8,
+};
 use alloy_rpc_types_eth::AccessList;
 use alloy_serde::OtherFields;
 
@@ -103,6 +105,25 @@ pub struct UnknownTypedTransaction {
     pub memo: DeserMemo,
 }
 
+impl UnknownTypedTransaction {
+    /// Returns the `expiredTime` of a sponsored transaction, if present.
+    pub fn expired_time(&self) -> Option<u64> {
+        self.fields.get_deserialized::<U64>("expiredTime").and_then(Result::ok).map(|v| v.to())
+    }
+
+    /// Returns the gas payer signature of a sponsored transaction, if present.
+    pub fn payer_signature(&self) -> Option<Signature> {
+        let field = |key| self.fields.get_deserialized::<U256>(key).and_then(Result::ok);
+        let (v, r, s) = (field("payerV")?, field("payerR")?, field("payerS")?);
+        let y_parity = match u64::try_from(v) {
+            Ok(0) => false,
+            Ok(1) => true,
+            _ => return None,
+        };
+        Some(Signature::new(r, s, y_parity))
+    }
+}
+
 impl alloy_consensus::Transaction for UnknownTypedTransaction {
     #[inline]
     fn chain_id(&self) -> Option<ChainId> {
@@ -427,4 +448,63 @@ mod tests {
 
         assert_eq!(tx, roundrip_tx);
     }
+
+    const SPONSORED_TX: &str = r#"{
+        "blockHash": "0x1d3fc86e4f3b8e6ad7b6e2b8b0be2c30a5b1a1c0f5d6f1e4a3a7c5a1ab0f0b5c",
+        "blockNumber": "0x2168c4c",
+        "chainId": "0x7e4",
+        "expiredTime": "0x66d29dd2",
+        "from": "0x75bbaf493a22aeb569799d8f136a57c1169f6755",
+        "gas": "0x1aa12",
+        "gasPrice": "0x4a817c800",
+        "hash": "0x421f2f71c76e0fc45c11339ec0453ae08f2b2bf2ae54c8c87f68a9bc486880d4",
+        "input": "0x095ea7b30000000000000000000000008a28c188a067dfa6aaec36e2b67b34d2c3042df90fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "maxFeePerGas": "0x4a817c800",
+        "maxPriorityFeePerGas": "0x4a817c800",
+        "nonce": "0x0",
+        "payerR": "0xa874e9dbb63262b7c3500c3130480c4877cebe91203098b3febc701db4940ff8",
+        "payerS": "0x5dc5f279f16ae525781b938a71935525f5baa42a7e2207a02f45a0d84e723575",
+        "payerV": "0x1",
+        "r": "0x697557c9fff6a7a781c4d96c9f9fa7ce6f6e3f8bb37d02544925b9d4e4ae83f7",
+        "s": "0x16cb0fcfcfbfd2d05bde7aac1a7a9b28faa0434851d6a7257f53f6936d72e0b5",
+        "to": "0x0b7007c13325c48911f73a2dad5fa5dcbf808adc",
+        "transactionIndex": "0x0",
+        "type": "0x64",
+        "v": "0x1",
+        "value": "0x0"
+    }"#;
+
+    #[test]
+    fn test_serde_sponsored() {
+        let tx: AnyRpcTransaction = serde_json::from_str(SPONSORED_TX).unwrap();
+
+        let sponsored = tx.inner.inner.as_sponsored().expect("expected sponsored envelope");
+        assert_eq!(sponsored.tx().expired_time, 0x66d29dd2);
+        assert!(sponsored.tx().payer_signature().unwrap().v());
+
+        let envelope = tx.inner.inner.clone().try_into_envelope().unwrap();
+        assert_eq!(envelope.as_sponsored(), Some(sponsored));
+
+        let roundrip_tx: AnyRpcTransaction =
+            serde_json::from_str(&serde_json::to_string(&tx).unwrap()).unwrap();
+        assert_eq!(tx, roundrip_tx);
+    }
+
+    #[test]
+    fn test_unknown_sponsored() {
+        let unknown: UnknownTxEnvelope = serde_json::from_str(SPONSORED_TX).unwrap();
+        assert_eq!(unknown.inner.expired_time(), Some(0x66d29dd2));
+        assert!(unknown.inner.payer_signature().unwrap().v());
+
+        let expected: AnyRpcTransaction = serde_json::from_str(SPONSORED_TX).unwrap();
+        let envelope = AnyTxEnvelope::Unknown(unknown).try_into_envelope().unwrap();
+        assert_eq!(Some(&envelope), expected.inner.inner.as_envelope());
+
+        for payer_v in ["0x1b", "0x10000000000000000"] {
+            let tx =
+                SPONSORED_TX.replace(r#""payerV": "0x1""#, &format!(r#""payerV": "{payer_v}""#));
+            let unknown: UnknownTxEnvelope = serde_json::from_str(&tx).unwrap();
+            assert_eq!(unknown.inner.payer_signature(), None);
+        }
+    }
 }
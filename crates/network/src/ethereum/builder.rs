@@ -99,6 +99,10 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
         self.access_list = Some(access_list);
     }
 
+    fn expiry(&self) -> Option<u64> {
+        self.expired_time
+    }
+
     fn complete_type(&self, ty: TxType) -> Result<(), Vec<&'static str>> {
         match ty {
             TxType::Legacy => self.complete_legacy(),
@@ -106,7 +110,7 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
             TxType::Eip1559 => self.complete_1559(),
             TxType::Eip4844 => self.complete_4844(),
             TxType::Eip7702 => self.complete_7702(),
-            TxType::Sponsored => todo!()
+            TxType::Sponsored => self.complete_sponsored(),
         }
     }
 
@@ -132,7 +136,13 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
         let eip4844 = eip1559 && self.sidecar.is_some() && self.to.is_some();
 
         let eip7702 = eip1559 && self.authorization_list().is_some();
-        common && (legacy || eip2930 || eip1559 || eip4844 || eip7702)
+
+        let sponsored = eip1559
+            && self.expired_time.is_some()
+            && self.payer_v.is_some()
+            && self.payer_r.is_some()
+            && self.payer_s.is_some();
+        common && (legacy || eip2930 || eip1559 || eip4844 || eip7702 || sponsored)
     }
 
     #[doc(alias = "output_transaction_type")]
@@ -170,7 +180,8 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
 #[cfg(test)]
 mod tests {
     use crate::{
-        TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702, TransactionBuilderError,
+        TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702,
+        TransactionBuilderError, TransactionBuilderSponsored,
     };
     use alloy_consensus::{BlobTransactionSidecar, TxEip1559, TxType, TypedTransaction};
     use alloy_eips::eip7702::Authorization;
@@ -250,6 +261,25 @@ mod tests {
         assert!(matches!(tx, TypedTransaction::Eip7702(_)));
     }
 
+    #[test]
+    fn test_sponsored_when_expired_time() {
+        let request = TransactionRequest::default()
+            .with_nonce(1)
+            .with_gas_limit(0)
+            .with_max_fee_per_gas(0)
+            .with_max_priority_fee_per_gas(0)
+            .with_to(Address::ZERO)
+            .with_access_list(AccessList::default())
+            .with_expired_time(1_700_000_000)
+            .with_payer_signature(Signature::test_signature());
+
+        let tx = request.build_unsigned().unwrap();
+
+        let TypedTransaction::Sponsored(tx) = tx else { panic!("wrong variant") };
+        assert_eq!(tx.expired_time, 1_700_000_000);
+        assert_eq!(tx.payer_r, Signature::test_signature().r());
+    }
+
     #[test]
     fn test_default_to_1559() {
         let request = TransactionRequest::default()
@@ -379,4 +409,27 @@ mod tests {
         assert!(errors.contains(&"max_priority_fee_per_gas"));
         assert!(errors.contains(&"max_fee_per_gas"));
     }
+
+    #[test]
+    fn test_invalid_sponsored_fields() {
+        let request = TransactionRequest::default().with_expired_time(0);
+
+        let error = request.build_unsigned().unwrap_err();
+
+        let TransactionBuilderError::InvalidTransactionRequest(tx_type, errors) = error.error
+        else {
+            panic!("wrong variant")
+        };
+
+        assert_eq!(tx_type, TxType::Sponsored);
+        assert_eq!(errors.len(), 8);
+        assert!(errors.contains(&"to"));
+        assert!(errors.contains(&"nonce"));
+        assert!(errors.contains(&"gas_limit"));
+        assert!(errors.contains(&"max_priority_fee_per_gas"));
+        assert!(errors.contains(&"max_fee_per_gas"));
+        assert!(errors.contains(&"payer_v"));
+        assert!(errors.contains(&"payer_r"));
+        assert!(errors.contains(&"payer_s"));
+    }
 }
@@ -15,7 +15,8 @@ use core::fmt::{Debug, Display};
 mod transaction;
 pub use transaction::{
     BuildResult, NetworkWallet, TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702,
-    TransactionBuilderError, TxSigner, TxSignerSync, UnbuiltTransactionError,
+    TransactionBuilderError, TransactionBuilderPayer, TransactionBuilderSponsored, TxSigner,
+    TxSignerSync, UnbuiltTransactionError,
 };
 
 mod ethereum;
@@ -5,7 +5,9 @@ use alloy_rpc_types_eth::AccessList;
 use alloy_sol_types::SolCall;
 use futures_utils_wasm::impl_future;
 
-pub use alloy_network_primitives::{TransactionBuilder4844, TransactionBuilder7702};
+pub use alloy_network_primitives::{
+    TransactionBuilder4844, TransactionBuilder7702, TransactionBuilderSponsored,
+};
 
 /// Result type for transaction builders
 pub type BuildResult<T, N> = Result<T, UnbuiltTransactionError<N>>;
@@ -272,6 +274,13 @@ pub trait TransactionBuilder<N: Network>: Default + Sized + Send + Sync + 'stati
         self
     }
 
+    /// Get the timestamp after which the transaction expires, for networks
+    /// with expiring transactions such as sponsored transactions. Defaults to
+    /// `None`.
+    fn expiry(&self) -> Option<u64> {
+        None
+    }
+
     /// Check if all necessary keys are present to build the specified type,
     /// returning a list of missing keys.
     fn complete_type(&self, ty: N::TxType) -> Result<(), Vec<&'static str>>;
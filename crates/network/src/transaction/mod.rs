@@ -1,8 +1,11 @@
 mod builder;
 pub use builder::{
     BuildResult, TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702,
-    TransactionBuilderError, UnbuiltTransactionError,
+    TransactionBuilderError, TransactionBuilderSponsored, UnbuiltTransactionError,
 };
 
+mod payer;
+pub use payer::TransactionBuilderPayer;
+
 mod signer;
 pub use signer::{NetworkWallet, TxSigner, TxSignerSync};
@@ -0,0 +1,47 @@
+use crate::{TransactionBuilderSponsored, TxSigner};
+use alloy_primitives::PrimitiveSignature as Signature;
+use futures_utils_wasm::impl_future;
+
+/// Transaction builder extension allowing the gas payer of a sponsored transaction to co-sign
+/// the request.
+///
+/// The payer must sign before the sender, as the sender signature commits to the payer
+/// signature. This trait is implemented for every [`TransactionBuilderSponsored`].
+pub trait TransactionBuilderPayer: TransactionBuilderSponsored {
+    /// Asynchronously sign the request as the gas payer, and set the resulting payer signature.
+    ///
+    /// The request must contain every field of the sponsored transaction except the payer
+    /// signature, including `from`.
+    fn sign_payer<S>(&mut self, payer: &S) -> impl_future!(<Output = alloy_signer::Result<()>>)
+    where
+        S: TxSigner<Signature> + Send + Sync + ?Sized,
+    {
+        async move {
+            let mut tx = self.payer_signing_tx().map_err(|missing| {
+                alloy_signer::Error::other(format!(
+                    "missing keys for sponsored transaction payer signature: {}",
+                    missing.join(", ")
+                ))
+            })?;
+            let signature = payer.sign_transaction(&mut tx).await?;
+            self.set_payer_signature(signature);
+            Ok(())
+        }
+    }
+
+    /// Builder-pattern method for signing the request as the gas payer.
+    fn with_payer_signer<S>(
+        mut self,
+        payer: &S,
+    ) -> impl_future!(<Output = alloy_signer::Result<Self>>)
+    where
+        S: TxSigner<Signature> + Send + Sync + ?Sized,
+    {
+        async move {
+            self.sign_payer(payer).await?;
+            Ok(self)
+        }
+    }
+}
+
+impl<T: TransactionBuilderSponsored> TransactionBuilderPayer for T {}
@@ -1,7 +1,7 @@
 use crate::{
     fillers::{
         CachedNonceManager, ChainIdFiller, FillerControlFlow, GasFiller, JoinFill, NonceFiller,
-        NonceManager, RecommendedFillers, SimpleNonceManager, TxFiller, WalletFiller,
+        NonceManager, PayerFiller, RecommendedFillers, SimpleNonceManager, TxFiller, WalletFiller,
     },
     provider::SendableTx,
     Provider, RootProvider,
@@ -257,6 +257,14 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         self.filler(WalletFiller::new(wallet))
     }
 
+    /// Add a gas payer layer to the stack being built, sponsoring the gas of sponsored
+    /// transactions. This must be added before the [`wallet`](Self::wallet) layer.
+    ///
+    /// See [`PayerFiller`].
+    pub fn payer<P>(self, payer: P) -> ProviderBuilder<L, JoinFill<F, PayerFiller<P>>, N> {
+        self.filler(PayerFiller::new(payer))
+    }
+
     /// Change the network.
     ///
     /// By default, the network is `Ethereum`. This method must be called to configure a different
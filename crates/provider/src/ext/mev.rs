@@ -0,0 +1,299 @@
+//! This module extends the Ethereum JSON-RPC provider with the MEV bundle and private
+//! transaction namespaces, as exposed by Flashbots-compatible relays and builders.
+use crate::Provider;
+use alloy_json_rpc::{RpcError, RpcParam, RpcReturn};
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{hex, keccak256, Bytes, B256};
+use alloy_rpc_client::RpcCall;
+use alloy_rpc_types_mev::{
+    BundleStats, BundleStatsRequest, CancelBundleRequest, EthBundleHash, EthCallBundle,
+    EthCallBundleResponse, EthSendBundle, PrivateTransactionRequest, SendBundleRequest,
+    SendBundleResponse, SimBundleOverrides, SimBundleResponse,
+};
+use alloy_signer::Signer;
+use alloy_transport::{RpcFut, TransportResult};
+use http::{HeaderMap, HeaderValue};
+use std::{fmt, future::IntoFuture, sync::Arc};
+
+/// The HTTP header used by Flashbots relays to authenticate the sender of a request.
+pub const FLASHBOTS_SIGNATURE_HEADER: &str = "x-flashbots-signature";
+
+/// MEV rpc interface, for bundles and private transactions.
+///
+/// Every method returns a [`MevBuilder`], which can be authenticated with
+/// [`MevBuilder::with_auth`] before being awaited.
+pub trait MevApi<N: Network = Ethereum>: Send + Sync {
+    /// Sends a bundle of transactions to be included in a specific block.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#eth_sendbundle)
+    /// for more details.
+    fn send_bundle(
+        &self,
+        bundle: EthSendBundle,
+    ) -> MevBuilder<(EthSendBundle,), Option<EthBundleHash>>;
+
+    /// Simulates a bundle of transactions against a specific block.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#eth_callbundle)
+    /// for more details.
+    fn call_bundle(
+        &self,
+        bundle: EthCallBundle,
+    ) -> MevBuilder<(EthCallBundle,), EthCallBundleResponse>;
+
+    /// Sends a MEV-Share bundle.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#mev_sendbundle)
+    /// for more details.
+    fn send_mev_bundle(
+        &self,
+        bundle: SendBundleRequest,
+    ) -> MevBuilder<(SendBundleRequest,), SendBundleResponse>;
+
+    /// Simulates a MEV-Share bundle, with the given overrides.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#mev_simbundle)
+    /// for more details.
+    fn sim_mev_bundle(
+        &self,
+        bundle: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> MevBuilder<(SendBundleRequest, SimBundleOverrides), SimBundleResponse>;
+
+    /// Sends a single transaction privately, without exposing it to the public mempool.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#eth_sendprivatetransaction)
+    /// for more details.
+    fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> MevBuilder<(PrivateTransactionRequest,), B256>;
+
+    /// Cancels a previously sent bundle.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#eth_cancelbundle)
+    /// for more details.
+    fn cancel_bundle(&self, request: CancelBundleRequest)
+        -> MevBuilder<(CancelBundleRequest,), ()>;
+
+    /// Returns the stats of a bundle sent to the relay.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#flashbots_getbundlestatsv2)
+    /// for more details.
+    fn get_bundle_stats(
+        &self,
+        bundle_hash: B256,
+        block_number: u64,
+    ) -> MevBuilder<(BundleStatsRequest,), BundleStats>;
+}
+
+impl<P, N> MevApi<N> for P
+where
+    P: Provider<N>,
+    N: Network,
+{
+    fn send_bundle(
+        &self,
+        bundle: EthSendBundle,
+    ) -> MevBuilder<(EthSendBundle,), Option<EthBundleHash>> {
+        MevBuilder::new(self.client().request("eth_sendBundle", (bundle,)))
+    }
+
+    fn call_bundle(
+        &self,
+        bundle: EthCallBundle,
+    ) -> MevBuilder<(EthCallBundle,), EthCallBundleResponse> {
+        MevBuilder::new(self.client().request("eth_callBundle", (bundle,)))
+    }
+
+    fn send_mev_bundle(
+        &self,
+        bundle: SendBundleRequest,
+    ) -> MevBuilder<(SendBundleRequest,), SendBundleResponse> {
+        MevBuilder::new(self.client().request("mev_sendBundle", (bundle,)))
+    }
+
+    fn sim_mev_bundle(
+        &self,
+        bundle: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> MevBuilder<(SendBundleRequest, SimBundleOverrides), SimBundleResponse> {
+        MevBuilder::new(self.client().request("mev_simBundle", (bundle, overrides)))
+    }
+
+    fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> MevBuilder<(PrivateTransactionRequest,), B256> {
+        MevBuilder::new(self.client().request("eth_sendPrivateTransaction", (request,)))
+    }
+
+    fn cancel_bundle(
+        &self,
+        request: CancelBundleRequest,
+    ) -> MevBuilder<(CancelBundleRequest,), ()> {
+        MevBuilder::new(self.client().request("eth_cancelBundle", (request,)))
+    }
+
+    fn get_bundle_stats(
+        &self,
+        bundle_hash: B256,
+        block_number: u64,
+    ) -> MevBuilder<(BundleStatsRequest,), BundleStats> {
+        let request = BundleStatsRequest { bundle_hash, block_number };
+        MevBuilder::new(self.client().request("flashbots_getBundleStatsV2", (request,)))
+    }
+}
+
+/// A builder for MEV RPC requests, which may be authenticated before being sent.
+///
+/// Flashbots relays identify the sender of a request with the `X-Flashbots-Signature` header,
+/// which is set by [`MevBuilder::with_auth`]. The header is attached to the request as an
+/// [`HeaderMap`] [extension], which HTTP transports send along with the body. Requests built
+/// without a signer are sent as is.
+///
+/// The signature commits to the exact body of the request, so it is only valid if the request is
+/// sent on its own, over HTTP.
+///
+/// [extension]: alloy_json_rpc::RequestMeta::extensions
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct MevBuilder<Params, Resp>
+where
+    Params: RpcParam,
+{
+    inner: RpcCall<Params, Resp>,
+    signer: Option<Arc<dyn Signer + Send + Sync>>,
+}
+
+impl<Params, Resp> fmt::Debug for MevBuilder<Params, Resp>
+where
+    Params: RpcParam,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MevBuilder")
+            .field("inner", &self.inner)
+            .field("authenticated", &self.signer.is_some())
+            .finish()
+    }
+}
+
+impl<Params, Resp> MevBuilder<Params, Resp>
+where
+    Params: RpcParam,
+{
+    /// Create a new [`MevBuilder`] from an [`RpcCall`].
+    pub const fn new(inner: RpcCall<Params, Resp>) -> Self {
+        Self { inner, signer: None }
+    }
+
+    /// Authenticate the request with the given signer, by setting the `X-Flashbots-Signature`
+    /// header.
+    pub fn with_auth<S>(mut self, signer: S) -> Self
+    where
+        S: Signer + Send + Sync + 'static,
+    {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+}
+
+impl<Params, Resp> IntoFuture for MevBuilder<Params, Resp>
+where
+    Params: RpcParam + 'static,
+    Resp: RpcReturn,
+{
+    type Output = TransportResult<Resp>;
+    type IntoFuture = RpcFut<'static, Resp>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let Self { mut inner, signer } = self;
+        Box::pin(async move {
+            if let Some(signer) = signer {
+                let body = serde_json::to_vec(inner.request()).map_err(RpcError::ser_err)?;
+                let value = flashbots_signature(&*signer, &body).await?;
+                let extensions = inner.request_mut().meta.extensions_mut();
+                extensions
+                    .get_or_insert_default::<HeaderMap>()
+                    .insert(FLASHBOTS_SIGNATURE_HEADER, value);
+            }
+            inner.await
+        })
+    }
+}
+
+/// Computes the `X-Flashbots-Signature` header value of the given request body.
+///
+/// The header is `<address>:<signature>`, where the signature is the [EIP-191] signature of the
+/// hex-encoded keccak256 hash of the body.
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+async fn flashbots_signature<S>(signer: &S, body: &[u8]) -> TransportResult<HeaderValue>
+where
+    S: Signer + Send + Sync + ?Sized,
+{
+    let message = hex::encode_prefixed(keccak256(body));
+    let signature = signer.sign_message(message.as_bytes()).await.map_err(RpcError::local_usage)?;
+    let value = format!("{}:{}", signer.address(), Bytes::from(signature.as_bytes()));
+    HeaderValue::from_str(&value).map_err(RpcError::local_usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_json_rpc::RequestPacket;
+    use alloy_primitives::PrimitiveSignature as Signature;
+    use alloy_rpc_client::RpcClient;
+    use alloy_signer_local::PrivateKeySigner;
+    use alloy_transport::mock::MockTransport;
+
+    fn headers(req: &RequestPacket) -> Option<HeaderMap> {
+        let RequestPacket::Single(req) = req else { panic!("expected a single request") };
+        req.meta().extensions().get::<HeaderMap>().cloned()
+    }
+
+    #[tokio::test]
+    async fn flashbots_signature_recovers_signer() {
+        let signer = PrivateKeySigner::random();
+        let body = br#"{"method":"eth_sendBundle","params":[],"id":0,"jsonrpc":"2.0"}"#;
+
+        let value = flashbots_signature(&signer, body).await.unwrap();
+        let (address, signature) = value.to_str().unwrap().split_once(':').unwrap();
+        assert_eq!(address, signer.address().to_string());
+
+        let signature = Signature::try_from(hex::decode(signature).unwrap().as_slice()).unwrap();
+        let message = hex::encode_prefixed(keccak256(body));
+        assert_eq!(signature.recover_address_from_msg(message).unwrap(), signer.address());
+    }
+
+    #[tokio::test]
+    async fn send_bundle_with_auth() {
+        let hash = B256::repeat_byte(0x11);
+        let transport = MockTransport::with_result(&EthBundleHash { bundle_hash: hash });
+        let client = RpcClient::new(transport.clone(), false);
+        let provider = ProviderBuilder::new().on_client(client);
+
+        let signer = PrivateKeySigner::random();
+        let bundle = EthSendBundle { block_number: 1, ..Default::default() };
+        let resp = provider.send_bundle(bundle).with_auth(signer.clone()).await.unwrap();
+        assert_eq!(resp, Some(EthBundleHash { bundle_hash: hash }));
+
+        let req = transport.packets().pop().unwrap();
+        let header = headers(&req).unwrap().get(FLASHBOTS_SIGNATURE_HEADER).cloned().unwrap();
+        let body = req.serialize().unwrap();
+        let expected = flashbots_signature(&signer, body.get().as_bytes()).await.unwrap();
+        assert_eq!(header, expected);
+    }
+
+    #[tokio::test]
+    async fn send_bundle_without_auth() {
+        let transport = MockTransport::with_result(&());
+        let client = RpcClient::new(transport.clone(), false);
+        let provider = ProviderBuilder::new().on_client(client);
+
+        let resp = provider.send_bundle(Default::default()).await.unwrap();
+        assert_eq!(resp, None);
+
+        assert_eq!(headers(&transport.packets().pop().unwrap()), None);
+    }
+}
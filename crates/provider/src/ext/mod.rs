@@ -20,11 +20,21 @@ mod debug;
 #[cfg(feature = "debug-api")]
 pub use debug::DebugApi;
 
+#[cfg(feature = "mev-api")]
+mod mev;
+#[cfg(feature = "mev-api")]
+pub use mev::{MevApi, MevBuilder, FLASHBOTS_SIGNATURE_HEADER};
+
 #[cfg(feature = "net-api")]
 mod net;
 #[cfg(feature = "net-api")]
 pub use net::NetApi;
 
+#[cfg(feature = "otterscan-api")]
+mod otterscan;
+#[cfg(feature = "otterscan-api")]
+pub use otterscan::{OtsSearchStream, OtterscanApi};
+
 #[cfg(feature = "trace-api")]
 mod trace;
 #[cfg(feature = "trace-api")]
@@ -40,6 +50,11 @@ mod txpool;
 #[cfg(feature = "txpool-api")]
 pub use txpool::TxPoolApi;
 
+#[cfg(feature = "wallet-calls-api")]
+mod wallet;
+#[cfg(feature = "wallet-calls-api")]
+pub use wallet::{PendingCallsBuilder, WalletCallsApi};
+
 #[cfg(feature = "erc4337-api")]
 mod erc4337;
 #[cfg(feature = "erc4337-api")]
@@ -0,0 +1,368 @@
+//! This module extends the Ethereum JSON-RPC provider with the Otterscan namespace's RPC methods.
+use crate::Provider;
+use alloy_eips::BlockId;
+use alloy_network::{Network, TransactionResponse};
+use alloy_primitives::{Address, Bytes, TxHash};
+use alloy_rpc_types_trace::otterscan::{
+    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
+    TransactionsWithReceipts,
+};
+use alloy_transport::TransportResult;
+use std::pin::Pin;
+
+/// A stream of pages of [`TransactionsWithReceipts`], as returned by the Otterscan search methods.
+#[cfg(not(target_arch = "wasm32"))]
+pub type OtsSearchStream<'a, T> =
+    Pin<Box<dyn futures::Stream<Item = TransportResult<TransactionsWithReceipts<T>>> + Send + 'a>>;
+
+/// A stream of pages of [`TransactionsWithReceipts`], as returned by the Otterscan search methods.
+#[cfg(target_arch = "wasm32")]
+pub type OtsSearchStream<'a, T> =
+    Pin<Box<dyn futures::Stream<Item = TransportResult<TransactionsWithReceipts<T>>> + 'a>>;
+
+/// Otterscan namespace rpc interface.
+///
+/// See [here](https://github.com/otterscan/otterscan/blob/develop/docs/custom-jsonrpc.md) for
+/// more details.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait OtterscanApi<N: Network>: Send + Sync {
+    /// Returns the version of the Otterscan API implemented by the node.
+    async fn ots_get_api_level(&self) -> TransportResult<u64>;
+
+    /// Returns whether the given address contains code at the given block.
+    async fn ots_has_code(&self, address: Address, block: BlockId) -> TransportResult<bool>;
+
+    /// Returns the internal ETH transfers, contract creations and self-destructs of a transaction.
+    async fn ots_get_internal_operations(
+        &self,
+        tx_hash: TxHash,
+    ) -> TransportResult<Vec<InternalOperation>>;
+
+    /// Returns the raw revert data of a transaction, or empty bytes if it succeeded.
+    async fn ots_get_transaction_error(&self, tx_hash: TxHash) -> TransportResult<Bytes>;
+
+    /// Returns the call tree of a transaction.
+    async fn ots_trace_transaction(&self, tx_hash: TxHash) -> TransportResult<Vec<TraceEntry>>;
+
+    /// Returns the details of a block, without its transactions.
+    async fn ots_get_block_details(
+        &self,
+        block_number: u64,
+    ) -> TransportResult<BlockDetails<N::HeaderResponse>>;
+
+    /// Returns a page of the transactions of a block, along with their receipts.
+    async fn ots_get_block_transactions(
+        &self,
+        block_number: u64,
+        page_number: usize,
+        page_size: usize,
+    ) -> TransportResult<OtsBlockTransactions<N::TransactionResponse, N::HeaderResponse>>;
+
+    /// Returns a page of the transactions sent from or to `address`, in blocks before
+    /// `block_number`, most recent first.
+    ///
+    /// A `block_number` of 0 starts the search from the latest block. The page is never split
+    /// within a block, so it may hold more than `page_size` transactions.
+    async fn ots_search_transactions_before(
+        &self,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts<N::TransactionResponse>>;
+
+    /// Returns a page of the transactions sent from or to `address`, in blocks after
+    /// `block_number`, most recent first.
+    ///
+    /// A `block_number` of 0 starts the search from the genesis block. The page is never split
+    /// within a block, so it may hold more than `page_size` transactions.
+    async fn ots_search_transactions_after(
+        &self,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts<N::TransactionResponse>>;
+
+    /// Returns the hash of the transaction sent by `sender` with the given nonce, if any.
+    async fn ots_get_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> TransportResult<Option<TxHash>>;
+
+    /// Returns the creator of a contract, and the transaction which created it.
+    ///
+    /// Returns `None` if the address is not a contract.
+    async fn ots_get_contract_creator(
+        &self,
+        address: Address,
+    ) -> TransportResult<Option<ContractCreator>>;
+
+    /// Returns a stream of pages of the transactions sent from or to `address`, walking back from
+    /// the latest block to the genesis block.
+    ///
+    /// See [`ots_search_transactions_before`](Self::ots_search_transactions_before).
+    fn ots_search_transactions_before_stream(
+        &self,
+        address: Address,
+        page_size: usize,
+    ) -> OtsSearchStream<'_, N::TransactionResponse>;
+
+    /// Returns a stream of pages of the transactions sent from or to `address`, walking forward
+    /// from the genesis block to the latest block.
+    ///
+    /// Each page is ordered most recent first, as returned by the node.
+    ///
+    /// See [`ots_search_transactions_after`](Self::ots_search_transactions_after).
+    fn ots_search_transactions_after_stream(
+        &self,
+        address: Address,
+        page_size: usize,
+    ) -> OtsSearchStream<'_, N::TransactionResponse>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, N> OtterscanApi<N> for P
+where
+    P: Provider<N>,
+    N: Network,
+{
+    async fn ots_get_api_level(&self) -> TransportResult<u64> {
+        self.client().request_noparams("ots_getApiLevel").await
+    }
+
+    async fn ots_has_code(&self, address: Address, block: BlockId) -> TransportResult<bool> {
+        self.client().request("ots_hasCode", (address, block)).await
+    }
+
+    async fn ots_get_internal_operations(
+        &self,
+        tx_hash: TxHash,
+    ) -> TransportResult<Vec<InternalOperation>> {
+        self.client().request("ots_getInternalOperations", (tx_hash,)).await
+    }
+
+    async fn ots_get_transaction_error(&self, tx_hash: TxHash) -> TransportResult<Bytes> {
+        self.client().request("ots_getTransactionError", (tx_hash,)).await
+    }
+
+    async fn ots_trace_transaction(&self, tx_hash: TxHash) -> TransportResult<Vec<TraceEntry>> {
+        self.client().request("ots_traceTransaction", (tx_hash,)).await
+    }
+
+    async fn ots_get_block_details(
+        &self,
+        block_number: u64,
+    ) -> TransportResult<BlockDetails<N::HeaderResponse>> {
+        self.client().request("ots_getBlockDetails", (block_number,)).await
+    }
+
+    async fn ots_get_block_transactions(
+        &self,
+        block_number: u64,
+        page_number: usize,
+        page_size: usize,
+    ) -> TransportResult<OtsBlockTransactions<N::TransactionResponse, N::HeaderResponse>> {
+        self.client()
+            .request("ots_getBlockTransactions", (block_number, page_number, page_size))
+            .await
+    }
+
+    async fn ots_search_transactions_before(
+        &self,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts<N::TransactionResponse>> {
+        self.client()
+            .request("ots_searchTransactionsBefore", (address, block_number, page_size))
+            .await
+    }
+
+    async fn ots_search_transactions_after(
+        &self,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts<N::TransactionResponse>> {
+        self.client()
+            .request("ots_searchTransactionsAfter", (address, block_number, page_size))
+            .await
+    }
+
+    async fn ots_get_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> TransportResult<Option<TxHash>> {
+        self.client().request("ots_getTransactionBySenderAndNonce", (sender, nonce)).await
+    }
+
+    async fn ots_get_contract_creator(
+        &self,
+        address: Address,
+    ) -> TransportResult<Option<ContractCreator>> {
+        self.client().request("ots_getContractCreator", (address,)).await
+    }
+
+    fn ots_search_transactions_before_stream(
+        &self,
+        address: Address,
+        page_size: usize,
+    ) -> OtsSearchStream<'_, N::TransactionResponse> {
+        Box::pin(async_stream::try_stream! {
+            let mut block_number = 0;
+            loop {
+                let page =
+                    self.ots_search_transactions_before(address, block_number, page_size).await?;
+                // The next page starts at the oldest block of this one.
+                let next = page.txs.last().and_then(|tx| tx.block_number());
+                let last_page = page.last_page;
+                yield page;
+
+                match next {
+                    Some(next) if !last_page => block_number = next,
+                    _ => break,
+                }
+            }
+        })
+    }
+
+    fn ots_search_transactions_after_stream(
+        &self,
+        address: Address,
+        page_size: usize,
+    ) -> OtsSearchStream<'_, N::TransactionResponse> {
+        Box::pin(async_stream::try_stream! {
+            let mut block_number = 0;
+            loop {
+                let page =
+                    self.ots_search_transactions_after(address, block_number, page_size).await?;
+                // Pages are ordered most recent first, so the next page starts at the newest
+                // block of this one, and the search ends with the first page.
+                let next = page.txs.first().and_then(|tx| tx.block_number());
+                let first_page = page.first_page;
+                yield page;
+
+                match next {
+                    Some(next) if !first_page => block_number = next,
+                    _ => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_consensus::{Signed, TxEnvelope, TxLegacy};
+    use alloy_json_rpc::SerializedRequest;
+    use alloy_network::Ethereum;
+    use alloy_primitives::{address, PrimitiveSignature as Signature, B256};
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::Transaction;
+    use alloy_transport::mock::{MockResponse, MockTransport};
+    use futures::TryStreamExt;
+
+    const ADDRESS: Address = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+
+    fn tx(block_number: u64) -> Transaction {
+        let tx = TxLegacy { nonce: block_number, ..Default::default() };
+        Transaction {
+            inner: TxEnvelope::Legacy(Signed::new_unchecked(
+                tx,
+                Signature::test_signature(),
+                B256::with_last_byte(block_number as u8),
+            )),
+            block_hash: Some(B256::ZERO),
+            block_number: Some(block_number),
+            transaction_index: Some(0),
+            effective_gas_price: None,
+            from: ADDRESS,
+        }
+    }
+
+    fn page(
+        blocks: &[u64],
+        first_page: bool,
+        last_page: bool,
+    ) -> TransactionsWithReceipts<Transaction> {
+        TransactionsWithReceipts {
+            txs: blocks.iter().copied().map(tx).collect(),
+            receipts: vec![],
+            first_page,
+            last_page,
+        }
+    }
+
+    /// A transport answering search requests from the given pages, indexed by their starting
+    /// block.
+    fn transport(pages: Vec<(u64, TransactionsWithReceipts<Transaction>)>) -> MockTransport {
+        MockTransport::new(move |req| {
+            let block_number = requested_block(req);
+            let (_, page) = pages.iter().find(|(block, _)| *block == block_number).unwrap();
+            MockResponse::success(page)
+        })
+    }
+
+    /// Returns the starting block of a search request.
+    fn requested_block(req: &SerializedRequest) -> u64 {
+        let (_, block_number, _): (Address, u64, usize) =
+            serde_json::from_str(req.params().unwrap().get()).unwrap();
+        block_number
+    }
+
+    fn provider(transport: &MockTransport) -> impl Provider<Ethereum> {
+        ProviderBuilder::new().on_client(RpcClient::new(transport.clone(), false))
+    }
+
+    fn requested_blocks(transport: &MockTransport) -> Vec<u64> {
+        transport.requests().iter().map(requested_block).collect()
+    }
+
+    #[tokio::test]
+    async fn search_transactions_before_stream() {
+        let transport = transport(vec![
+            (0, page(&[30, 20], true, false)),
+            (20, page(&[15, 10, 10], false, false)),
+            (10, page(&[5], false, true)),
+        ]);
+        let provider = provider(&transport);
+
+        let pages: Vec<_> =
+            provider.ots_search_transactions_before_stream(ADDRESS, 2).try_collect().await.unwrap();
+        assert_eq!(pages.len(), 3);
+        assert!(pages[2].last_page);
+        assert_eq!(requested_blocks(&transport), vec![0, 20, 10]);
+    }
+
+    #[tokio::test]
+    async fn search_transactions_after_stream() {
+        let transport = transport(vec![
+            (0, page(&[10, 5], false, true)),
+            (10, page(&[20, 15], false, false)),
+            (20, page(&[30], true, false)),
+        ]);
+        let provider = provider(&transport);
+
+        let pages: Vec<_> =
+            provider.ots_search_transactions_after_stream(ADDRESS, 2).try_collect().await.unwrap();
+        assert_eq!(pages.len(), 3);
+        assert!(pages[2].first_page);
+        assert_eq!(requested_blocks(&transport), vec![0, 10, 20]);
+    }
+
+    #[tokio::test]
+    async fn search_stream_ends_on_empty_page() {
+        let transport = transport(vec![(0, page(&[], true, false))]);
+        let provider = provider(&transport);
+
+        let pages: Vec<_> =
+            provider.ots_search_transactions_before_stream(ADDRESS, 2).try_collect().await.unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(requested_blocks(&transport), vec![0]);
+    }
+}
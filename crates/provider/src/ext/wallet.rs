@@ -0,0 +1,228 @@
+//! This module extends the Ethereum JSON-RPC provider with the [EIP-5792] wallet call methods.
+//!
+//! [EIP-5792]: https://eips.ethereum.org/EIPS/eip-5792
+use crate::{heart::WatchTxError, PendingTransactionError, Provider, RootProvider};
+use alloy_eip5792::{CallReceipt, CallsStatus, SendCallsRequest, WalletCapabilities};
+use alloy_network::Network;
+use alloy_primitives::Address;
+use alloy_transport::TransportResult;
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::{std::Instant, tokio::sleep};
+
+#[cfg(not(target_arch = "wasm32"))]
+use {std::time::Instant, tokio::time::sleep};
+
+/// [EIP-5792] wallet call namespace rpc interface.
+///
+/// [EIP-5792]: https://eips.ethereum.org/EIPS/eip-5792
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait WalletCallsApi<N: Network>: Send + Sync {
+    /// Returns the capabilities supported by the wallet for the given account, per chain.
+    async fn wallet_get_capabilities(
+        &self,
+        address: Address,
+    ) -> TransportResult<WalletCapabilities>;
+
+    /// Submits a batch of calls to the wallet, returning a builder to wait for their inclusion.
+    async fn wallet_send_calls(
+        &self,
+        request: SendCallsRequest,
+    ) -> TransportResult<PendingCallsBuilder<N>>;
+
+    /// Returns the status of a batch of calls submitted with
+    /// [`wallet_send_calls`](Self::wallet_send_calls).
+    async fn wallet_get_calls_status(&self, id: &str) -> TransportResult<CallsStatus>;
+
+    /// Requests the wallet to display the status of a batch of calls to the user.
+    async fn wallet_show_calls_status(&self, id: &str) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, N> WalletCallsApi<N> for P
+where
+    P: Provider<N>,
+    N: Network,
+{
+    async fn wallet_get_capabilities(
+        &self,
+        address: Address,
+    ) -> TransportResult<WalletCapabilities> {
+        self.client().request("wallet_getCapabilities", (address,)).await
+    }
+
+    async fn wallet_send_calls(
+        &self,
+        request: SendCallsRequest,
+    ) -> TransportResult<PendingCallsBuilder<N>> {
+        let id: String = self.client().request("wallet_sendCalls", (request,)).await?;
+        Ok(PendingCallsBuilder::new(self.root().clone(), id))
+    }
+
+    async fn wallet_get_calls_status(&self, id: &str) -> TransportResult<CallsStatus> {
+        self.client().request("wallet_getCallsStatus", (id,)).await
+    }
+
+    async fn wallet_show_calls_status(&self, id: &str) -> TransportResult<()> {
+        self.client().request("wallet_showCallsStatus", (id,)).await
+    }
+}
+
+/// A builder for waiting on a batch of calls submitted with
+/// [`wallet_send_calls`](WalletCallsApi::wallet_send_calls).
+///
+/// The status of the calls is polled with `wallet_getCallsStatus`, at the poll interval of the
+/// client unless overridden, until the wallet reports them as confirmed.
+#[must_use = "this type does nothing unless you call `get_receipts`"]
+#[derive(Debug)]
+pub struct PendingCallsBuilder<N: Network> {
+    provider: RootProvider<N>,
+    id: String,
+    poll_interval: Option<Duration>,
+    timeout: Option<Duration>,
+}
+
+impl<N: Network> PendingCallsBuilder<N> {
+    /// Creates a new pending calls builder.
+    pub const fn new(provider: RootProvider<N>, id: String) -> Self {
+        Self { provider, id, poll_interval: None, timeout: None }
+    }
+
+    /// Returns the provider.
+    pub const fn provider(&self) -> &RootProvider<N> {
+        &self.provider
+    }
+
+    /// Returns the identifier of the batch of calls, as returned by the wallet.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the poll interval, if overridden.
+    pub const fn poll_interval(&self) -> Option<Duration> {
+        self.poll_interval
+    }
+
+    /// Sets the poll interval, overriding the poll interval of the client.
+    pub fn set_poll_interval(&mut self, poll_interval: Duration) {
+        self.poll_interval = Some(poll_interval);
+    }
+
+    /// Sets the poll interval, overriding the poll interval of the client.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// Returns the timeout.
+    pub const fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets the timeout.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Sets the timeout.
+    pub const fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Waits for the calls to be confirmed, and returns the receipts of the transactions which
+    /// included them.
+    ///
+    /// Returns [`WatchTxError::Timeout`] if the calls are not confirmed within the timeout.
+    pub async fn get_receipts(self) -> Result<Vec<CallReceipt>, PendingTransactionError> {
+        let poll_interval =
+            self.poll_interval.unwrap_or_else(|| self.provider.client().poll_interval());
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let status = self.provider.wallet_get_calls_status(&self.id).await?;
+            if status.is_confirmed() {
+                return Ok(status.receipts.unwrap_or_default());
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() + poll_interval > deadline) {
+                return Err(WatchTxError::Timeout.into());
+            }
+            sleep(poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_eip5792::CallParams;
+    use alloy_network::Ethereum;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::mock::{MockResponse, MockTransport};
+    use std::sync::Mutex;
+
+    const RECEIPT: &str = r#"{"logs":[],"status":"0x1","chainId":"0x1","blockHash":"0xf19bbafd9fd0124ec110b848e8de4ab4f62bf60c189524e54213285e7f540d4a","blockNumber":"0xabcd","gasUsed":"0xdef","transactionHash":"0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaff"}"#;
+
+    /// A transport answering each request with the next of the given results.
+    fn transport(results: Vec<String>) -> MockTransport {
+        let results = Mutex::new(results.into_iter());
+        MockTransport::new(move |_| MockResponse::raw(results.lock().unwrap().next().unwrap()))
+    }
+
+    fn provider(transport: &MockTransport) -> impl Provider<Ethereum> {
+        ProviderBuilder::new().on_client(RpcClient::new(transport.clone(), false))
+    }
+
+    #[tokio::test]
+    async fn send_calls_and_get_receipts() {
+        let transport = transport(vec![
+            r#""0xbatch""#.to_string(),
+            r#"{"status":"PENDING"}"#.to_string(),
+            format!(r#"{{"status":"CONFIRMED","receipts":[{RECEIPT}]}}"#),
+        ]);
+        let provider = provider(&transport);
+
+        let request = SendCallsRequest {
+            version: "1.0".to_string(),
+            from: Address::ZERO,
+            calls: vec![CallParams {
+                to: Some(Address::ZERO),
+                data: None,
+                value: None,
+                chain_id: None,
+            }],
+            capabilities: None,
+        };
+        let pending = provider.wallet_send_calls(request).await.unwrap();
+        assert_eq!(pending.id(), "0xbatch");
+
+        let receipts =
+            pending.with_poll_interval(Duration::from_millis(1)).get_receipts().await.unwrap();
+        assert_eq!(receipts.len(), 1);
+        assert!(receipts[0].is_success());
+
+        let requests = transport.requests();
+        let methods: Vec<_> = requests.iter().map(|req| req.method()).collect();
+        assert_eq!(methods, ["wallet_sendCalls", "wallet_getCallsStatus", "wallet_getCallsStatus"]);
+        assert_eq!(requests[1].params().unwrap().get(), r#"["0xbatch"]"#);
+    }
+
+    #[tokio::test]
+    async fn get_receipts_timeout() {
+        let transport = transport(vec![r#"{"status":"PENDING"}"#.to_string(); 3]);
+        let provider = provider(&transport);
+
+        let err = PendingCallsBuilder::new(provider.root().clone(), "0xbatch".to_string())
+            .with_poll_interval(Duration::from_millis(10))
+            .with_timeout(Some(Duration::from_millis(15)))
+            .get_receipts()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PendingTransactionError::TxWatcher(WatchTxError::Timeout)));
+    }
+}
@@ -12,6 +12,11 @@ pub use chain_id::ChainIdFiller;
 mod wallet;
 pub use wallet::WalletFiller;
 
+mod payer;
+#[cfg(feature = "reqwest")]
+pub use payer::HttpPayer;
+pub use payer::{PayerFiller, TxPayer, DEFAULT_PAYER_TTL};
+
 mod nonce;
 pub use nonce::{CachedNonceManager, NonceFiller, NonceManager, SimpleNonceManager};
 
@@ -0,0 +1,309 @@
+use std::{fmt, time::Duration};
+
+use crate::{
+    fillers::{FillerControlFlow, TxFiller},
+    provider::SendableTx,
+    Provider,
+};
+use alloy_consensus::{BlockHeader, TxSponsoredPayer, TxType};
+use alloy_json_rpc::RpcError;
+use alloy_network::{
+    BlockResponse, Network, TransactionBuilder, TransactionBuilderSponsored, TxSigner,
+};
+use alloy_primitives::PrimitiveSignature as Signature;
+use alloy_rpc_types_eth::{BlockNumberOrTag, BlockTransactionsKind};
+use alloy_transport::{TransportErrorKind, TransportResult};
+use futures_utils_wasm::impl_future;
+
+/// The default time-to-live of a sponsored transaction, used to compute its `expired_time`.
+pub const DEFAULT_PAYER_TTL: Duration = Duration::from_secs(300);
+
+/// A gas payer, able to co-sign sponsored transactions.
+///
+/// This trait is implemented for every local [`TxSigner`]. Remote sponsors can be reached with
+/// [`HttpPayer`].
+pub trait TxPayer: Clone + Send + Sync + fmt::Debug {
+    /// Produce the payer signature for the given sponsored transaction.
+    fn sign_payer(
+        &self,
+        tx: &mut TxSponsoredPayer,
+    ) -> impl_future!(<Output = TransportResult<Signature>>);
+}
+
+impl<S> TxPayer for S
+where
+    S: TxSigner<Signature> + Clone + Send + Sync + fmt::Debug,
+{
+    async fn sign_payer(&self, tx: &mut TxSponsoredPayer) -> TransportResult<Signature> {
+        self.sign_transaction(tx).await.map_err(RpcError::local_usage)
+    }
+}
+
+/// A [`TxPayer`] which delegates the payer signature to a remote sponsor over HTTP.
+///
+/// The transaction to sponsor is posted as a JSON [`TransactionRequest`], with `from` set to the
+/// sender and the payer signature omitted. The sponsor is expected to answer with the JSON
+/// payer [`Signature`], or with an error status if it refuses to sponsor the transaction.
+///
+/// [`TransactionRequest`]: alloy_rpc_types_eth::TransactionRequest
+#[cfg(feature = "reqwest")]
+#[derive(Clone, Debug)]
+pub struct HttpPayer {
+    client: reqwest::Client,
+    url: url::Url,
+}
+
+#[cfg(feature = "reqwest")]
+impl HttpPayer {
+    /// Create a new [`HttpPayer`] posting to the given sponsor endpoint.
+    pub fn new(url: url::Url) -> Self {
+        Self { client: Default::default(), url }
+    }
+
+    /// Create a new [`HttpPayer`] with the given client and sponsor endpoint.
+    pub const fn with_client(client: reqwest::Client, url: url::Url) -> Self {
+        Self { client, url }
+    }
+
+    /// Get a reference to the sponsor endpoint.
+    pub const fn url(&self) -> &url::Url {
+        &self.url
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl TxPayer for HttpPayer {
+    async fn sign_payer(&self, tx: &mut TxSponsoredPayer) -> TransportResult<Signature> {
+        let mut request: alloy_rpc_types_eth::TransactionRequest = tx.tx.clone().into();
+        request.from = Some(tx.sender);
+        request.payer_v = None;
+        request.payer_r = None;
+        request.payer_s = None;
+
+        let body = serde_json::to_vec(&request).map_err(RpcError::ser_err)?;
+        let resp = self
+            .client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let status = resp.status();
+        let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+
+        if !status.is_success() {
+            return Err(TransportErrorKind::http_error(
+                status.as_u16(),
+                String::from_utf8_lossy(&body).into_owned(),
+            ));
+        }
+
+        serde_json::from_slice(&body)
+            .map_err(|err| RpcError::deser_err(err, String::from_utf8_lossy(&body)))
+    }
+}
+
+/// A [`TxFiller`] that obtains the gas payer signature of sponsored transactions.
+///
+/// Requests which are not sponsored are left untouched. For sponsored requests, the filler
+/// waits for every other field of the transaction to be filled, sets `expired_time` to the
+/// latest block timestamp plus the configured TTL if unset, and asks the [`TxPayer`] to sign.
+///
+/// The sender signature commits to the payer signature, so this filler must be placed before
+/// the [`WalletFiller`] in the stack. The wallet filler will wait for the payer signature to be
+/// set before signing.
+///
+/// # Example
+///
+/// ```
+/// # use alloy_network::{NetworkWallet, EthereumWallet, Ethereum, TxSigner};
+/// # use alloy_primitives::PrimitiveSignature;
+/// # use alloy_provider::{fillers::TxPayer, ProviderBuilder, RootProvider, Provider};
+/// # async fn test<W, P>(url: url::Url, wallet: W, payer: P) -> Result<(), Box<dyn std::error::Error>>
+/// # where
+/// #     W: NetworkWallet<Ethereum> + Clone,
+/// #     P: TxPayer,
+/// # {
+/// let provider = ProviderBuilder::new()
+///     .payer(payer)
+///     .wallet(wallet)
+///     .on_http(url);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`WalletFiller`]: crate::fillers::WalletFiller
+#[derive(Clone, Debug)]
+pub struct PayerFiller<P> {
+    payer: P,
+    ttl: Duration,
+}
+
+impl<P> AsRef<P> for PayerFiller<P> {
+    fn as_ref(&self) -> &P {
+        &self.payer
+    }
+}
+
+impl<P> AsMut<P> for PayerFiller<P> {
+    fn as_mut(&mut self) -> &mut P {
+        &mut self.payer
+    }
+}
+
+impl<P> PayerFiller<P> {
+    /// Creates a new payer filler with the given payer and the [`DEFAULT_PAYER_TTL`].
+    pub const fn new(payer: P) -> Self {
+        Self { payer, ttl: DEFAULT_PAYER_TTL }
+    }
+
+    /// Sets the time-to-live used to compute the `expired_time` of sponsored transactions.
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns the time-to-live of sponsored transactions.
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+impl<P, N> TxFiller<N> for PayerFiller<P>
+where
+    P: TxPayer,
+    N: Network,
+    N::TransactionRequest: TransactionBuilderSponsored,
+{
+    /// The `expired_time` of the transaction.
+    type Fillable = u64;
+
+    fn status(&self, tx: &<N as Network>::TransactionRequest) -> FillerControlFlow {
+        if tx.output_tx_type().into() != TxType::Sponsored as u8 || tx.payer_signature().is_some() {
+            return FillerControlFlow::Finished;
+        }
+
+        // `expired_time` is filled by this filler.
+        let missing: Vec<_> = match tx.payer_signing_tx() {
+            Ok(_) => return FillerControlFlow::Ready,
+            Err(missing) => missing.into_iter().filter(|key| *key != "expired_time").collect(),
+        };
+
+        if missing.is_empty() {
+            FillerControlFlow::Ready
+        } else {
+            FillerControlFlow::missing("Payer", missing)
+        }
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<N>) {}
+
+    async fn prepare<Pr>(
+        &self,
+        provider: &Pr,
+        tx: &<N as Network>::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        Pr: Provider<N>,
+    {
+        if let Some(expired_time) = tx.expired_time() {
+            return Ok(expired_time);
+        }
+
+        let block = provider
+            .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
+            .await?
+            .ok_or_else(|| RpcError::local_usage_str("latest block not found"))?;
+
+        Ok(block.header().timestamp().saturating_add(self.ttl.as_secs()))
+    }
+
+    async fn fill(
+        &self,
+        expired_time: Self::Fillable,
+        mut tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        if let Some(builder) = tx.as_mut_builder() {
+            if builder.expired_time().is_none() {
+                builder.set_expired_time(expired_time);
+            }
+
+            let mut payer_tx = builder.payer_signing_tx().map_err(|missing| {
+                RpcError::local_usage_str(&format!("missing properties: {:?}", missing))
+            })?;
+            let signature = self.payer.sign_payer(&mut payer_tx).await?;
+            builder.set_payer_signature(signature);
+        }
+
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_network::{Ethereum, TransactionBuilder};
+    use alloy_primitives::{address, U256};
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_signer_local::PrivateKeySigner;
+
+    fn sponsored_request() -> TransactionRequest {
+        TransactionRequest::default()
+            .with_from(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+            .with_to(address!("0000000000000000000000000000000000000001"))
+            .with_value(U256::from(100))
+            .with_nonce(0)
+            .with_chain_id(2020)
+            .with_gas_limit(21000)
+            .with_max_fee_per_gas(20e9 as u128)
+            .with_max_priority_fee_per_gas(1e9 as u128)
+            .transaction_type(100)
+    }
+
+    #[test]
+    fn status_ignores_non_sponsored() {
+        let filler = PayerFiller::new(PrivateKeySigner::random());
+        let tx = TransactionRequest::default().with_nonce(0);
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx).is_finished());
+    }
+
+    #[test]
+    fn status_waits_for_other_fields() {
+        let filler = PayerFiller::new(PrivateKeySigner::random());
+
+        let mut tx = sponsored_request();
+        tx.nonce = None;
+        tx.gas = None;
+        assert_eq!(
+            TxFiller::<Ethereum>::status(&filler, &tx),
+            FillerControlFlow::missing("Payer", vec!["nonce", "gas_limit"])
+        );
+
+        let tx = sponsored_request();
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx).is_ready());
+    }
+
+    #[tokio::test]
+    async fn fills_payer_signature() {
+        let payer = PrivateKeySigner::random();
+        let filler = PayerFiller::new(payer.clone());
+
+        let tx = TxFiller::<Ethereum>::fill(
+            &filler,
+            0x66d29dd2,
+            SendableTx::Builder(sponsored_request()),
+        )
+        .await
+        .unwrap();
+        let tx = tx.as_builder().unwrap();
+
+        assert_eq!(tx.expired_time, Some(0x66d29dd2));
+        assert!(TxFiller::<Ethereum>::status(&filler, tx).is_finished());
+
+        let payer_tx = tx.payer_signing_tx().unwrap();
+        let signature = tx.payer_signature().unwrap();
+        let hash = alloy_consensus::SignableTransaction::signature_hash(&payer_tx);
+        assert_eq!(signature.recover_address_from_prehash(&hash).unwrap(), payer.address());
+    }
+}
@@ -1,19 +1,28 @@
 //! Block heartbeat and pending transaction watcher.
 
 use crate::{Provider, RootProvider};
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, SponsoredTxExpired, TxType, Typed2718};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
 use alloy_json_rpc::RpcError;
-use alloy_network::{BlockResponse, Network};
+use alloy_network::{
+    BlockResponse, Network, TransactionBuilder, TransactionBuilder4844, TransactionResponse,
+};
 use alloy_primitives::{
     map::{B256HashMap, B256HashSet},
-    TxHash, B256,
+    TxHash, B256, U256,
 };
 use alloy_transport::{utils::Spawnable, TransportError};
-use futures::{stream::StreamExt, FutureExt, Stream};
+use futures::{
+    future::{select_ok, SelectOk},
+    ready,
+    stream::StreamExt,
+    FutureExt, Stream,
+};
 use std::{
-    collections::{BTreeMap, VecDeque},
+    collections::{btree_map::Entry, BTreeMap, VecDeque},
     fmt,
     future::Future,
+    task::Poll,
     time::Duration,
 };
 use tokio::{
@@ -138,6 +147,13 @@ impl<N: Network> PendingTransactionBuilder<N> {
         self
     }
 
+    /// Returns the hashes of the transactions replaced by this one, oldest first.
+    ///
+    /// See [`speed_up`](Self::speed_up) and [`cancel`](Self::cancel).
+    pub fn replaced_tx_hashes(&self) -> &[TxHash] {
+        self.config.replaced_tx_hashes()
+    }
+
     /// Returns the number of confirmations to wait for.
     #[doc(alias = "confirmations")]
     pub const fn required_confirmations(&self) -> u64 {
@@ -173,6 +189,26 @@ impl<N: Network> PendingTransactionBuilder<N> {
         self
     }
 
+    /// Returns the expiry of the transaction.
+    pub const fn expired_time(&self) -> Option<u64> {
+        self.config.expired_time()
+    }
+
+    /// Sets the expiry of the transaction.
+    ///
+    /// See [`PendingTransactionConfig::set_expired_time`].
+    pub fn set_expired_time(&mut self, expired_time: Option<u64>) {
+        self.config.set_expired_time(expired_time);
+    }
+
+    /// Sets the expiry of the transaction.
+    ///
+    /// See [`PendingTransactionConfig::set_expired_time`].
+    pub const fn with_expired_time(mut self, expired_time: Option<u64>) -> Self {
+        self.config.expired_time = expired_time;
+        self
+    }
+
     /// Registers the watching configuration with the provider.
     ///
     /// This does not wait for the transaction to be confirmed, but returns a [`PendingTransaction`]
@@ -210,7 +246,8 @@ impl<N: Network> PendingTransactionBuilder<N> {
     ///   confirmed.
     /// - [`watch`](Self::watch) for watching the transaction without fetching the receipt.
     pub async fn get_receipt(self) -> Result<N::ReceiptResponse, PendingTransactionError> {
-        let hash = self.config.tx_hash;
+        let mut hashes = self.config.replaced.clone();
+        hashes.push(self.config.tx_hash);
         let mut pending_tx = self.provider.watch_pending_transaction(self.config).await?;
 
         // FIXME: this is a hotfix to prevent a race condition where the heartbeat would miss the
@@ -223,15 +260,18 @@ impl<N: Network> PendingTransactionBuilder<N> {
             select! {
                 _ = interval.tick() => {},
                 res = &mut pending_tx => {
-                    let _ = res?;
+                    // Only the confirmed transaction can have a receipt.
+                    hashes = vec![res?];
                     confirmed = true;
                 }
             }
 
             // try to fetch the receipt
-            let receipt = self.provider.get_transaction_receipt(hash).await?;
-            if let Some(receipt) = receipt {
-                return Ok(receipt);
+            for hash in hashes.iter().rev() {
+                let receipt = self.provider.get_transaction_receipt(*hash).await?;
+                if let Some(receipt) = receipt {
+                    return Ok(receipt);
+                }
             }
 
             if confirmed {
@@ -241,6 +281,159 @@ impl<N: Network> PendingTransactionBuilder<N> {
     }
 }
 
+impl<N: Network> PendingTransactionBuilder<N>
+where
+    N::TransactionRequest: TransactionBuilder4844,
+{
+    /// Replaces the transaction with a copy paying higher fees, in order to speed up its
+    /// inclusion.
+    ///
+    /// The transaction is fetched by hash, and resent through the given provider with the same
+    /// nonce and fees bumped by `bump_pct` percent, so that it is signed by its wallet. The bump
+    /// is at least 10%, or 100% for blob transactions, as required by nodes to accept a
+    /// replacement. Nodes do not return the sidecar of blob transactions, so these must be
+    /// replaced with [`replace`](Self::replace) instead.
+    ///
+    /// Returns the builder of the replacement, which resolves when any of the transactions is
+    /// confirmed, with the hash of the confirmed one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider, tx: alloy_rpc_types_eth::transaction::TransactionRequest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pending = provider.send_transaction(tx).await?;
+    /// let pending = pending.speed_up(&provider, 20).await?;
+    /// let mined = pending.watch().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn speed_up<P: Provider<N>>(
+        self,
+        provider: &P,
+        bump_pct: u64,
+    ) -> Result<Self, PendingTransactionError> {
+        let mut tx = self.replaced_request().await?;
+        bump_fees::<N>(&mut tx, bump_pct);
+        self.replace(provider, tx).await
+    }
+
+    /// Cancels the transaction, by replacing it with an empty transfer from the sender to itself.
+    ///
+    /// The replacement has the same nonce, and fees bumped by the minimum required for nodes to
+    /// accept it, and is sent through the given provider, so that it is signed by its wallet. Blob
+    /// transactions can only be replaced by blob transactions, and cannot be cancelled.
+    ///
+    /// Returns the builder of the replacement, which resolves when any of the transactions is
+    /// confirmed, with the hash of the confirmed one.
+    pub async fn cancel<P: Provider<N>>(
+        self,
+        provider: &P,
+    ) -> Result<Self, PendingTransactionError> {
+        let replaced = self.replaced_request().await?;
+        if replaced.max_fee_per_blob_gas().is_some() {
+            return Err(RpcError::local_usage_str("blob transactions cannot be cancelled").into());
+        }
+        let (Some(from), Some(nonce)) = (replaced.from(), replaced.nonce()) else {
+            return Err(RpcError::local_usage_str("missing sender or nonce").into());
+        };
+
+        let mut tx = N::TransactionRequest::default()
+            .with_from(from)
+            .with_to(from)
+            .with_value(U256::ZERO)
+            .with_nonce(nonce)
+            .with_gas_limit(CANCEL_GAS_LIMIT);
+        if let Some(chain_id) = replaced.chain_id() {
+            tx.set_chain_id(chain_id);
+        }
+        match (replaced.max_fee_per_gas(), replaced.max_priority_fee_per_gas()) {
+            (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
+                tx.set_max_fee_per_gas(max_fee_per_gas);
+                tx.set_max_priority_fee_per_gas(max_priority_fee_per_gas);
+            }
+            _ => tx.set_gas_price(replaced.gas_price().unwrap_or_default()),
+        }
+        bump_fees::<N>(&mut tx, MIN_REPLACEMENT_BUMP);
+        self.replace(provider, tx).await
+    }
+
+    /// Sends a replacement of the transaction through the given provider.
+    ///
+    /// The replacement must have the nonce of the transaction, and fees high enough for nodes to
+    /// accept it. Returns the builder of the replacement, which resolves when any of the
+    /// transactions is confirmed, with the hash of the confirmed one.
+    pub async fn replace<P: Provider<N>>(
+        mut self,
+        provider: &P,
+        tx: N::TransactionRequest,
+    ) -> Result<Self, PendingTransactionError> {
+        let replacement = *provider.send_transaction(tx).await?.tx_hash();
+        debug!(tx=%self.config.tx_hash, %replacement, "replaced transaction");
+        let replaced = std::mem::replace(&mut self.config.tx_hash, replacement);
+        self.config.replaced.push(replaced);
+        Ok(self)
+    }
+
+    /// Fetches the transaction, returning a request with the same fields.
+    async fn replaced_request(&self) -> Result<N::TransactionRequest, PendingTransactionError> {
+        let tx = self
+            .provider
+            .get_transaction_by_hash(self.config.tx_hash)
+            .await?
+            .ok_or(RpcError::NullResp)?;
+        if tx.is_type(TxType::Sponsored as u8) {
+            // The payer signature commits to the fees.
+            return Err(RpcError::local_usage_str(
+                "sponsored transactions must be replaced with a new payer signature",
+            )
+            .into());
+        }
+
+        let envelope = N::TxEnvelope::decode_2718(&mut tx.as_ref().encoded_2718().as_slice())
+            .map_err(RpcError::local_usage)?;
+        let request: N::TransactionRequest = envelope.into();
+        Ok(request.with_from(tx.from()))
+    }
+}
+
+/// The gas used by a plain transfer, used by the replacement of a cancelled transaction.
+const CANCEL_GAS_LIMIT: u64 = 21_000;
+
+/// The minimum fee bump, in percent, for nodes to accept a replacement transaction.
+const MIN_REPLACEMENT_BUMP: u64 = 10;
+
+/// The minimum fee bump, in percent, for nodes to accept a replacement blob transaction.
+const MIN_BLOB_REPLACEMENT_BUMP: u64 = 100;
+
+/// Bumps the fees of a replacement transaction by `bump_pct` percent, or the minimum required for
+/// nodes to accept it.
+fn bump_fees<N: Network>(tx: &mut N::TransactionRequest, bump_pct: u64)
+where
+    N::TransactionRequest: TransactionBuilder4844,
+{
+    let min_bump = if tx.max_fee_per_blob_gas().is_some() {
+        MIN_BLOB_REPLACEMENT_BUMP
+    } else {
+        MIN_REPLACEMENT_BUMP
+    };
+    let bump_pct = bump_pct.max(min_bump) as u128;
+    // Nodes require every fee to increase.
+    let bump = |fee: u128| fee.saturating_add(fee.saturating_mul(bump_pct).div_ceil(100).max(1));
+
+    if let Some(gas_price) = tx.gas_price() {
+        tx.set_gas_price(bump(gas_price));
+    }
+    if let Some(max_fee_per_gas) = tx.max_fee_per_gas() {
+        tx.set_max_fee_per_gas(bump(max_fee_per_gas));
+    }
+    if let Some(max_priority_fee_per_gas) = tx.max_priority_fee_per_gas() {
+        tx.set_max_priority_fee_per_gas(bump(max_priority_fee_per_gas));
+    }
+    if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas() {
+        tx.set_max_fee_per_blob_gas(bump(max_fee_per_blob_gas));
+    }
+}
+
 /// Configuration for watching a pending transaction.
 ///
 /// This type can be used to create a [`PendingTransactionBuilder`], but in general it is only used
@@ -258,12 +451,24 @@ pub struct PendingTransactionConfig {
 
     /// Optional timeout for the transaction.
     timeout: Option<Duration>,
+
+    /// Optional expiry of the transaction, as a block timestamp.
+    expired_time: Option<u64>,
+
+    /// The hashes of the transactions replaced by this one, which may be mined instead.
+    pub(crate) replaced: Vec<TxHash>,
 }
 
 impl PendingTransactionConfig {
     /// Create a new watch for a transaction.
     pub const fn new(tx_hash: TxHash) -> Self {
-        Self { tx_hash, required_confirmations: 1, timeout: None }
+        Self {
+            tx_hash,
+            required_confirmations: 1,
+            timeout: None,
+            expired_time: None,
+            replaced: Vec::new(),
+        }
     }
 
     /// Returns the transaction hash.
@@ -320,6 +525,35 @@ impl PendingTransactionConfig {
         self
     }
 
+    /// Returns the expiry of the transaction.
+    pub const fn expired_time(&self) -> Option<u64> {
+        self.expired_time
+    }
+
+    /// Sets the expiry of the transaction, as a block timestamp.
+    ///
+    /// This is the `expired_time` of sponsored transactions. Once the chain reaches a block with
+    /// a timestamp at or past the expiry without including the transaction, the watch fails with
+    /// [`WatchTxError::Expired`] instead of waiting for the timeout.
+    pub fn set_expired_time(&mut self, expired_time: Option<u64>) {
+        self.expired_time = expired_time;
+    }
+
+    /// Sets the expiry of the transaction, as a block timestamp.
+    ///
+    /// See [`set_expired_time`](Self::set_expired_time).
+    pub const fn with_expired_time(mut self, expired_time: Option<u64>) -> Self {
+        self.expired_time = expired_time;
+        self
+    }
+
+    /// Returns the hashes of the transactions replaced by this one, oldest first.
+    ///
+    /// The watch resolves when any of them, or this one, is confirmed.
+    pub fn replaced_tx_hashes(&self) -> &[TxHash] {
+        &self.replaced
+    }
+
     /// Wraps this configuration with a provider to expose watching methods.
     pub const fn with_provider<N: Network>(
         self,
@@ -335,6 +569,10 @@ pub enum WatchTxError {
     /// Transaction was not confirmed after configured timeout.
     #[error("transaction was not confirmed within the timeout")]
     Timeout,
+
+    /// Transaction expired before being included in a block.
+    #[error("transaction was not included before its expiry: {0}")]
+    Expired(SponsoredTxExpired),
 }
 
 #[doc(alias = "TransactionWatcher")]
@@ -367,6 +605,10 @@ pub struct PendingTransaction {
     /// The receiver for the notification.
     // TODO: send a receipt?
     pub(crate) rx: oneshot::Receiver<Result<(), WatchTxError>>,
+    /// The transactions replaced by this one, which may be confirmed instead.
+    pub(crate) replaced: Option<SelectOk<PendingTransaction>>,
+    /// The error of this transaction, returned if the replaced transactions fail too.
+    pub(crate) error: Option<PendingTransactionError>,
 }
 
 impl fmt::Debug for PendingTransaction {
@@ -380,7 +622,16 @@ impl PendingTransaction {
     pub fn ready(tx_hash: TxHash) -> Self {
         let (tx, rx) = oneshot::channel();
         tx.send(Ok(())).ok(); // Make sure that the receiver is notified already.
-        Self { tx_hash, rx }
+        Self { tx_hash, rx, replaced: None, error: None }
+    }
+
+    /// Resolves when either this transaction or any of the transactions it replaced is confirmed,
+    /// with the hash of the confirmed one.
+    pub(crate) fn or_replaced(mut self, replaced: Vec<Self>) -> Self {
+        if !replaced.is_empty() {
+            self.replaced = Some(select_ok(replaced));
+        }
+        self
     }
 
     /// Returns this transaction's hash.
@@ -397,10 +648,25 @@ impl Future for PendingTransaction {
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Self::Output> {
-        self.rx.poll_unpin(cx).map(|res| {
-            res??;
-            Ok(self.tx_hash)
-        })
+        let this = &mut *self;
+        if this.error.is_none() {
+            if let Poll::Ready(res) = this.rx.poll_unpin(cx) {
+                match res.map_err(Into::into).and_then(|res| res.map_err(Into::into)) {
+                    Ok(()) => return Poll::Ready(Ok(this.tx_hash)),
+                    Err(err) if this.replaced.is_none() => return Poll::Ready(Err(err)),
+                    // Wait for the replaced transactions.
+                    Err(err) => this.error = Some(err),
+                }
+            }
+        }
+
+        if let Some(replaced) = &mut this.replaced {
+            match ready!(replaced.poll_unpin(cx)) {
+                Ok((tx_hash, _)) => return Poll::Ready(Ok(tx_hash)),
+                Err(_) => this.replaced = None,
+            }
+        }
+        this.error.take().map_or(Poll::Pending, |err| Poll::Ready(Err(err)))
     }
 }
 
@@ -422,7 +688,7 @@ impl<N: Network> HeartbeatHandle<N> {
         let (tx, rx) = oneshot::channel();
         let tx_hash = config.tx_hash;
         match self.tx.send(TxWatcher { config, received_at_block, tx }).await {
-            Ok(()) => Ok(PendingTransaction { tx_hash, rx }),
+            Ok(()) => Ok(PendingTransaction { tx_hash, rx, replaced: None, error: None }),
             Err(e) => Err(e.0.config),
         }
     }
@@ -452,6 +718,12 @@ pub(crate) struct Heartbeat<N, S> {
     /// Ordered map of transactions to reap at a certain time.
     reap_at: BTreeMap<Instant, B256>,
 
+    /// Ordered map of transactions expiring at a certain block timestamp.
+    expire_at: BTreeMap<u64, Vec<B256>>,
+
+    /// Timestamp of the latest block.
+    latest_timestamp: Option<u64>,
+
     _network: std::marker::PhantomData<N>,
 }
 
@@ -464,6 +736,8 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
             unconfirmed: Default::default(),
             waiting_confs: Default::default(),
             reap_at: Default::default(),
+            expire_at: Default::default(),
+            latest_timestamp: None,
             _network: Default::default(),
         }
     }
@@ -493,17 +767,62 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
         let to_reap = std::mem::replace(&mut self.reap_at, to_keep);
 
         for tx_hash in to_reap.values() {
-            if let Some(watcher) = self.unconfirmed.remove(tx_hash) {
+            if let Some(watcher) = self.remove_unconfirmed(tx_hash) {
                 debug!(tx=%tx_hash, "reaped");
                 watcher.notify(Err(WatchTxError::Timeout));
             }
         }
     }
 
+    /// Fail any unconfirmed transaction which expired at or before the given block timestamp.
+    fn reap_expired(&mut self, timestamp: u64) {
+        let to_keep = timestamp
+            .checked_add(1)
+            .map(|next| self.expire_at.split_off(&next))
+            .unwrap_or_default();
+        let to_reap = std::mem::replace(&mut self.expire_at, to_keep);
+
+        for (expired_time, tx_hash) in
+            to_reap.into_iter().flat_map(|(t, hashes)| hashes.into_iter().map(move |h| (t, h)))
+        {
+            if let Some(watcher) = self.unconfirmed.remove(&tx_hash) {
+                debug!(tx=%tx_hash, %expired_time, %timestamp, "expired");
+                watcher.notify(Err(WatchTxError::Expired(SponsoredTxExpired {
+                    expired_time,
+                    timestamp,
+                })));
+            }
+        }
+    }
+
+    /// Insert a transaction into the unconfirmed list, tracking its expiry if any.
+    fn insert_unconfirmed(&mut self, watcher: TxWatcher) {
+        let tx_hash = watcher.config.tx_hash;
+        if let Some(expired_time) = watcher.config.expired_time {
+            self.expire_at.entry(expired_time).or_default().push(tx_hash);
+        }
+        self.unconfirmed.insert(tx_hash, watcher);
+    }
+
+    /// Remove a transaction from the unconfirmed list, and stop tracking its expiry.
+    fn remove_unconfirmed(&mut self, tx_hash: &B256) -> Option<TxWatcher> {
+        let watcher = self.unconfirmed.remove(tx_hash)?;
+        if let Some(expired_time) = watcher.config.expired_time {
+            if let Entry::Occupied(mut entry) = self.expire_at.entry(expired_time) {
+                entry.get_mut().retain(|hash| hash != tx_hash);
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+        Some(watcher)
+    }
+
     /// Reap transactions overridden by the reorg.
     /// Accepts new chain height as an argument, and drops any subscriptions
     /// that were received in blocks affected by the reorg (e.g. >= new_height).
     fn move_reorg_to_unconfirmed(&mut self, new_height: u64) {
+        let mut reorged = Vec::new();
         for waiters in self.waiting_confs.values_mut() {
             *waiters = std::mem::take(waiters).into_iter().filter_map(|watcher| {
                 if let Some(received_at_block) = watcher.received_at_block {
@@ -511,13 +830,16 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
                     if received_at_block >= new_height {
                         let hash = watcher.config.tx_hash;
                         debug!(tx=%hash, %received_at_block, %new_height, "return to unconfirmed due to reorg");
-                        self.unconfirmed.insert(hash, watcher);
+                        reorged.push(watcher);
                         return None;
                     }
                 }
                 Some(watcher)
             }).collect();
         }
+        for watcher in reorged {
+            self.insert_unconfirmed(watcher);
+        }
     }
 
     /// Handle a watch instruction by adding it to the watch list, and
@@ -556,7 +878,19 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
             }
         }
 
-        self.unconfirmed.insert(to_watch.config.tx_hash, to_watch);
+        // The transaction can no longer be included if the chain already passed its expiry.
+        if let Some(expired_time) = to_watch.config.expired_time {
+            if let Some(timestamp) = self.latest_timestamp.filter(|t| *t >= expired_time) {
+                debug!(tx=%to_watch.config.tx_hash, %expired_time, %timestamp, "already expired");
+                to_watch.notify(Err(WatchTxError::Expired(SponsoredTxExpired {
+                    expired_time,
+                    timestamp,
+                })));
+                return;
+            }
+        }
+
+        self.insert_unconfirmed(to_watch);
     }
 
     fn add_to_waiting_list(&mut self, watcher: TxWatcher, block_height: u64) {
@@ -575,6 +909,7 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
     ) {
         // Blocks without numbers are ignored, as they're not part of the chain.
         let block_height = block.header().as_ref().number();
+        let block_timestamp = block.header().as_ref().timestamp();
 
         // Add the block the lookbehind.
         // The value is chosen arbitrarily to not have a huge memory footprint but still
@@ -602,7 +937,7 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
         let to_check: Vec<_> = block
             .transactions()
             .hashes()
-            .filter_map(|tx_hash| self.unconfirmed.remove(&tx_hash))
+            .filter_map(|tx_hash| self.remove_unconfirmed(&tx_hash))
             .collect();
         for mut watcher in to_check {
             // If `confirmations` is not more than 1 we can notify the watcher immediately.
@@ -625,6 +960,10 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
 
         self.check_confirmations(block_height);
 
+        // Transactions which were not included by now will never be, if they expired.
+        self.latest_timestamp = Some(block_timestamp);
+        self.reap_expired(block_timestamp);
+
         // Update the latest block. We use `send_replace` here to ensure the
         // latest block is always up to date, even if no receivers exist.
         // C.f. https://docs.rs/tokio/latest/tokio/sync/watch/struct.Sender.html#method.send
@@ -697,3 +1036,140 @@ impl<N: Network, S: Stream<Item = N::BlockResponse> + Unpin + 'static> Heartbeat
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_network::Ethereum;
+    use alloy_rpc_types_eth::{Block, BlockTransactions, Header};
+
+    fn block(number: u64, timestamp: u64, txs: Vec<B256>) -> Block {
+        let header = alloy_consensus::Header { number, timestamp, ..Default::default() };
+        Block::new(Header::new(header), BlockTransactions::Hashes(txs))
+    }
+
+    async fn watch(
+        handle: &HeartbeatHandle<Ethereum>,
+        tx_hash: B256,
+        expired_time: Option<u64>,
+    ) -> PendingTransaction {
+        let config = PendingTransactionConfig::new(tx_hash).with_expired_time(expired_time);
+        handle.watch_tx(config, None).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn expired_tx_fails_early() {
+        let (blocks, rx) = mpsc::unbounded_channel();
+        let stream = futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|block| (block, rx))
+        });
+        let handle = Heartbeat::<Ethereum, _>::new(Box::pin(stream)).spawn();
+
+        // Not included before the chain passed its expiry.
+        let expiring = watch(&handle, B256::with_last_byte(1), Some(150)).await;
+        blocks.send(block(1, 140, vec![])).unwrap();
+        blocks.send(block(2, 150, vec![])).unwrap();
+        let err = expiring.await.unwrap_err();
+        assert!(matches!(
+            err,
+            PendingTransactionError::TxWatcher(WatchTxError::Expired(SponsoredTxExpired {
+                expired_time: 150,
+                timestamp: 150
+            }))
+        ));
+
+        // Included before its expiry.
+        let included = watch(&handle, B256::with_last_byte(2), Some(200)).await;
+        blocks.send(block(3, 160, vec![B256::with_last_byte(2)])).unwrap();
+        assert_eq!(included.await.unwrap(), B256::with_last_byte(2));
+
+        // Already expired when registered.
+        let expired = watch(&handle, B256::with_last_byte(3), Some(160)).await;
+        assert!(matches!(
+            expired.await,
+            Err(PendingTransactionError::TxWatcher(WatchTxError::Expired(_)))
+        ));
+
+        // Expiring at the maximum timestamp.
+        let expiring = watch(&handle, B256::with_last_byte(4), Some(u64::MAX)).await;
+        blocks.send(block(4, u64::MAX, vec![])).unwrap();
+        assert!(matches!(
+            expiring.await,
+            Err(PendingTransactionError::TxWatcher(WatchTxError::Expired(_)))
+        ));
+    }
+
+    #[test]
+    fn untracks_expiry_of_settled_txs() {
+        let mut heart = Heartbeat::<Ethereum, _>::new(futures::stream::empty::<Block>());
+        let (latest, _) = watch::channel(None);
+        let mut watch = |tx_hash: B256, expired_time: u64, timeout: Option<Duration>| {
+            let config = PendingTransactionConfig::new(tx_hash)
+                .with_expired_time(Some(expired_time))
+                .with_timeout(timeout);
+            let (tx, rx) = oneshot::channel();
+            heart.handle_watch_ix(TxWatcher { config, received_at_block: None, tx });
+            rx
+        };
+        let _included = watch(B256::with_last_byte(1), u64::MAX, None);
+        let _timed_out = watch(B256::with_last_byte(2), u64::MAX, Some(Duration::ZERO));
+        let _pending = watch(B256::with_last_byte(3), 200, None);
+        assert_eq!(heart.expire_at.values().flatten().count(), 3);
+
+        heart.handle_new_block(block(1, 100, vec![B256::with_last_byte(1)]), &latest);
+        heart.reap_timeouts();
+        assert_eq!(heart.expire_at.len(), 1);
+        assert_eq!(heart.expire_at[&200], [B256::with_last_byte(3)]);
+    }
+
+    #[tokio::test]
+    async fn resolves_with_replaced_tx() {
+        let (blocks, rx) = mpsc::unbounded_channel();
+        let stream = futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|block| (block, rx))
+        });
+        let handle = Heartbeat::<Ethereum, _>::new(Box::pin(stream)).spawn();
+
+        let replaced = vec![
+            watch(&handle, B256::with_last_byte(1), None).await,
+            watch(&handle, B256::with_last_byte(2), None).await,
+        ];
+        let pending = watch(&handle, B256::with_last_byte(3), None).await.or_replaced(replaced);
+        blocks.send(block(1, 100, vec![B256::with_last_byte(2)])).unwrap();
+        assert_eq!(pending.await.unwrap(), B256::with_last_byte(2));
+
+        // The replaced transactions failing does not fail the replacement.
+        let replaced = vec![watch(&handle, B256::with_last_byte(4), Some(150)).await];
+        let pending = watch(&handle, B256::with_last_byte(5), None).await.or_replaced(replaced);
+        blocks.send(block(2, 150, vec![])).unwrap();
+        blocks.send(block(3, 160, vec![B256::with_last_byte(5)])).unwrap();
+        assert_eq!(pending.await.unwrap(), B256::with_last_byte(5));
+    }
+
+    #[test]
+    fn bumps_replacement_fees() {
+        use alloy_rpc_types_eth::TransactionRequest;
+
+        let mut tx = TransactionRequest::default()
+            .with_max_fee_per_gas(1_000)
+            .with_max_priority_fee_per_gas(0);
+        bump_fees::<Ethereum>(&mut tx, 5);
+        assert_eq!((tx.max_fee_per_gas, tx.max_priority_fee_per_gas), (Some(1_100), Some(1)));
+        bump_fees::<Ethereum>(&mut tx, 25);
+        assert_eq!((tx.max_fee_per_gas, tx.max_priority_fee_per_gas), (Some(1_375), Some(2)));
+
+        let mut tx = TransactionRequest::default().with_gas_price(999);
+        bump_fees::<Ethereum>(&mut tx, 10);
+        assert_eq!(tx.gas_price, Some(1_099));
+
+        let mut tx = TransactionRequest::default()
+            .with_max_fee_per_gas(100)
+            .with_max_priority_fee_per_gas(10)
+            .with_max_fee_per_blob_gas(7);
+        bump_fees::<Ethereum>(&mut tx, 10);
+        assert_eq!(
+            (tx.max_fee_per_gas, tx.max_priority_fee_per_gas, tx.max_fee_per_blob_gas),
+            (Some(200), Some(20), Some(14))
+        );
+    }
+}
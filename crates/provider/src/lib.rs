@@ -46,6 +46,12 @@ pub use heart::{
 
 pub mod layers;
 
+mod logs;
+pub use logs::LogStream;
+
+mod reorg;
+pub use reorg::{CanonicalBlocks, CanonicalLogs, ChainEvent, ChainTracker, DeliveryPolicy, Reorg};
+
 mod provider;
 pub use provider::{
     builder, Caller, EthCall, EthCallParams, FilterPollerBuilder, ParamsWithBlock, Provider,
@@ -0,0 +1,374 @@
+use alloy_json_rpc::RpcError;
+use alloy_primitives::U64;
+use alloy_rpc_client::{RpcClientInner, WeakClient};
+use alloy_rpc_types_eth::{BlockNumberOrTag, Filter, Log};
+use alloy_transport::{TransportError, TransportErrorKind, TransportResult, TransportStream};
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+/// The default number of blocks queried by a single `eth_getLogs` request.
+const DEFAULT_CHUNK_SIZE: u64 = 10_000;
+
+/// The default number of `eth_getLogs` requests in flight.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Substrings of the messages of the errors returned by providers rejecting an `eth_getLogs`
+/// request because its block range is too large, or it matches too many logs.
+const RANGE_ERRORS: &[&str] = &[
+    "query returned more than",
+    "response size exceeded",
+    "too many results",
+    "too many logs",
+    "block range",
+    "range limit",
+    "range is too large",
+    "limited to a",
+];
+
+/// A stream of the logs matching a filter, queried with `eth_getLogs` over chunks of its block
+/// range, returned by [`Provider::get_logs_paginated`].
+///
+/// The block range of the filter is split into chunks of `chunk_size` blocks, which are queried
+/// with up to `concurrency` requests in flight, and the logs are yielded in order. When a
+/// provider rejects a chunk because its range is too large or it matches too many logs, the chunk
+/// is split into smaller ones, using the range suggested by the error message if any, and the
+/// smaller size is used for the following chunks.
+///
+/// Block tags of the filter are resolved when the stream is first polled. Filters by block hash,
+/// filters without a `fromBlock`, and filters of the pending block are queried with a single
+/// request.
+///
+/// The stream ends after the first error.
+///
+/// [`Provider::get_logs_paginated`]: crate::Provider::get_logs_paginated
+#[must_use = "streams do nothing unless polled"]
+pub struct LogStream {
+    client: WeakClient,
+    filter: Filter,
+    chunk_size: u64,
+    concurrency: usize,
+    inner: Option<TransportStream<'static, Log>>,
+}
+
+impl fmt::Debug for LogStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogStream")
+            .field("filter", &self.filter)
+            .field("chunk_size", &self.chunk_size)
+            .field("concurrency", &self.concurrency)
+            .field("started", &self.inner.is_some())
+            .finish()
+    }
+}
+
+impl LogStream {
+    /// Creates a new stream of the logs matching the filter.
+    pub const fn new(client: WeakClient, filter: Filter) -> Self {
+        Self {
+            client,
+            filter,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            inner: None,
+        }
+    }
+
+    /// Sets the number of blocks queried by a single request. Defaults to 10,000.
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the maximum number of requests in flight. Defaults to 4.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Returns the filter.
+    pub const fn filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    fn start(&self) -> TransportStream<'static, Log> {
+        let client = self.client.clone();
+        let filter = self.filter.clone();
+        let (chunk_size, concurrency) = (self.chunk_size, self.concurrency);
+        Box::pin(stream! {
+            let Some(client) = client.upgrade() else {
+                yield Err(TransportErrorKind::backend_gone());
+                return;
+            };
+
+            let range = match block_range(&client, &filter).await {
+                Ok(range) => range,
+                Err(err) => {
+                    yield Err(err);
+                    return;
+                }
+            };
+            let Some((from, to)) = range else {
+                match client.request::<_, Vec<Log>>("eth_getLogs", (filter,)).await {
+                    Ok(logs) => for log in logs {
+                        yield Ok(log);
+                    },
+                    Err(err) => yield Err(err),
+                }
+                return;
+            };
+
+            let pager = Pager { client, filter, chunk_size: AtomicU64::new(chunk_size) };
+            let ranges = std::iter::successors(Some(from), |start| {
+                start.checked_add(chunk_size).filter(|start| *start <= to)
+            })
+            .map(|start| start..=to.min(start.saturating_add(chunk_size - 1)));
+            let mut chunks =
+                futures::stream::iter(ranges).map(|range| pager.fetch(range)).buffered(concurrency);
+            while let Some(chunk) = chunks.next().await {
+                match chunk {
+                    Ok(logs) => for log in logs {
+                        yield Ok(log);
+                    },
+                    Err(err) => {
+                        yield Err(err);
+                        return;
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl Stream for LogStream {
+    type Item = TransportResult<Log>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.inner.is_none() {
+            this.inner = Some(this.start());
+        }
+        this.inner.as_mut().unwrap().as_mut().poll_next(cx)
+    }
+}
+
+/// Queries the chunks of a block range.
+struct Pager {
+    client: Arc<RpcClientInner>,
+    filter: Filter,
+    /// The current number of blocks queried by a single request, shared by the chunks.
+    chunk_size: AtomicU64,
+}
+
+impl Pager {
+    /// Queries the logs of the range, splitting it as required.
+    async fn fetch(&self, range: RangeInclusive<u64>) -> TransportResult<Vec<Log>> {
+        let (mut start, end) = range.into_inner();
+        let mut logs = Vec::new();
+        while start <= end {
+            let len = self.chunk_size.load(Ordering::Relaxed).min(end - start + 1);
+            let to = start + len - 1;
+            let filter = self.filter.clone().from_block(start).to_block(to);
+            match self.client.request::<_, Vec<Log>>("eth_getLogs", (filter,)).await {
+                Ok(chunk) => {
+                    logs.extend(chunk);
+                    start = to + 1;
+                }
+                Err(err) => {
+                    let Some(next) = shrunk_range(&err, len) else {
+                        return Err(err);
+                    };
+                    debug!(%err, from = start, to, next, "splitting eth_getLogs block range");
+                    self.chunk_size.fetch_min(next, Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(logs)
+    }
+}
+
+/// Returns the numeric block range of the filter, or `None` if it must be queried with a single
+/// request.
+async fn block_range(
+    client: &RpcClientInner,
+    filter: &Filter,
+) -> TransportResult<Option<(u64, u64)>> {
+    let (from, to) = filter.block_option.as_range();
+    let Some(from) = from.filter(|_| filter.is_paginatable() && !filter.is_pending_block_filter())
+    else {
+        return Ok(None);
+    };
+    let from = block_number(client, *from).await?;
+    let to = block_number(client, to.copied().unwrap_or_default()).await?;
+    Ok(Some((from, to)))
+}
+
+/// Resolves a block tag to its number.
+pub(crate) async fn block_number(
+    client: &RpcClientInner,
+    block: BlockNumberOrTag,
+) -> TransportResult<u64> {
+    #[derive(Debug, serde::Deserialize)]
+    struct Header {
+        number: U64,
+    }
+
+    match block {
+        BlockNumberOrTag::Number(number) => Ok(number),
+        BlockNumberOrTag::Earliest => Ok(0),
+        BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => {
+            client.request_noparams::<U64>("eth_blockNumber").await.map(|number| number.to())
+        }
+        tag => {
+            let header: Option<Header> =
+                client.request("eth_getBlockByNumber", (tag, false)).await?;
+            header.map(|header| header.number.to()).ok_or(RpcError::NullResp)
+        }
+    }
+}
+
+/// Returns the number of blocks to query instead of `len`, if the error is a provider rejecting
+/// the range as too large, or as matching too many logs.
+///
+/// The range suggested by the error message is used if any, and the range is halved otherwise.
+fn shrunk_range(err: &TransportError, len: u64) -> Option<u64> {
+    let message = err.as_error_resp()?.message.to_lowercase();
+    if !RANGE_ERRORS.iter().any(|pattern| message.contains(pattern)) {
+        return None;
+    }
+    let next = suggested_range(&message).filter(|next| *next < len).unwrap_or(len / 2);
+    (next > 0).then_some(next)
+}
+
+/// Parses the number of blocks suggested by an error message.
+fn suggested_range(message: &str) -> Option<u64> {
+    // A range of block numbers, e.g. `try with this block range [0x4b2940, 0x4b2c0c]`.
+    if let Some((_, range)) = message.split_once('[') {
+        let (start, end) = range.split_once(']')?.0.split_once(',')?;
+        let parse = |n: &str| u64::from_str_radix(n.trim().trim_start_matches("0x"), 16).ok();
+        let (start, end) = (parse(start)?, parse(end)?);
+        return end.checked_sub(start).map(|len| len + 1);
+    }
+
+    // A maximum number of blocks, e.g. `exceed maximum block range: 5000`. Numbers of logs or
+    // bytes are ignored.
+    if message.contains("result") || message.contains("size") {
+        return None;
+    }
+    message
+        .split(|c: char| !c.is_ascii_digit() && c != ',')
+        .filter_map(|n| n.replace(',', "").parse().ok())
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Provider, ProviderBuilder};
+    use alloy_json_rpc::SerializedRequest;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::mock::{MockResponse, MockTransport};
+    use futures::TryStreamExt;
+
+    fn error(message: &'static str) -> TransportError {
+        RpcError::ErrorResp(alloy_json_rpc::ErrorPayload {
+            code: -32005,
+            message: message.into(),
+            data: None,
+        })
+    }
+
+    #[test]
+    fn parses_range_errors() {
+        let err = error(
+            "query returned more than 10000 results. Try with this block range [0x4B2940, 0x4B2C0C].",
+        );
+        assert_eq!(shrunk_range(&err, 10_000), Some(0x4b2c0c - 0x4b2940 + 1));
+        // The suggested range is not larger than the rejected one.
+        assert_eq!(shrunk_range(&err, 100), Some(50));
+
+        let err = error("Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range and no limit on the response size, or you can request any block range with a cap of 10K logs in the response. Based on your parameters, this block range should work: [0x0, 0x1f3]");
+        assert_eq!(shrunk_range(&err, 10_000), Some(500));
+
+        assert_eq!(shrunk_range(&error("exceed maximum block range: 5000"), 10_000), Some(5000));
+        assert_eq!(
+            shrunk_range(&error("eth_getLogs is limited to a 1,000 range"), 10_000),
+            Some(1000)
+        );
+        assert_eq!(shrunk_range(&error("query timeout exceeded"), 10_000), None);
+        assert_eq!(shrunk_range(&error("Too many requests, rate limit exceeded"), 10_000), None);
+        assert_eq!(shrunk_range(&error("too many logs, narrow the query"), 10_000), Some(5000));
+        assert_eq!(shrunk_range(&error("block range is too large"), 10_000), Some(5000));
+        assert_eq!(shrunk_range(&error("block range is too large"), 1), None);
+        assert_eq!(shrunk_range(&TransportErrorKind::backend_gone(), 10_000), None);
+    }
+
+    /// A transport answering `eth_getLogs` with a log per block, rejecting ranges of more than 10
+    /// blocks.
+    fn mock_transport() -> MockTransport {
+        MockTransport::new(|req| match req.method() {
+            "eth_blockNumber" => MockResponse::success("0x63"),
+            "eth_getLogs" => {
+                let (from, to) = range(req);
+                if to - from >= 10 {
+                    MockResponse::error(
+                        -32005,
+                        format!(
+                            "query returned more than 10000 results. Try with this block range [{:#x}, {:#x}].",
+                            from,
+                            from + 6
+                        ),
+                    )
+                } else {
+                    let logs: Vec<Log> = (from..=to)
+                        .map(|number| Log { block_number: Some(number), ..Default::default() })
+                        .collect();
+                    MockResponse::success(&logs)
+                }
+            }
+            method => unreachable!("unexpected request {method}"),
+        })
+    }
+
+    /// Returns the block range of an `eth_getLogs` request.
+    fn range(req: &SerializedRequest) -> (u64, u64) {
+        let (filter,): (Filter,) = serde_json::from_str(req.params().unwrap().get()).unwrap();
+        (filter.get_from_block().unwrap(), filter.get_to_block().unwrap())
+    }
+
+    #[tokio::test]
+    async fn paginates_logs() {
+        let transport = mock_transport();
+        let provider = ProviderBuilder::new().on_client(RpcClient::new(transport.clone(), true));
+
+        let filter = Filter::new().from_block(0);
+        let logs: Vec<_> = provider
+            .get_logs_paginated(&filter)
+            .with_chunk_size(20)
+            .with_concurrency(3)
+            .try_collect()
+            .await
+            .unwrap();
+        let numbers: Vec<_> = logs.iter().map(|log| log.block_number.unwrap()).collect();
+        assert_eq!(numbers, (0..=99).collect::<Vec<_>>());
+
+        // Only the chunks in flight before the first one was split use the initial size.
+        let ranges: Vec<_> = transport
+            .requests()
+            .iter()
+            .filter(|req| req.method() == "eth_getLogs")
+            .map(range)
+            .collect();
+        assert_eq!(ranges[0], (0, 19));
+        assert!(ranges.iter().filter(|(from, to)| to - from >= 7).count() <= 3);
+    }
+}
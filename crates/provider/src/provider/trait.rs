@@ -5,8 +5,9 @@
 use crate::{
     heart::PendingTransactionError,
     utils::{self, Eip1559Estimation, EstimatorFunction},
-    EthCall, Identity, PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig,
-    ProviderBuilder, ProviderCall, RootProvider, RpcWithBlock, SendableTx,
+    CanonicalBlocks, CanonicalLogs, DeliveryPolicy, EthCall, Identity, LogStream,
+    PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig, ProviderBuilder,
+    ProviderCall, RootProvider, RpcWithBlock, SendableTx,
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::eip2718::Encodable2718;
@@ -23,7 +24,7 @@ use alloy_rpc_types_eth::{
     AccessListResult, BlockId, BlockNumberOrTag, EIP1186AccountProofResponse, FeeHistory, Filter,
     FilterChanges, Index, Log, SyncStatus,
 };
-use alloy_transport::TransportResult;
+use alloy_transport::{TransportResult, TransportStream};
 use serde_json::value::RawValue;
 use std::borrow::Cow;
 
@@ -403,6 +404,37 @@ pub trait Provider<N: Network = Ethereum>: Send + Sync {
         Ok(PollerBuilder::new(self.weak_client(), "eth_getFilterChanges", (id,)))
     }
 
+    /// Watch the blocks of the canonical chain, delivering them as required by the given
+    /// [`DeliveryPolicy`].
+    ///
+    /// Blocks are buffered until the policy releases them, and blocks dropped by a
+    /// reorganisation in the meantime are discarded. A [`ChainEvent::Reorg`] is yielded only if
+    /// delivered blocks are replaced. See [`CanonicalBlocks`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Get the blocks once they are 12 blocks deep:
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_provider::{ChainEvent, DeliveryPolicy};
+    /// use futures::StreamExt;
+    ///
+    /// let mut stream = provider.watch_blocks_with_policy(DeliveryPolicy::Depth(12));
+    /// while let Some(event) = stream.next().await {
+    ///     if let ChainEvent::Item(block) = event? {
+    ///         println!("confirmed block: {}", block.header.number);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ChainEvent::Reorg`]: crate::ChainEvent::Reorg
+    fn watch_blocks_with_policy(&self, policy: DeliveryPolicy) -> CanonicalBlocks<N> {
+        self.watch_canonical_blocks().with_policy(policy)
+    }
+
     /// Watch for new pending transaction by polling the provider with
     /// [`eth_getFilterChanges`](Self::get_filter_changes).
     ///
@@ -463,6 +495,91 @@ pub trait Provider<N: Network = Ethereum>: Send + Sync {
         Ok(PollerBuilder::new(self.weak_client(), "eth_getFilterChanges", (id,)))
     }
 
+    /// Watch the logs of the canonical chain matching the given [Filter], delivering them as
+    /// required by the given [`DeliveryPolicy`].
+    ///
+    /// Logs are buffered until the policy releases them, and logs of blocks dropped by a
+    /// reorganisation in the meantime are discarded. Delivered logs of dropped blocks are
+    /// yielded again with `removed` set. See [`CanonicalLogs`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_provider::{ChainEvent, DeliveryPolicy};
+    /// use alloy_rpc_types_eth::Filter;
+    /// use futures::StreamExt;
+    ///
+    /// let mut stream = provider.watch_logs_with_policy(&Filter::new(), DeliveryPolicy::Finalized);
+    /// while let Some(event) = stream.next().await {
+    ///     if let ChainEvent::Item(log) = event? {
+    ///         println!("finalized log: {log:?}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn watch_logs_with_policy(&self, filter: &Filter, policy: DeliveryPolicy) -> CanonicalLogs<N> {
+        self.watch_canonical_logs(filter).with_policy(policy)
+    }
+
+    /// Watch the blocks of the canonical chain, reporting reorganisations.
+    ///
+    /// Returns a [`CanonicalBlocks`] stream, which yields a [`ChainEvent::Reorg`] whenever
+    /// previously yielded blocks are replaced, before the new blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_provider::ChainEvent;
+    /// use futures::StreamExt;
+    ///
+    /// let mut stream = provider.watch_canonical_blocks().with_window(128);
+    /// while let Some(event) = stream.next().await {
+    ///     match event? {
+    ///         ChainEvent::Item(block) => println!("new block: {}", block.header.number),
+    ///         ChainEvent::Reorg(reorg) => println!("reorg of depth {}", reorg.depth),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ChainEvent::Reorg`]: crate::ChainEvent::Reorg
+    fn watch_canonical_blocks(&self) -> CanonicalBlocks<N> {
+        CanonicalBlocks::new(self.weak_client())
+    }
+
+    /// Watch the logs of the canonical chain matching the given [Filter], reporting
+    /// reorganisations.
+    ///
+    /// Returns a [`CanonicalLogs`] stream, which yields the logs of dropped blocks again, with
+    /// `removed` set, when the chain is reorganised.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::address;
+    /// use alloy_provider::ChainEvent;
+    /// use alloy_rpc_types_eth::Filter;
+    /// use futures::StreamExt;
+    ///
+    /// let address = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+    /// let mut stream = provider.watch_canonical_logs(&Filter::new().address(address));
+    /// while let Some(event) = stream.next().await {
+    ///     if let ChainEvent::Item(log) = event? {
+    ///         println!("log (removed: {}): {log:?}", log.removed);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn watch_canonical_logs(&self, filter: &Filter) -> CanonicalLogs<N> {
+        CanonicalLogs::new(self.weak_client(), filter.clone())
+    }
+
     /// Watch for new pending transaction bodies by polling the provider with
     /// [`eth_getFilterChanges`](Self::get_filter_changes).
     ///
@@ -543,6 +660,93 @@ pub trait Provider<N: Network = Ethereum>: Send + Sync {
         self.client().request("eth_getLogs", (filter,)).await
     }
 
+    /// Retrieves the logs matching the given [Filter] as a stream, deserializing each [`Log`] as
+    /// the response is received.
+    ///
+    /// When the client uses a plain `reqwest` HTTP transport, or a pubsub transport whose backend
+    /// supports [streaming requests], such as IPC, the response to `eth_getLogs` is decoded
+    /// incrementally, so that very large results need not be buffered entirely. Over IPC, the
+    /// request is sent over a dedicated connection to the socket.
+    ///
+    /// Other transports, including transports wrapped in layers, fall back to
+    /// [`Provider::get_logs`], buffering the whole response, and log a warning.
+    ///
+    /// [streaming requests]: alloy_pubsub::PubSubFrontend::request_stream
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_rpc_types_eth::Filter;
+    /// use futures::StreamExt;
+    ///
+    /// let filter = Filter::new().from_block(0).to_block(1_000_000);
+    /// let mut logs = provider.get_logs_stream(&filter).await?;
+    /// while let Some(log) = logs.next().await {
+    ///     println!("log: {:?}", log?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_logs_stream(
+        &self,
+        filter: &Filter,
+    ) -> TransportResult<TransportStream<'static, Log>> {
+        #[cfg(any(feature = "pubsub", all(feature = "reqwest", not(target_arch = "wasm32"))))]
+        let transport = self.client().transport().as_any();
+
+        #[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+        if let Some(http) = transport.downcast_ref::<alloy_transport_http::ReqwestTransport>() {
+            let req = self
+                .client()
+                .make_request("eth_getLogs", (filter,))
+                .serialize()
+                .map_err(RpcError::ser_err)?;
+            return http.request_stream(req).await;
+        }
+
+        #[cfg(feature = "pubsub")]
+        if let Some(pubsub) = transport.downcast_ref::<alloy_pubsub::PubSubFrontend>() {
+            if pubsub.supports_request_stream() {
+                let req = self
+                    .client()
+                    .make_request("eth_getLogs", (filter,))
+                    .serialize()
+                    .map_err(RpcError::ser_err)?;
+                return pubsub.request_stream(req).await;
+            }
+        }
+
+        warn!("the transport does not support streaming responses, buffering eth_getLogs");
+        let logs = self.get_logs(filter).await?;
+        Ok(Box::pin(futures::stream::iter(logs.into_iter().map(Ok))))
+    }
+
+    /// Retrieves the logs matching the given [Filter] as a [`LogStream`], which queries its block
+    /// range in chunks, for providers capping the block range or the number of results of
+    /// `eth_getLogs`.
+    ///
+    /// See [`LogStream`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_rpc_types_eth::Filter;
+    /// use futures::StreamExt;
+    ///
+    /// let filter = Filter::new().from_block(0);
+    /// let mut logs = provider.get_logs_paginated(&filter).with_chunk_size(2_000);
+    /// while let Some(log) = logs.next().await {
+    ///     println!("log: {:?}", log?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_logs_paginated(&self, filter: &Filter) -> LogStream {
+        LogStream::new(self.weak_client(), filter.clone())
+    }
+
     /// Get the account and storage values of the specified account including the merkle proofs.
     ///
     /// This call can be used to verify that the data has not been tampered with.
@@ -786,15 +990,21 @@ pub trait Provider<N: Network = Ethereum>: Send + Sync {
         // we don't miss it if user will subscriber to it immediately after sending.
         let _handle = self.root().get_heart();
 
+        // Sponsored transactions are failed early once the chain passed their expiry.
         match tx {
             SendableTx::Builder(mut tx) => {
                 alloy_network::TransactionBuilder::prep_for_submission(&mut tx);
+                let expired_time = alloy_network::TransactionBuilder::expiry(&tx);
                 let tx_hash = self.client().request("eth_sendTransaction", (tx,)).await?;
-                Ok(PendingTransactionBuilder::new(self.root().clone(), tx_hash))
+                Ok(PendingTransactionBuilder::new(self.root().clone(), tx_hash)
+                    .with_expired_time(expired_time))
             }
             SendableTx::Envelope(tx) => {
                 let encoded_tx = tx.encoded_2718();
-                self.send_raw_transaction(&encoded_tx).await
+                // Built from the unsigned transaction, which skips recovering the sender.
+                let tx = N::TransactionRequest::from(N::UnsignedTx::from(tx));
+                let expired_time = alloy_network::TransactionBuilder::expiry(&tx);
+                Ok(self.send_raw_transaction(&encoded_tx).await?.with_expired_time(expired_time))
             }
         }
     }
@@ -1082,8 +1292,15 @@ impl<N: Network> Provider<N> for RootProvider<N> {
     #[inline]
     async fn watch_pending_transaction(
         &self,
-        config: PendingTransactionConfig,
+        mut config: PendingTransactionConfig,
     ) -> Result<PendingTransaction, PendingTransactionError> {
+        // Watch the transactions replaced by this one as well, as any of them may be confirmed.
+        let mut replaced = Vec::with_capacity(config.replaced.len());
+        for tx_hash in std::mem::take(&mut config.replaced) {
+            let config = config.clone().with_tx_hash(tx_hash);
+            replaced.push(self.watch_pending_transaction(config).await?);
+        }
+
         let block_number =
             if let Some(receipt) = self.get_transaction_receipt(*config.tx_hash()).await? {
                 // The transaction is already confirmed.
@@ -1097,10 +1314,12 @@ impl<N: Network> Provider<N> for RootProvider<N> {
                 None
             };
 
-        self.get_heart()
+        let pending = self
+            .get_heart()
             .watch_tx(config, block_number)
             .await
-            .map_err(|_| PendingTransactionError::FailedToRegister)
+            .map_err(|_| PendingTransactionError::FailedToRegister)?;
+        Ok(pending.or_replaced(replaced))
     }
 }
 
@@ -1134,6 +1353,63 @@ mod tests {
     #[cfg(feature = "hyper")]
     use tower::{Layer, Service};
 
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn test_get_logs_stream() {
+        use futures::TryStreamExt;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let logs: Vec<Log> =
+            (0..3).map(|index| Log { log_index: Some(index), ..Default::default() }).collect();
+        let first = serde_json::to_string(&logs[0]).unwrap();
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":0,"result":{}}}"#,
+            serde_json::to_string(&logs).unwrap()
+        );
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = conn.read(&mut buf).await.unwrap();
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                body.len()
+            );
+            conn.write_all(head.as_bytes()).await.unwrap();
+            // Hold the rest of the body back until the first log was streamed.
+            let (start, rest) = body.split_at(body.find(&first).unwrap() + first.len() + 1);
+            conn.write_all(start.as_bytes()).await.unwrap();
+            rx.await.unwrap();
+            conn.write_all(rest.as_bytes()).await.unwrap();
+        });
+
+        let provider = ProviderBuilder::new().on_http(url.parse().unwrap());
+        let mut stream = provider.get_logs_stream(&Filter::new()).await.unwrap();
+        let log = tokio::time::timeout(Duration::from_secs(5), stream.try_next()).await;
+        assert_eq!(log.unwrap().unwrap().as_ref(), Some(&logs[0]));
+        tx.send(()).unwrap();
+        let rest: Vec<Log> = stream.try_collect().await.unwrap();
+        assert_eq!(rest, logs[1..]);
+    }
+
+    #[tokio::test]
+    async fn test_send_sponsored_tx_expiry() {
+        use alloy_network::TransactionBuilderSponsored;
+        use alloy_transport::mock::MockTransport;
+
+        let transport = MockTransport::with_result(&B256::with_last_byte(1));
+        let provider = RootProvider::<Ethereum>::new(RpcClient::new(transport, true));
+
+        let tx = TransactionRequest::default().with_expired_time(1_700_000_000);
+        let pending = provider.send_transaction(tx).await.unwrap();
+        assert_eq!(pending.expired_time(), Some(1_700_000_000));
+
+        let pending = provider.send_transaction(TransactionRequest::default()).await.unwrap();
+        assert_eq!(pending.expired_time(), None);
+    }
+
     #[tokio::test]
     async fn test_provider_builder() {
         let provider = RootProvider::builder().with_recommended_fillers().on_anvil();
@@ -0,0 +1,827 @@
+use crate::{blocks::NewBlocks, logs::block_number};
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Ethereum, Network};
+use alloy_network_primitives::{BlockResponse, HeaderResponse};
+use alloy_primitives::B256;
+use alloy_rpc_client::{RpcClientInner, WeakClient};
+use alloy_rpc_types_eth::{BlockNumberOrTag, Filter, Log};
+use alloy_transport::{TransportErrorKind, TransportResult, TransportStream};
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The default number of blocks tracked by a [`ChainTracker`].
+const DEFAULT_WINDOW: usize = 64;
+
+/// The maximum number of blocks tracked by a [`ChainTracker`].
+const MAX_WINDOW: usize = 1 << 16;
+
+/// A reorganisation of the chain, reported by a [`ChainTracker`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reorg {
+    /// The number of blocks removed from the canonical chain.
+    pub depth: u64,
+    /// The blocks removed from the canonical chain, in ascending order.
+    pub dropped: Vec<BlockNumHash>,
+    /// The blocks which replaced them, in ascending order. There may be more of them than
+    /// dropped blocks.
+    pub added: Vec<BlockNumHash>,
+}
+
+impl Reorg {
+    /// Returns the number of the first block which was replaced.
+    pub fn fork_block(&self) -> Option<u64> {
+        self.dropped.first().map(|block| block.number)
+    }
+}
+
+/// An item of a canonical chain stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainEvent<T> {
+    /// A new item of the canonical chain.
+    Item(T),
+    /// A reorganisation of the chain, yielded before the items of the blocks which were added.
+    Reorg(Reorg),
+}
+
+impl<T> ChainEvent<T> {
+    /// Returns the item, if any.
+    pub fn into_item(self) -> Option<T> {
+        match self {
+            Self::Item(item) => Some(item),
+            Self::Reorg(_) => None,
+        }
+    }
+
+    /// Returns the reorganisation, if any.
+    pub const fn as_reorg(&self) -> Option<&Reorg> {
+        match self {
+            Self::Item(_) => None,
+            Self::Reorg(reorg) => Some(reorg),
+        }
+    }
+}
+
+/// Tracks the latest blocks of the canonical chain, in order to detect reorganisations.
+///
+/// The tracker keeps the number and hash of up to `window` consecutive blocks. A new block is
+/// linked to them if its parent hash is the hash of the tracked block before it. Blocks which are
+/// not linked must be preceded by their ancestors, until one of them is, and inserting them
+/// replaces the tracked blocks they conflict with, which is reported as a [`Reorg`].
+///
+/// Reorganisations deeper than the window cannot be detected exactly: the blocks at or below the
+/// oldest tracked block are considered linked.
+#[derive(Clone, Debug)]
+pub struct ChainTracker {
+    blocks: VecDeque<BlockNumHash>,
+    window: usize,
+}
+
+impl Default for ChainTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl ChainTracker {
+    /// Creates a new tracker keeping up to `window` blocks, at most 65,536.
+    pub fn new(window: usize) -> Self {
+        Self { blocks: VecDeque::new(), window: window.clamp(1, MAX_WINDOW) }
+    }
+
+    /// Returns the maximum number of tracked blocks.
+    pub const fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the latest tracked block.
+    pub fn tip(&self) -> Option<BlockNumHash> {
+        self.blocks.back().copied()
+    }
+
+    /// Returns the tracked block with the given number.
+    pub fn get(&self, number: u64) -> Option<BlockNumHash> {
+        let oldest = self.blocks.front()?.number;
+        let index = usize::try_from(number.checked_sub(oldest)?).ok()?;
+        self.blocks.get(index).copied()
+    }
+
+    /// Returns true if the block is tracked.
+    pub fn contains(&self, block: &BlockNumHash) -> bool {
+        self.get(block.number).is_some_and(|tracked| tracked.hash == block.hash)
+    }
+
+    /// Returns true if the block with the given number and parent hash is linked to the tracked
+    /// blocks.
+    pub fn is_linked(&self, number: u64, parent_hash: B256) -> bool {
+        let Some(oldest) = self.blocks.front() else {
+            return true;
+        };
+        if number <= oldest.number {
+            return true;
+        }
+        self.get(number - 1).is_some_and(|parent| parent.hash == parent_hash)
+    }
+
+    /// Inserts consecutive blocks, in ascending order, the first of which is linked.
+    ///
+    /// Returns the reorganisation if tracked blocks were replaced. Blocks which are already
+    /// tracked are skipped.
+    pub fn insert(&mut self, blocks: &[BlockNumHash]) -> Option<Reorg> {
+        let skip = blocks.iter().take_while(|block| self.contains(block)).count();
+        let blocks = &blocks[skip..];
+        let first = blocks.first()?;
+
+        let keep = self.blocks.iter().take_while(|block| block.number < first.number).count();
+        let dropped: Vec<_> = self.blocks.drain(keep..).collect();
+        if self.blocks.back().is_some_and(|tip| tip.number + 1 != first.number) {
+            // Not consecutive to the tracked blocks, start over.
+            self.blocks.clear();
+        }
+        self.blocks.extend(blocks);
+        let excess = self.blocks.len().saturating_sub(self.window);
+        self.blocks.drain(..excess);
+
+        (!dropped.is_empty()).then(|| Reorg {
+            depth: dropped.len() as u64,
+            dropped,
+            added: blocks.to_vec(),
+        })
+    }
+
+    /// Removes all the tracked blocks.
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+    }
+}
+
+/// Returns the number and hash of a block.
+fn num_hash<N: Network>(block: &N::BlockResponse) -> BlockNumHash {
+    BlockNumHash::new(block.header().number(), block.header().hash())
+}
+
+/// Returns the new blocks of the canonical chain ending with `block`, fetching its ancestors
+/// until one is linked to the tracked blocks.
+///
+/// If no ancestor is linked within the window, e.g. after a long disconnection, the tracker is
+/// cleared.
+async fn canonical_segment<N: Network>(
+    client: &RpcClientInner,
+    tracker: &mut ChainTracker,
+    block: N::BlockResponse,
+) -> TransportResult<Vec<N::BlockResponse>> {
+    if tracker.contains(&num_hash::<N>(&block)) {
+        return Ok(Vec::new());
+    }
+
+    let mut segment = vec![block];
+    loop {
+        let header = segment.last().unwrap().header();
+        let (number, parent_hash) = (header.number(), header.parent_hash());
+        if tracker.is_linked(number, parent_hash) {
+            break;
+        }
+        if segment.len() >= tracker.window() {
+            debug!(number, "no linked ancestor within the window, clearing tracked blocks");
+            tracker.clear();
+            break;
+        }
+        trace!(number = number - 1, %parent_hash, "fetching ancestor");
+        let parent: Option<N::BlockResponse> =
+            client.request("eth_getBlockByHash", (parent_hash, false)).await?;
+        segment.push(parent.ok_or(RpcError::NullResp)?);
+    }
+    segment.reverse();
+    Ok(segment)
+}
+
+/// When the items of a canonical chain stream are delivered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeliveryPolicy {
+    /// Items are delivered as soon as their block is received.
+    #[default]
+    Latest,
+    /// Items are delivered once the given number of blocks were built on top of their block.
+    Depth(u64),
+    /// Items are delivered once their block is at or below the [safe] block.
+    ///
+    /// [safe]: BlockNumberOrTag::Safe
+    Safe,
+    /// Items are delivered once their block is at or below the [finalized] block.
+    ///
+    /// [finalized]: BlockNumberOrTag::Finalized
+    Finalized,
+}
+
+impl DeliveryPolicy {
+    /// Returns the number of the latest block whose items can be delivered, given the number of
+    /// the tip of the chain.
+    async fn released_block(
+        self,
+        client: &RpcClientInner,
+        tip: u64,
+    ) -> TransportResult<Option<u64>> {
+        let tag = match self {
+            Self::Latest => return Ok(Some(tip)),
+            Self::Depth(depth) => return Ok(tip.checked_sub(depth)),
+            Self::Safe => BlockNumberOrTag::Safe,
+            Self::Finalized => BlockNumberOrTag::Finalized,
+        };
+        block_number(client, tag).await.map(|number| Some(number.min(tip)))
+    }
+
+    /// Returns the minimum number of blocks to track, so that buffered items are re-validated.
+    fn min_window(self) -> usize {
+        match self {
+            Self::Depth(depth) => {
+                usize::try_from(depth).map_or(MAX_WINDOW, |depth| depth.saturating_add(1))
+            }
+            _ => 1,
+        }
+    }
+}
+
+/// Returns the hash of the canonical block with the given number.
+async fn canonical_hash(client: &RpcClientInner, number: u64) -> TransportResult<B256> {
+    #[derive(Debug, serde::Deserialize)]
+    struct Header {
+        hash: B256,
+    }
+
+    let header: Option<Header> =
+        client.request("eth_getBlockByNumber", (BlockNumberOrTag::Number(number), false)).await?;
+    header.map(|header| header.hash).ok_or(RpcError::NullResp)
+}
+
+/// Buffers the items of a canonical chain stream until the [`DeliveryPolicy`] releases them.
+struct Delivery<T> {
+    policy: DeliveryPolicy,
+    /// The items waiting to be released, in ascending block order.
+    pending: VecDeque<(BlockNumHash, T)>,
+    /// The number of the latest released block.
+    released: Option<u64>,
+}
+
+impl<T> Delivery<T> {
+    const fn new(policy: DeliveryPolicy) -> Self {
+        Self { policy, pending: VecDeque::new(), released: None }
+    }
+
+    /// Returns true if the block was released.
+    fn is_released(&self, block: &BlockNumHash) -> bool {
+        self.released.is_some_and(|released| block.number <= released)
+    }
+
+    /// Discards the pending items of the dropped blocks, returning true if released blocks were
+    /// dropped.
+    fn reorg(&mut self, reorg: &Reorg) -> bool {
+        self.pending.retain(|(block, _)| !reorg.dropped.contains(block));
+        let Some(fork_block) = reorg.fork_block() else {
+            return false;
+        };
+        if !self.released.is_some_and(|released| fork_block <= released) {
+            return false;
+        }
+        self.released = fork_block.checked_sub(1);
+        true
+    }
+
+    /// Releases the pending items as required by the policy, discarding those of the blocks
+    /// which are no longer canonical.
+    ///
+    /// Blocks which left the window of the tracker are checked against the canonical block of the
+    /// same number.
+    async fn release(
+        &mut self,
+        client: &RpcClientInner,
+        tracker: &ChainTracker,
+    ) -> TransportResult<Vec<T>> {
+        let Some(tip) = tracker.tip() else {
+            return Ok(Vec::new());
+        };
+        if self.pending.is_empty()
+            && matches!(self.policy, DeliveryPolicy::Safe | DeliveryPolicy::Finalized)
+        {
+            return Ok(Vec::new());
+        }
+        let Some(released_block) = self.policy.released_block(client, tip.number).await? else {
+            return Ok(Vec::new());
+        };
+        self.released = self.released.max(Some(released_block));
+
+        let count =
+            self.pending.iter().take_while(|(block, _)| block.number <= released_block).count();
+        let mut untracked = HashMap::new();
+        for (block, _) in self.pending.iter().take(count) {
+            if tracker.get(block.number).is_none() && !untracked.contains_key(&block.number) {
+                trace!(number = block.number, "fetching canonical hash of untracked block");
+                untracked.insert(block.number, canonical_hash(client, block.number).await?);
+            }
+        }
+
+        let mut items = Vec::with_capacity(count);
+        for (block, item) in self.pending.drain(..count) {
+            let hash = tracker.get(block.number).map(|tracked| tracked.hash);
+            if hash.or_else(|| untracked.get(&block.number).copied()) != Some(block.hash) {
+                trace!(number = block.number, hash = %block.hash, "discarding item of dropped block");
+                continue;
+            }
+            items.push(item);
+        }
+        Ok(items)
+    }
+}
+
+/// A stream of the blocks of the canonical chain, reporting reorganisations, returned by
+/// [`Provider::watch_canonical_blocks`].
+///
+/// New blocks are received with a `newHeads` subscription if the transport supports it, or by
+/// polling the block number otherwise, and tracked by a [`ChainTracker`]. When a block is not
+/// linked to the tracked ones, its ancestors are fetched by hash until one is, and if tracked
+/// blocks were replaced, a [`ChainEvent::Reorg`] is yielded before the new blocks. Blocks which
+/// were already yielded are skipped.
+///
+/// With a [`DeliveryPolicy`] other than [`Latest`](DeliveryPolicy::Latest), blocks are buffered
+/// until the policy releases them, and are checked to still be canonical before. Blocks dropped
+/// while buffered are discarded, and a reorganisation is only yielded if released blocks were
+/// replaced.
+///
+/// Blocks are fetched without their transactions bodies. The stream yields the error of a failed
+/// request, and resumes with the next block.
+///
+/// [`Provider::watch_canonical_blocks`]: crate::Provider::watch_canonical_blocks
+#[must_use = "streams do nothing unless polled"]
+pub struct CanonicalBlocks<N: Network = Ethereum> {
+    client: WeakClient,
+    window: usize,
+    policy: DeliveryPolicy,
+    inner: Option<TransportStream<'static, ChainEvent<N::BlockResponse>>>,
+}
+
+impl<N: Network> fmt::Debug for CanonicalBlocks<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalBlocks")
+            .field("window", &self.window)
+            .field("policy", &self.policy)
+            .field("started", &self.inner.is_some())
+            .finish()
+    }
+}
+
+impl<N: Network> CanonicalBlocks<N> {
+    /// Creates a new stream of the blocks of the canonical chain.
+    pub const fn new(client: WeakClient) -> Self {
+        Self { client, window: DEFAULT_WINDOW, policy: DeliveryPolicy::Latest, inner: None }
+    }
+
+    /// Sets the number of blocks tracked to detect reorganisations. Defaults to 64.
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Sets when blocks are delivered. Defaults to [`DeliveryPolicy::Latest`].
+    ///
+    /// The window is extended to cover the depth of [`DeliveryPolicy::Depth`], up to the maximum
+    /// window of the [`ChainTracker`]. With [`DeliveryPolicy::Safe`] and
+    /// [`DeliveryPolicy::Finalized`], buffered blocks which left the window are checked against
+    /// the canonical block of the same number before release, which costs a request per block.
+    pub const fn with_policy(mut self, policy: DeliveryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn start(&self) -> TransportStream<'static, ChainEvent<N::BlockResponse>> {
+        let client = self.client.clone();
+        let mut tracker = ChainTracker::new(self.window.max(self.policy.min_window()));
+        let mut delivery = Delivery::new(self.policy);
+        Box::pin(stream! {
+            let mut blocks = Box::pin(NewBlocks::<N>::new(client.clone()).into_stream());
+            while let Some(block) = blocks.next().await {
+                let Some(client) = client.upgrade() else {
+                    yield Err(TransportErrorKind::backend_gone());
+                    return;
+                };
+                let segment = match canonical_segment::<N>(&client, &mut tracker, block).await {
+                    Ok(segment) => segment,
+                    Err(err) => {
+                        yield Err(err);
+                        continue;
+                    }
+                };
+
+                let added: Vec<_> = segment.iter().map(num_hash::<N>).collect();
+                if let Some(reorg) = tracker.insert(&added) {
+                    debug!(depth = reorg.depth, fork_block = ?reorg.fork_block(), "chain reorganised");
+                    if delivery.reorg(&reorg) {
+                        yield Ok(ChainEvent::Reorg(reorg));
+                    }
+                }
+                delivery.pending.extend(added.into_iter().zip(segment));
+                match delivery.release(&client, &tracker).await {
+                    Ok(blocks) => for block in blocks {
+                        yield Ok(ChainEvent::Item(block));
+                    },
+                    Err(err) => yield Err(err),
+                }
+            }
+        })
+    }
+}
+
+impl<N: Network> Stream for CanonicalBlocks<N> {
+    type Item = TransportResult<ChainEvent<N::BlockResponse>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.inner.is_none() {
+            this.inner = Some(this.start());
+        }
+        this.inner.as_mut().unwrap().as_mut().poll_next(cx)
+    }
+}
+
+/// A stream of the logs of the canonical chain matching a filter, reporting reorganisations,
+/// returned by [`Provider::watch_canonical_logs`].
+///
+/// The blocks of the canonical chain are followed like [`CanonicalBlocks`] does, and the logs of
+/// each new block are queried by block hash, so the block range of the filter is ignored. When
+/// the chain is reorganised, a [`ChainEvent::Reorg`] is yielded, followed by the logs of the
+/// dropped blocks with `removed` set, from the newest to the oldest, as `eth_subscribe` does, and
+/// by the logs of the new blocks.
+///
+/// With a [`DeliveryPolicy`] other than [`Latest`](DeliveryPolicy::Latest), logs are buffered
+/// like blocks are by [`CanonicalBlocks`], and only the logs which were released are yielded
+/// again when their block is dropped.
+///
+/// The stream yields the error of a failed request, and resumes with the next block, whose
+/// missing ancestors are queried again.
+///
+/// [`Provider::watch_canonical_logs`]: crate::Provider::watch_canonical_logs
+#[must_use = "streams do nothing unless polled"]
+pub struct CanonicalLogs<N: Network = Ethereum> {
+    client: WeakClient,
+    filter: Filter,
+    window: usize,
+    policy: DeliveryPolicy,
+    inner: Option<TransportStream<'static, ChainEvent<Log>>>,
+    _phantom: PhantomData<fn() -> N>,
+}
+
+impl<N: Network> fmt::Debug for CanonicalLogs<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalLogs")
+            .field("filter", &self.filter)
+            .field("window", &self.window)
+            .field("policy", &self.policy)
+            .field("started", &self.inner.is_some())
+            .finish()
+    }
+}
+
+impl<N: Network> CanonicalLogs<N> {
+    /// Creates a new stream of the logs of the canonical chain matching the filter.
+    pub const fn new(client: WeakClient, filter: Filter) -> Self {
+        Self {
+            client,
+            filter,
+            window: DEFAULT_WINDOW,
+            policy: DeliveryPolicy::Latest,
+            inner: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the number of blocks tracked to detect reorganisations. Defaults to 64.
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Sets when logs are delivered. Defaults to [`DeliveryPolicy::Latest`].
+    ///
+    /// The window is extended to cover the depth of [`DeliveryPolicy::Depth`], up to the maximum
+    /// window of the [`ChainTracker`]. With [`DeliveryPolicy::Safe`] and
+    /// [`DeliveryPolicy::Finalized`], buffered blocks which left the window are checked against
+    /// the canonical block of the same number before release, which costs a request per block.
+    pub const fn with_policy(mut self, policy: DeliveryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the filter.
+    pub const fn filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    fn start(&self) -> TransportStream<'static, ChainEvent<Log>> {
+        let client = self.client.clone();
+        let filter = self.filter.clone();
+        let mut tracker = ChainTracker::new(self.window.max(self.policy.min_window()));
+        let mut delivery = Delivery::new(self.policy);
+        // The logs of the tracked blocks, to be removed if they are dropped once released.
+        let mut tracked_logs = HashMap::<BlockNumHash, Vec<Log>>::new();
+        Box::pin(stream! {
+            let mut blocks = Box::pin(NewBlocks::<N>::new(client.clone()).into_stream());
+            'blocks: while let Some(block) = blocks.next().await {
+                let Some(client) = client.upgrade() else {
+                    yield Err(TransportErrorKind::backend_gone());
+                    return;
+                };
+                let segment = match canonical_segment::<N>(&client, &mut tracker, block).await {
+                    Ok(segment) => segment,
+                    Err(err) => {
+                        yield Err(err);
+                        continue;
+                    }
+                };
+
+                // Query all the logs before inserting the blocks, so that failed blocks are
+                // queried again with the next one.
+                let added: Vec<_> = segment.iter().map(num_hash::<N>).collect();
+                let mut logs = Vec::with_capacity(added.len());
+                for block in &added {
+                    let filter = filter.clone().at_block_hash(block.hash);
+                    match client.request::<_, Vec<Log>>("eth_getLogs", (filter,)).await {
+                        Ok(block_logs) => logs.push(block_logs),
+                        Err(err) => {
+                            yield Err(err);
+                            continue 'blocks;
+                        }
+                    }
+                }
+
+                if let Some(reorg) = tracker.insert(&added) {
+                    debug!(depth = reorg.depth, fork_block = ?reorg.fork_block(), "chain reorganised");
+                    let removed: Vec<_> = reorg
+                        .dropped
+                        .iter()
+                        .rev()
+                        .filter(|block| delivery.is_released(block))
+                        .flat_map(|block| tracked_logs.remove(block).unwrap_or_default().into_iter().rev())
+                        .collect();
+                    if delivery.reorg(&reorg) {
+                        yield Ok(ChainEvent::Reorg(reorg));
+                        for mut log in removed {
+                            log.removed = true;
+                            yield Ok(ChainEvent::Item(log));
+                        }
+                    }
+                }
+                for (block, block_logs) in added.into_iter().zip(logs) {
+                    if !block_logs.is_empty() {
+                        tracked_logs.insert(block, block_logs.clone());
+                    }
+                    delivery.pending.extend(block_logs.into_iter().map(|log| (block, log)));
+                }
+                tracked_logs.retain(|block, _| tracker.contains(block));
+                match delivery.release(&client, &tracker).await {
+                    Ok(logs) => for log in logs {
+                        yield Ok(ChainEvent::Item(log));
+                    },
+                    Err(err) => yield Err(err),
+                }
+            }
+        })
+    }
+}
+
+impl<N: Network> Stream for CanonicalLogs<N> {
+    type Item = TransportResult<ChainEvent<Log>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.inner.is_none() {
+            this.inner = Some(this.start());
+        }
+        this.inner.as_mut().unwrap().as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Provider, ProviderBuilder};
+    use alloy_primitives::U64;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::{Block, Header, Transaction};
+    use alloy_transport::mock::{MockResponse, MockTransport};
+    use std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    const fn block(number: u64, fork: u8) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(fork + number as u8))
+    }
+
+    #[test]
+    fn caps_window_of_huge_depths() {
+        for depth in [1 << 40, u64::MAX] {
+            let window = DeliveryPolicy::Depth(depth).min_window();
+            let mut tracker = ChainTracker::new(DEFAULT_WINDOW.max(window));
+            assert_eq!(tracker.window(), MAX_WINDOW);
+            assert_eq!(tracker.insert(&[block(0, 0x10)]), None);
+        }
+        assert_eq!(ChainTracker::new(0).window(), 1);
+    }
+
+    #[test]
+    fn tracks_reorgs() {
+        let mut tracker = ChainTracker::new(4);
+        let a: Vec<_> = (0..6).map(|number| block(number, 0x10)).collect();
+        assert_eq!(tracker.insert(&a[..3]), None);
+        assert_eq!(tracker.insert(&a[2..6]), None);
+        assert_eq!(tracker.tip(), Some(a[5]));
+        assert_eq!(tracker.get(1), None);
+        assert!(tracker.contains(&a[2]));
+
+        assert!(tracker.is_linked(6, a[5].hash));
+        assert!(!tracker.is_linked(6, a[4].hash));
+        assert!(!tracker.is_linked(7, a[5].hash));
+        // Blocks at or below the oldest tracked block cannot be checked.
+        assert!(tracker.is_linked(2, B256::ZERO));
+
+        let b: Vec<_> = (4..7).map(|number| block(number, 0x20)).collect();
+        assert!(tracker.is_linked(4, a[3].hash));
+        let reorg = tracker.insert(&b).unwrap();
+        assert_eq!(reorg, Reorg { depth: 2, dropped: a[4..6].to_vec(), added: b.clone() });
+        assert_eq!(reorg.fork_block(), Some(4));
+        assert_eq!(tracker.tip(), Some(b[2]));
+        assert!(!tracker.contains(&a[4]));
+
+        // A shorter chain replaces the tracked blocks above it.
+        let c = block(5, 0x30);
+        let reorg = tracker.insert(&[c]).unwrap();
+        assert_eq!(reorg.dropped, b[1..].to_vec());
+        assert_eq!(tracker.tip(), Some(c));
+    }
+
+    /// A chain which can be replaced, and its finalized block, served with a log per block by
+    /// [`MockChain::transport`].
+    #[derive(Clone, Default)]
+    struct MockChain(Arc<Mutex<Vec<BlockNumHash>>>, Arc<AtomicU64>);
+
+    impl MockChain {
+        fn set(&self, chain: Vec<BlockNumHash>) {
+            *self.0.lock().unwrap() = chain;
+        }
+
+        fn finalize(&self, number: u64) {
+            self.1.store(number, Ordering::Relaxed);
+        }
+
+        fn transport(&self) -> MockTransport {
+            let (chain, finalized) = (self.0.clone(), self.1.clone());
+            MockTransport::new(move |req| {
+                let params = req.params().map(|params| params.get()).unwrap_or_default();
+                let chain = chain.lock().unwrap();
+                match req.method() {
+                    "eth_blockNumber" => MockResponse::success(&U64::from(chain.len() - 1)),
+                    "eth_getBlockByNumber" => {
+                        let (number, _): (BlockNumberOrTag, bool) =
+                            serde_json::from_str(params).unwrap();
+                        let number = match number {
+                            BlockNumberOrTag::Finalized => finalized.load(Ordering::Relaxed),
+                            number => number.as_number().unwrap(),
+                        };
+                        MockResponse::success(&mock_block(chain[number as usize]))
+                    }
+                    "eth_getBlockByHash" => {
+                        let (hash, _): (B256, bool) = serde_json::from_str(params).unwrap();
+                        let number = (hash[31] & 0xf) as u64;
+                        MockResponse::success(&mock_block(BlockNumHash::new(number, hash)))
+                    }
+                    "eth_getLogs" => {
+                        let (filter,): (Filter,) = serde_json::from_str(params).unwrap();
+                        let hash = *filter.block_option.as_block_hash().unwrap();
+                        let log: Log = Log { block_hash: Some(hash), ..Default::default() };
+                        MockResponse::success(&[log])
+                    }
+                    method => unreachable!("unexpected request {method}"),
+                }
+            })
+        }
+    }
+
+    fn mock_block(block: BlockNumHash) -> Block<Transaction> {
+        let parent_hash = if block.number == 0 {
+            B256::ZERO
+        } else {
+            // Forks branch off the first chain after block 1.
+            let fork = block.hash[31] - block.number as u8;
+            let parent = block.number - 1;
+            if parent < 2 { self::block(parent, 0x10) } else { self::block(parent, fork) }.hash
+        };
+        let header = Header {
+            hash: block.hash,
+            inner: alloy_consensus::Header {
+                number: block.number,
+                parent_hash,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        Block::empty(header)
+    }
+
+    async fn next(stream: &mut CanonicalLogs) -> ChainEvent<(B256, bool)> {
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next()).await;
+        match event.unwrap().unwrap().unwrap() {
+            ChainEvent::Item(log) => ChainEvent::Item((log.block_hash.unwrap(), log.removed)),
+            ChainEvent::Reorg(reorg) => ChainEvent::Reorg(reorg),
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_reorged_logs() {
+        let a: Vec<_> = (0..4).map(|number| block(number, 0x10)).collect();
+        let b: Vec<_> = (2..5).map(|number| block(number, 0x20)).collect();
+
+        let chain = MockChain::default();
+        chain.set(a[..2].to_vec());
+        let client =
+            RpcClient::new(chain.transport(), true).with_poll_interval(Duration::from_millis(10));
+        let provider = ProviderBuilder::new().on_client(client);
+        let mut stream = provider.watch_canonical_logs(&Filter::new());
+
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[1].hash, false)));
+
+        chain.set(a.clone());
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[2].hash, false)));
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[3].hash, false)));
+
+        chain.set([&a[..2], &b].concat());
+        assert_eq!(
+            next(&mut stream).await,
+            ChainEvent::Reorg(Reorg { depth: 2, dropped: a[2..].to_vec(), added: b.clone() })
+        );
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[3].hash, true)));
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[2].hash, true)));
+        for block in &b {
+            assert_eq!(next(&mut stream).await, ChainEvent::Item((block.hash, false)));
+        }
+    }
+
+    #[tokio::test]
+    async fn delays_logs_by_depth() {
+        let a: Vec<_> = (0..4).map(|number| block(number, 0x10)).collect();
+        let b: Vec<_> = (2..5).map(|number| block(number, 0x20)).collect();
+
+        let chain = MockChain::default();
+        chain.set(a[..2].to_vec());
+        let client =
+            RpcClient::new(chain.transport(), true).with_poll_interval(Duration::from_millis(10));
+        let provider = ProviderBuilder::new().on_client(client);
+        let mut stream = provider.watch_logs_with_policy(&Filter::new(), DeliveryPolicy::Depth(1));
+
+        // Block 1 is released once block 2 is received.
+        assert!(tokio::time::timeout(Duration::from_millis(50), stream.next()).await.is_err());
+        chain.set(a.clone());
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[1].hash, false)));
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[2].hash, false)));
+
+        // The log of block 3 was not released, and is discarded.
+        chain.set([&a[..2], &b].concat());
+        assert_eq!(
+            next(&mut stream).await,
+            ChainEvent::Reorg(Reorg { depth: 2, dropped: a[2..].to_vec(), added: b.clone() })
+        );
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((a[2].hash, true)));
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((b[0].hash, false)));
+        assert_eq!(next(&mut stream).await, ChainEvent::Item((b[1].hash, false)));
+    }
+
+    #[tokio::test]
+    async fn checks_blocks_below_window() {
+        let a: Vec<_> = (0..6).map(|number| block(number, 0x10)).collect();
+        let b: Vec<_> = (2..6).map(|number| block(number, 0x20)).collect();
+
+        let chain = MockChain::default();
+        chain.set([&a[..2], &b].concat());
+        chain.finalize(5);
+        let client = RpcClient::new(chain.transport(), true);
+
+        // The finalized block lags behind the tip by more than the window, which only tracks
+        // the new blocks 4 and 5.
+        let mut tracker = ChainTracker::new(2);
+        tracker.insert(&b[2..]);
+        let mut delivery = Delivery::new(DeliveryPolicy::Finalized);
+        delivery.pending.extend(a[1..].iter().chain(&b[2..]).map(|block| (*block, *block)));
+
+        let released = delivery.release(&client, &tracker).await.unwrap();
+        assert_eq!(released, [a[1], b[2], b[3]]);
+        assert!(delivery.pending.is_empty());
+    }
+}
@@ -1,5 +1,48 @@
-use crate::{handle::ConnectionHandle, service::PubSubService, PubSubFrontend};
-use alloy_transport::{impl_future, TransportResult};
+use crate::{handle::ConnectionHandle, service::PubSubService, PubSubFrontend, ReconnectPolicy};
+use alloy_json_rpc::SerializedRequest;
+use alloy_transport::{impl_future, TransportFut, TransportResult, TransportStream};
+use serde_json::value::RawValue;
+use std::{fmt, sync::Arc};
+
+type StreamFn = dyn Fn(SerializedRequest) -> TransportFut<'static, TransportStream<'static, Box<RawValue>>>
+    + Send
+    + Sync;
+
+/// Sends requests whose result is an array, and streams the elements of the
+/// result as they are read, instead of buffering the whole response.
+///
+/// Returned by [`PubSubConnect::request_streamer`] for backends supporting it,
+/// which usually send each request over a dedicated connection.
+#[derive(Clone)]
+pub struct RequestStreamer(Arc<StreamFn>);
+
+impl fmt::Debug for RequestStreamer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestStreamer").finish_non_exhaustive()
+    }
+}
+
+impl RequestStreamer {
+    /// Creates a new streamer from a function sending a request, and
+    /// returning the stream of the elements of its result.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(SerializedRequest) -> TransportFut<'static, TransportStream<'static, Box<RawValue>>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Sends the request, returning the stream of the elements of its result.
+    pub fn request_stream(
+        &self,
+        req: SerializedRequest,
+    ) -> TransportFut<'static, TransportStream<'static, Box<RawValue>>> {
+        (self.0)(req)
+    }
+}
 
 /// Configuration objects that contain connection details for a backend.
 ///
@@ -17,15 +60,33 @@ pub trait PubSubConnect: Sized + Send + Sync + 'static {
     /// [`ConnectionInterface`]: crate::ConnectionInterface
     fn connect(&self) -> impl_future!(<Output = TransportResult<ConnectionHandle>>);
 
+    /// Returns the policy used to reconnect the transport in the event the
+    /// connection fails.
+    ///
+    /// Defaults to a single attempt.
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        ReconnectPolicy::default()
+    }
+
     /// Attempt to reconnect the transport.
     ///
     /// Override this to add custom reconnection logic to your connector. This
     /// will be used by the internal pubsub connection managers in the event the
-    /// connection fails.
+    /// connection fails, as many times as allowed by the
+    /// [`reconnect_policy`](Self::reconnect_policy).
     fn try_reconnect(&self) -> impl_future!(<Output = TransportResult<ConnectionHandle>>) {
         self.connect()
     }
 
+    /// Returns a [`RequestStreamer`] streaming the array results of requests,
+    /// if the backend supports it. It is used by
+    /// [`PubSubFrontend::request_stream`].
+    ///
+    /// Defaults to `None`.
+    fn request_streamer(&self) -> Option<RequestStreamer> {
+        None
+    }
+
     /// Convert the configuration object into a service with a running backend.
     fn into_service(self) -> impl_future!(<Output = TransportResult<PubSubFrontend>>) {
         PubSubService::connect(self)
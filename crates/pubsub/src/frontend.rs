@@ -1,8 +1,13 @@
-use crate::{ix::PubSubInstruction, managers::InFlight, RawSubscription};
-use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
+use crate::{
+    ix::PubSubInstruction, managers::InFlight, reconnect::SharedConnectionState, ConnectionState,
+    RawSubscription, RequestStreamer,
+};
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, RpcReturn, SerializedRequest};
 use alloy_primitives::B256;
-use alloy_transport::{TransportError, TransportErrorKind, TransportFut, TransportResult};
-use futures::{future::try_join_all, FutureExt, TryFutureExt};
+use alloy_transport::{
+    TransportError, TransportErrorKind, TransportFut, TransportResult, TransportStream,
+};
+use futures::{future::try_join_all, FutureExt, StreamExt, TryFutureExt};
 use std::{
     future::Future,
     sync::atomic::{AtomicUsize, Ordering},
@@ -20,19 +25,69 @@ pub struct PubSubFrontend {
     /// The number of items to buffer in new subscription channels. Defaults to
     /// 16. See [`tokio::sync::broadcast::channel`] for a description.
     channel_size: AtomicUsize,
+    /// The state of the connection of the service to its backend.
+    state: SharedConnectionState,
+    /// The streamer of array results, if the backend supports it.
+    streamer: Option<RequestStreamer>,
 }
 
 impl Clone for PubSubFrontend {
     fn clone(&self) -> Self {
         let channel_size = self.channel_size.load(Ordering::Relaxed);
-        Self { tx: self.tx.clone(), channel_size: AtomicUsize::new(channel_size) }
+        Self {
+            tx: self.tx.clone(),
+            channel_size: AtomicUsize::new(channel_size),
+            state: self.state.clone(),
+            streamer: self.streamer.clone(),
+        }
     }
 }
 
 impl PubSubFrontend {
     /// Create a new frontend.
-    pub(crate) const fn new(tx: mpsc::UnboundedSender<PubSubInstruction>) -> Self {
-        Self { tx, channel_size: AtomicUsize::new(16) }
+    pub(crate) const fn new(
+        tx: mpsc::UnboundedSender<PubSubInstruction>,
+        state: SharedConnectionState,
+        streamer: Option<RequestStreamer>,
+    ) -> Self {
+        Self { tx, channel_size: AtomicUsize::new(16), state, streamer }
+    }
+
+    /// Get the state of the connection of the service to its backend.
+    pub fn connection_state(&self) -> ConnectionState {
+        if self.tx.is_closed() {
+            return ConnectionState::Closed;
+        }
+        self.state.get()
+    }
+
+    /// Returns `true` if the backend supports streaming array results with
+    /// [`request_stream`](Self::request_stream).
+    pub const fn supports_request_stream(&self) -> bool {
+        self.streamer.is_some()
+    }
+
+    /// Sends a request whose result is an array, and streams the elements of
+    /// the result as they are read, instead of buffering the whole response.
+    ///
+    /// The request is sent by the [`RequestStreamer`] of the backend, bypassing
+    /// the service. Fails if the backend does not support streaming, see
+    /// [`supports_request_stream`](Self::supports_request_stream).
+    pub async fn request_stream<R: RpcReturn>(
+        &self,
+        req: SerializedRequest,
+    ) -> TransportResult<TransportStream<'static, R>> {
+        let Some(streamer) = &self.streamer else {
+            return Err(TransportErrorKind::custom_str(
+                "the pubsub backend does not support streaming requests",
+            ));
+        };
+        let stream = streamer.request_stream(req).await?;
+        Ok(Box::pin(stream.map(|item| {
+            let value = item?;
+            serde_json::from_str(value.get())
+                .map_err(|err| TransportError::deser_err(err, value.get()))
+        })))
     }
 
     /// Get the subscription ID for a local ID.
@@ -43,6 +43,12 @@ impl ConnectionHandle {
         (handle, interface)
     }
 
+    /// Returns `true` if the backend is still running, i.e. it has not shut
+    /// down or dropped its [`ConnectionInterface`].
+    pub fn is_connected(&self) -> bool {
+        !self.to_socket.is_closed()
+    }
+
     /// Shutdown the backend.
     pub fn shutdown(self) {
         let _ = self.shutdown.send(());
@@ -10,7 +10,7 @@
 extern crate tracing;
 
 mod connect;
-pub use connect::PubSubConnect;
+pub use connect::{PubSubConnect, RequestStreamer};
 
 mod frontend;
 pub use frontend::PubSubFrontend;
@@ -22,10 +22,13 @@ pub use handle::{ConnectionHandle, ConnectionInterface};
 
 mod managers;
 
+mod reconnect;
+pub use reconnect::{ConnectionEvent, ConnectionState, ReconnectPolicy};
+
 mod service;
 
 mod sub;
 pub use sub::{
-    RawSubscription, SubAnyStream, SubResultStream, Subscription, SubscriptionItem,
-    SubscriptionStream,
+    RawSubscription, RawSubscriptionStream, SubAnyStream, SubResultStream, Subscription,
+    SubscriptionItem, SubscriptionStream,
 };
@@ -1,4 +1,4 @@
-use crate::RawSubscription;
+use crate::{RawSubscription, SubscriptionItem};
 use alloy_json_rpc::SerializedRequest;
 use alloy_primitives::B256;
 use serde_json::value::RawValue;
@@ -12,8 +12,9 @@ pub(crate) struct ActiveSubscription {
     pub(crate) local_id: B256,
     /// The serialized subscription request.
     pub(crate) request: SerializedRequest,
-    /// The channel via which notifications are broadcast.
-    pub(crate) tx: broadcast::Sender<Box<RawValue>>,
+    /// The channel via which notifications and reconnection markers are
+    /// broadcast.
+    pub(crate) tx: broadcast::Sender<SubscriptionItem<Box<RawValue>>>,
 }
 
 // NB: We implement this to prevent any incorrect future implementations.
@@ -78,7 +79,15 @@ impl ActiveSubscription {
     /// If no receiver exists, the notification is dropped.
     pub(crate) fn notify(&self, notification: Box<RawValue>) {
         if self.tx.receiver_count() > 0 {
-            let _ = self.tx.send(notification);
+            let _ = self.tx.send(SubscriptionItem::Item(notification));
+        }
+    }
+
+    /// Notify the subscription channel that the subscription was re-established
+    /// after a reconnection, if any receiver exists.
+    pub(crate) fn notify_reconnected(&self) {
+        if self.tx.receiver_count() > 0 {
+            let _ = self.tx.send(SubscriptionItem::Reconnected);
         }
     }
 }
@@ -50,9 +50,16 @@ impl SubscriptionManager {
 
         // If we already know a subscription with the exact params,
         // we can just update the server_id and get a new listener.
-        if self.local_to_sub.contains_left(&local_id) {
+        if let Some(active) = self.local_to_sub.get_by_left(&local_id) {
+            // A known subscription without server_id is being re-established
+            // after a reconnection, so its listeners may have missed
+            // notifications.
+            if !self.local_to_server.contains_left(&local_id) {
+                active.notify_reconnected();
+            }
+            let sub = active.subscribe();
             self.change_server_id(local_id, server_id);
-            self.get_subscription(local_id).expect("checked existence")
+            sub
         } else {
             self.insert(request, server_id, channel_size)
         }
@@ -101,3 +108,76 @@ impl SubscriptionManager {
         self.local_to_sub.get_by_left(&local_id).map(ActiveSubscription::subscribe)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SubscriptionItem;
+    use alloy_json_rpc::{Id, Request};
+    use futures::{executor::block_on, StreamExt};
+    use serde_json::value::RawValue;
+
+    fn notification(server_id: &str, result: &str) -> EthNotification {
+        EthNotification {
+            subscription: SubId::String(server_id.to_string()),
+            result: RawValue::from_string(result.to_string()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn remaps_server_id_on_reconnect() {
+        let mut subs = SubscriptionManager::default();
+        let request =
+            Request::new("eth_subscribe", Id::Number(1), ("newHeads",)).serialize().unwrap();
+        let mut sub = subs.upsert(request.clone(), SubId::String("0xa".into()), 16);
+
+        // Subscribing again with the same params does not signal a reconnection.
+        let mut other = subs.upsert(request.clone(), SubId::String("0xa".into()), 16);
+        let stream = subs.upsert(request.clone(), SubId::String("0xa".into()), 16).into_stream();
+        subs.notify(notification("0xa", "1"));
+        assert_eq!(other.try_recv().unwrap().get(), "1");
+
+        subs.drop_server_ids();
+        subs.notify(notification("0xa", "2"));
+        let _ = subs.upsert(request, SubId::String("0xb".into()), 16);
+        subs.notify(notification("0xb", "3"));
+
+        assert_eq!(subs.len(), 1);
+        assert_eq!(subs.local_id_for(&SubId::String("0xb".into())), Some(*sub.local_id()));
+        assert!(
+            matches!(sub.try_recv_any(), Ok(SubscriptionItem::Item(value)) if value.get() == "1")
+        );
+        assert!(sub.try_recv_any().unwrap().is_reconnected());
+        assert!(
+            matches!(sub.try_recv_any(), Ok(SubscriptionItem::Item(value)) if value.get() == "3")
+        );
+        // The plain receivers skip the marker.
+        assert_eq!(other.try_recv().unwrap().get(), "3");
+        drop(subs);
+        let items: Vec<_> = block_on(stream.map(|item| item.unwrap().get().to_string()).collect());
+        assert_eq!(items, ["1", "3"]);
+    }
+
+    #[test]
+    fn mixes_plain_and_any_receivers() {
+        let mut subs = SubscriptionManager::default();
+        let request =
+            Request::new("eth_subscribe", Id::Number(1), ("newHeads",)).serialize().unwrap();
+        let mut sub = subs.upsert(request.clone(), SubId::String("0xa".into()), 16);
+
+        subs.notify(notification("0xa", "1"));
+        subs.drop_server_ids();
+        let _ = subs.upsert(request, SubId::String("0xb".into()), 16);
+        subs.notify(notification("0xb", "2"));
+        subs.notify(notification("0xb", "3"));
+
+        // Each notification is received once, whichever method reads it.
+        assert_eq!(sub.try_recv().unwrap().get(), "1");
+        assert!(sub.try_recv_any().unwrap().is_reconnected());
+        assert!(
+            matches!(sub.try_recv_any(), Ok(SubscriptionItem::Item(value)) if value.get() == "2")
+        );
+        assert_eq!(sub.try_recv().unwrap().get(), "3");
+        assert!(sub.try_recv().is_err());
+    }
+}
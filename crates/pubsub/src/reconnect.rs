@@ -0,0 +1,345 @@
+use crate::ConnectionHandle;
+use alloy_transport::{TransportErrorKind, TransportResult};
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    future::Future,
+    hash::BuildHasher,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::{
+    std::Instant,
+    tokio::{sleep, timeout},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{sleep, timeout, Instant};
+
+/// The default delay before the first reconnection attempt.
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The default maximum delay between reconnection attempts.
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// A connection event of a pubsub backend, passed to the observer of a
+/// [`ReconnectPolicy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The backend connected for the first time.
+    Connected,
+    /// The backend disconnected, and reconnection is about to start.
+    Disconnected,
+    /// A reconnection attempt is starting. Attempts are numbered from 1.
+    Reconnecting {
+        /// The number of the attempt.
+        attempt: u32,
+    },
+    /// The backend reconnected.
+    Reconnected,
+    /// Reconnection was abandoned, and the service shut down.
+    ReconnectFailed,
+}
+
+/// The state of the connection of a pubsub service to its backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ConnectionState {
+    /// The backend is connected.
+    Connected = 0,
+    /// The backend disconnected, and the service is reconnecting.
+    Reconnecting = 1,
+    /// The service shut down, either because all frontends were dropped, or
+    /// because reconnection failed.
+    Closed = 2,
+}
+
+/// A [`ConnectionState`] shared between a service and its frontends.
+#[derive(Clone, Debug)]
+pub(crate) struct SharedConnectionState(Arc<AtomicU8>);
+
+impl Default for SharedConnectionState {
+    fn default() -> Self {
+        Self(Arc::new(AtomicU8::new(ConnectionState::Connected as u8)))
+    }
+}
+
+impl SharedConnectionState {
+    /// Get the current state.
+    pub(crate) fn get(&self) -> ConnectionState {
+        match self.0.load(Ordering::Relaxed) {
+            0 => ConnectionState::Connected,
+            1 => ConnectionState::Reconnecting,
+            _ => ConnectionState::Closed,
+        }
+    }
+
+    /// Set the current state.
+    pub(crate) fn set(&self, state: ConnectionState) {
+        self.0.store(state as u8, Ordering::Relaxed);
+    }
+}
+
+type Observer = Arc<dyn Fn(ConnectionEvent) + Send + Sync>;
+
+/// The policy for reconnecting a pubsub backend after its connection failed.
+///
+/// Reconnection is attempted up to `max_attempts` times, waiting between
+/// attempts with an exponential backoff, starting at `initial_backoff` and
+/// capped at `max_backoff`. A random jitter of up to half the backoff is
+/// subtracted from each wait, so that many clients do not reconnect in
+/// lockstep. If a deadline is set, reconnection is abandoned once it is
+/// reached, including while an attempt is in progress.
+///
+/// Once reconnection is abandoned, the pubsub service shuts down, and its
+/// pending requests and subscriptions fail.
+///
+/// The default policy makes a single attempt.
+#[derive(Clone)]
+pub struct ReconnectPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    deadline: Option<Duration>,
+    observer: Option<Observer>,
+}
+
+impl fmt::Debug for ReconnectPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .field("deadline", &self.deadline)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReconnectPolicy {
+    /// Creates a new policy making a single reconnection attempt.
+    pub const fn new() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            jitter: true,
+            deadline: None,
+            observer: None,
+        }
+    }
+
+    /// Sets the maximum number of reconnection attempts. At least one attempt
+    /// is always made.
+    pub const fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the delays of the exponential backoff between attempts.
+    pub const fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets whether to apply a random jitter to the backoff. Enabled by
+    /// default.
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the total time allowed for reconnection, after which it is
+    /// abandoned.
+    pub const fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets a callback invoked on each [`ConnectionEvent`].
+    ///
+    /// The callback is invoked from the pubsub service task, so it should
+    /// return quickly.
+    pub fn with_observer<F>(mut self, observer: F) -> Self
+    where
+        F: Fn(ConnectionEvent) + Send + Sync + 'static,
+    {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Returns the maximum number of reconnection attempts.
+    pub const fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the total time allowed for reconnection, if any.
+    pub const fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    /// Returns the backoff to wait after the given failed attempt, without
+    /// jitter.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// Invoke the observer, if any.
+    pub(crate) fn notify(&self, event: ConnectionEvent) {
+        if let Some(observer) = &self.observer {
+            observer(event);
+        }
+    }
+
+    /// Returns the backoff to wait after the given failed attempt, with
+    /// jitter if enabled.
+    fn jittered_backoff(&self, attempt: u32) -> Duration {
+        let backoff = self.backoff(attempt);
+        if !self.jitter {
+            return backoff;
+        }
+        // A random fraction in [0, 0.5).
+        let random = RandomState::new().hash_one(attempt) as f64 / u64::MAX as f64 / 2.0;
+        backoff.mul_f64(1.0 - random)
+    }
+
+    /// Reconnect with the given function, following the policy.
+    pub(crate) async fn reconnect<F, Fut>(
+        &self,
+        mut connect: F,
+    ) -> TransportResult<ConnectionHandle>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = TransportResult<ConnectionHandle>>,
+    {
+        let deadline = self.deadline.map(|deadline| Instant::now() + deadline);
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.notify(ConnectionEvent::Reconnecting { attempt });
+
+            let res = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    timeout(remaining, connect()).await.unwrap_or_else(|_| {
+                        Err(TransportErrorKind::custom_str("reconnection deadline exceeded"))
+                    })
+                }
+                None => connect().await,
+            };
+            let err = match res {
+                Ok(handle) => {
+                    self.notify(ConnectionEvent::Reconnected);
+                    return Ok(handle);
+                }
+                Err(err) => err,
+            };
+
+            let backoff = self.jittered_backoff(attempt);
+            if attempt >= self.max_attempts
+                || deadline.is_some_and(|deadline| Instant::now() + backoff >= deadline)
+            {
+                error!(%err, attempt, "giving up reconnecting pubsub backend");
+                self.notify(ConnectionEvent::ReconnectFailed);
+                return Err(err);
+            }
+
+            warn!(%err, attempt, ?backoff, "failed to reconnect pubsub backend, retrying");
+            sleep(backoff).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = ReconnectPolicy::new()
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(false);
+        let backoffs: Vec<_> = (1..=6).map(|attempt| policy.backoff(attempt).as_millis()).collect();
+        assert_eq!(backoffs, [100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+
+        let policy = policy.with_jitter(true);
+        for attempt in 1..=6 {
+            let backoff = policy.jittered_backoff(attempt);
+            assert!(backoff <= policy.backoff(attempt));
+            assert!(backoff >= policy.backoff(attempt) / 2);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_after_max_attempts() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let policy = ReconnectPolicy::new().with_max_attempts(3).with_observer({
+            let events = Arc::clone(&events);
+            move |event| events.lock().unwrap().push(event)
+        });
+
+        let start = Instant::now();
+        let mut attempts = 0;
+        let res = policy
+            .reconnect(|| {
+                attempts += 1;
+                async { Err(TransportErrorKind::backend_gone()) }
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(attempts, 3);
+        // Two waits of at most 100ms and 200ms.
+        assert!(start.elapsed() <= Duration::from_millis(300));
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                ConnectionEvent::Reconnecting { attempt: 1 },
+                ConnectionEvent::Reconnecting { attempt: 2 },
+                ConnectionEvent::Reconnecting { attempt: 3 },
+                ConnectionEvent::ReconnectFailed,
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_at_deadline() {
+        let policy = ReconnectPolicy::new()
+            .with_max_attempts(u32::MAX)
+            .with_backoff(Duration::from_secs(1), Duration::from_secs(1))
+            .with_jitter(false)
+            .with_deadline(Duration::from_millis(2500));
+
+        let mut attempts = 0;
+        let res = policy
+            .reconnect(|| {
+                attempts += 1;
+                async { Err(TransportErrorKind::backend_gone()) }
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(attempts, 3);
+
+        // An attempt hanging past the deadline is abandoned.
+        let start = Instant::now();
+        let res = policy.reconnect(futures::future::pending).await;
+        assert!(res.is_err());
+        assert_eq!(start.elapsed(), Duration::from_millis(2500));
+    }
+}
@@ -2,7 +2,9 @@ use crate::{
     handle::ConnectionHandle,
     ix::PubSubInstruction,
     managers::{InFlight, RequestManager, SubscriptionManager},
-    PubSubConnect, PubSubFrontend, RawSubscription,
+    reconnect::SharedConnectionState,
+    ConnectionEvent, ConnectionState, PubSubConnect, PubSubFrontend, RawSubscription,
+    ReconnectPolicy,
 };
 use alloy_json_rpc::{Id, PubSubItem, Request, Response, ResponsePayload, SubId};
 use alloy_primitives::B256;
@@ -23,6 +25,12 @@ pub(crate) struct PubSubService<T> {
     /// The configuration details required to reconnect.
     pub(crate) connector: T,
 
+    /// The policy followed to reconnect.
+    pub(crate) reconnect_policy: ReconnectPolicy,
+
+    /// The state of the connection, shared with the frontends.
+    pub(crate) state: SharedConnectionState,
+
     /// The inbound requests.
     pub(crate) reqs: mpsc::UnboundedReceiver<PubSubInstruction>,
 
@@ -37,22 +45,29 @@ impl<T: PubSubConnect> PubSubService<T> {
     /// Create a new service from a connector.
     pub(crate) async fn connect(connector: T) -> TransportResult<PubSubFrontend> {
         let handle = connector.connect().await?;
+        let reconnect_policy = connector.reconnect_policy();
+        let streamer = connector.request_streamer();
+        reconnect_policy.notify(ConnectionEvent::Connected);
 
         let (tx, reqs) = mpsc::unbounded_channel();
+        let state = SharedConnectionState::default();
         let this = Self {
             handle,
             connector,
+            reconnect_policy,
+            state: state.clone(),
             reqs,
             subs: SubscriptionManager::default(),
             in_flights: Default::default(),
         };
         this.spawn();
-        Ok(PubSubFrontend::new(tx))
+        Ok(PubSubFrontend::new(tx, state, streamer))
     }
 
-    /// Reconnect by dropping the backend and creating a new one.
+    /// Reconnect by dropping the backend and creating a new one, following
+    /// the reconnect policy.
     async fn get_new_backend(&mut self) -> TransportResult<ConnectionHandle> {
-        let mut handle = self.connector.try_reconnect().await?;
+        let mut handle = self.reconnect_policy.reconnect(|| self.connector.try_reconnect()).await?;
         std::mem::swap(&mut self.handle, &mut handle);
         Ok(handle)
     }
@@ -61,8 +76,11 @@ impl<T: PubSubConnect> PubSubService<T> {
     /// subscriptions.
     async fn reconnect(&mut self) -> TransportResult<()> {
         info!("Reconnecting pubsub service backend.");
+        self.state.set(ConnectionState::Reconnecting);
+        self.reconnect_policy.notify(ConnectionEvent::Disconnected);
 
         let mut old_handle = self.get_new_backend().await?;
+        self.state.set(ConnectionState::Connected);
 
         debug!("Draining old backend to_handle");
 
@@ -84,17 +102,34 @@ impl<T: PubSubConnect> PubSubService<T> {
         // Re-subscribe to all active subscriptions
         debug!(count = self.subs.len(), "Re-starting active subscriptions");
 
-        // Drop all server IDs. We'll re-insert them as we get responses.
+        // Drop all server IDs. We'll re-insert them as we get responses, which
+        // maps the new server IDs to the existing local IDs, and notifies the
+        // listeners that they may have missed notifications.
         self.subs.drop_server_ids();
 
         // Dispatch all subscription requests.
-        for (_, sub) in self.subs.iter() {
+        for (local_id, sub) in self.subs.iter() {
             let req = sub.request().to_owned();
             // 0 is a dummy value, we don't care about the channel size here,
             // as none of these will result in channel creation.
-            let (in_flight, _) = InFlight::new(req.clone(), 0);
+            let (in_flight, rx) = InFlight::new(req.clone(), 0);
             self.in_flights.insert(in_flight);
 
+            // Nobody awaits the response, so report a failed re-subscription
+            // instead of silently dropping the subscription's notifications.
+            let local_id = *local_id;
+            async move {
+                match rx.await {
+                    Ok(Ok(resp)) if resp.is_success() => {}
+                    Ok(Ok(resp)) => {
+                        error!(%local_id, payload = ?resp.payload, "failed to re-establish subscription");
+                    }
+                    Ok(Err(err)) => error!(%local_id, %err, "failed to re-establish subscription"),
+                    Err(_) => {}
+                }
+            }
+            .spawn_task();
+
             let msg = req.into_serialized();
             self.handle.to_socket.send(msg).map_err(|_| TransportErrorKind::backend_gone())?;
         }
@@ -230,6 +265,7 @@ impl<T: PubSubConnect> PubSubService<T> {
                 }
             };
 
+            self.state.set(ConnectionState::Closed);
             if let Err(err) = result {
                 error!(%err, "pubsub service reconnection error");
             }
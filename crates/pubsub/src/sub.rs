@@ -10,11 +10,14 @@ use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 /// local ID.
 ///
 /// This type is mostly a wrapper around [`broadcast::Receiver`], and exposes
-/// the same methods.
+/// the same methods. The [`SubscriptionItem::Reconnected`] markers are only
+/// yielded by the `recv_any` methods and [`RawSubscription::into_stream_any`],
+/// and skipped by the other methods.
 #[derive(Debug)]
 pub struct RawSubscription {
-    /// The channel via which notifications are received.
-    pub(crate) rx: broadcast::Receiver<Box<RawValue>>,
+    /// The channel via which notifications and reconnection markers are
+    /// received.
+    pub(crate) rx: broadcast::Receiver<SubscriptionItem<Box<RawValue>>>,
     /// The local ID of the subscription.
     pub(crate) local_id: B256,
 }
@@ -30,6 +33,20 @@ impl RawSubscription {
     ///
     /// [`blocking_recv`]: broadcast::Receiver::blocking_recv
     pub fn blocking_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
+        loop {
+            if let Some(value) = self.rx.blocking_recv()?.into_value() {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Wrapper for [`blocking_recv`], may produce reconnection markers. Block
+    /// the current thread until a message is available.
+    ///
+    /// [`blocking_recv`]: broadcast::Receiver::blocking_recv
+    pub fn blocking_recv_any(
+        &mut self,
+    ) -> Result<SubscriptionItem<Box<RawValue>>, broadcast::error::RecvError> {
         self.rx.blocking_recv()
     }
 
@@ -41,6 +58,9 @@ impl RawSubscription {
 
     /// Returns the number of messages in the broadcast channel that this
     /// receiver has yet to receive.
+    ///
+    /// NB: This count includes the reconnection markers, which are skipped by
+    /// the methods other than `recv_any`.
     pub fn len(&self) -> usize {
         self.rx.len()
     }
@@ -49,6 +69,20 @@ impl RawSubscription {
     ///
     /// [`recv`]: broadcast::Receiver::recv
     pub async fn recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
+        loop {
+            if let Some(value) = self.rx.recv().await?.into_value() {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Wrapper for [`recv`], may produce reconnection markers. Await an item
+    /// from the channel.
+    ///
+    /// [`recv`]: broadcast::Receiver::recv
+    pub async fn recv_any(
+        &mut self,
+    ) -> Result<SubscriptionItem<Box<RawValue>>, broadcast::error::RecvError> {
         self.rx.recv().await
     }
 
@@ -73,11 +107,32 @@ impl RawSubscription {
     ///
     /// [`try_recv`]: broadcast::Receiver::try_recv
     pub fn try_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::TryRecvError> {
+        loop {
+            if let Some(value) = self.rx.try_recv()?.into_value() {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Wrapper for [`try_recv`], may produce reconnection markers. Attempt to
+    /// receive a message from the channel without awaiting.
+    ///
+    /// [`try_recv`]: broadcast::Receiver::try_recv
+    pub fn try_recv_any(
+        &mut self,
+    ) -> Result<SubscriptionItem<Box<RawValue>>, broadcast::error::TryRecvError> {
         self.rx.try_recv()
     }
 
-    /// Convert the subscription into a stream.
-    pub fn into_stream(self) -> BroadcastStream<Box<RawValue>> {
+    /// Convert the subscription into a stream, which skips the reconnection
+    /// markers.
+    pub fn into_stream(self) -> RawSubscriptionStream {
+        RawSubscriptionStream { inner: self.into_stream_any() }
+    }
+
+    /// Convert the subscription into a stream, which yields the reconnection
+    /// markers.
+    pub fn into_stream_any(self) -> BroadcastStream<SubscriptionItem<Box<RawValue>>> {
         self.rx.into()
     }
 
@@ -87,14 +142,74 @@ impl RawSubscription {
     }
 }
 
-/// An item in a typed [`Subscription`]. This is either the expected type, or
-/// some serialized value of another type.
+/// A stream of the notifications of a [`RawSubscription`], skipping the
+/// [`SubscriptionItem::Reconnected`] markers.
 #[derive(Debug)]
+pub struct RawSubscriptionStream {
+    inner: BroadcastStream<SubscriptionItem<Box<RawValue>>>,
+}
+
+impl Stream for RawSubscriptionStream {
+    type Item = Result<Box<RawValue>, BroadcastStreamRecvError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(item)) => match item.into_value() {
+                    Some(value) => return task::Poll::Ready(Some(Ok(value))),
+                    None => continue,
+                },
+                Some(Err(err)) => return task::Poll::Ready(Some(Err(err))),
+                None => return task::Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// An item in a typed [`Subscription`]. This is either the expected type,
+/// some serialized value of another type, or a marker that the subscription
+/// was re-established.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum SubscriptionItem<T> {
     /// The expected item.
     Item(T),
     /// Some other value.
     Other(Box<RawValue>),
+    /// The pubsub backend reconnected, and the subscription was re-established
+    /// on the server. Notifications sent while disconnected are lost, so
+    /// consumers may need to backfill the gap, e.g. by fetching the blocks or
+    /// logs they missed.
+    Reconnected,
+}
+
+impl<T> SubscriptionItem<T> {
+    /// Returns `true` if the item is a [`SubscriptionItem::Reconnected`]
+    /// marker.
+    pub const fn is_reconnected(&self) -> bool {
+        matches!(self, Self::Reconnected)
+    }
+}
+
+impl SubscriptionItem<Box<RawValue>> {
+    /// Returns the notification, or `None` for a reconnection marker.
+    fn into_value(self) -> Option<Box<RawValue>> {
+        match self {
+            Self::Item(value) | Self::Other(value) => Some(value),
+            Self::Reconnected => None,
+        }
+    }
+
+    /// Deserialize the notification into the expected type.
+    fn into_typed<T: DeserializeOwned>(self) -> SubscriptionItem<T> {
+        match self {
+            Self::Item(value) | Self::Other(value) => value.into(),
+            Self::Reconnected => SubscriptionItem::Reconnected,
+        }
+    }
 }
 
 impl<T: DeserializeOwned> From<Box<RawValue>> for SubscriptionItem<T> {
@@ -116,9 +231,12 @@ impl<T: DeserializeOwned> From<Box<RawValue>> for SubscriptionItem<T> {
 /// - The [`Subscription::recv`] method and its variants will discard any notifications of
 ///   unexpected types.
 /// - The [`Subscription::recv_any`] and its variants will yield unexpected types as
-///   [`SubscriptionItem::Other`].
+///   [`SubscriptionItem::Other`], and reconnections as [`SubscriptionItem::Reconnected`].
 /// - The [`Subscription::recv_result`] and its variants will attempt to deserialize the
 ///   notifications and yield the `serde_json::Result` of the deserialization.
+///
+/// Only the `recv_any` APIs signal that the subscription was re-established after the backend
+/// reconnected, and that notifications may have been missed. The other APIs skip these markers.
 #[derive(Debug)]
 #[must_use]
 pub struct Subscription<T> {
@@ -201,7 +319,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn blocking_recv_any(
         &mut self,
     ) -> Result<SubscriptionItem<T>, broadcast::error::RecvError> {
-        self.inner.blocking_recv().map(Into::into)
+        self.inner.blocking_recv_any().map(SubscriptionItem::into_typed)
     }
 
     /// Wrapper for [`recv`], may produce unexpected values. Await an item from
@@ -209,7 +327,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     ///
     /// [`recv`]: broadcast::Receiver::recv
     pub async fn recv_any(&mut self) -> Result<SubscriptionItem<T>, broadcast::error::RecvError> {
-        self.inner.recv().await.map(Into::into)
+        self.inner.recv_any().await.map(SubscriptionItem::into_typed)
     }
 
     /// Wrapper for [`try_recv`]. Attempt to receive a message from the channel
@@ -217,7 +335,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     ///
     /// [`try_recv`]: broadcast::Receiver::try_recv
     pub fn try_recv_any(&mut self) -> Result<SubscriptionItem<T>, broadcast::error::TryRecvError> {
-        self.inner.try_recv().map(Into::into)
+        self.inner.try_recv_any().map(SubscriptionItem::into_typed)
     }
 
     /// Convert the subscription into a stream.
@@ -244,7 +362,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_any_stream(self) -> SubAnyStream<T> {
         SubAnyStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            inner: self.inner.into_stream_any(),
             _pd: std::marker::PhantomData,
         }
     }
@@ -255,9 +373,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     /// [`blocking_recv`]: broadcast::Receiver::blocking_recv
     pub fn blocking_recv(&mut self) -> Result<T, broadcast::error::RecvError> {
         loop {
-            match self.blocking_recv_any()? {
-                SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+            if let SubscriptionItem::Item(item) = self.inner.blocking_recv()?.into() {
+                return Ok(item);
             }
         }
     }
@@ -268,9 +385,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     /// [`recv`]: broadcast::Receiver::recv
     pub async fn recv(&mut self) -> Result<T, broadcast::error::RecvError> {
         loop {
-            match self.recv_any().await? {
-                SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+            if let SubscriptionItem::Item(item) = self.inner.recv().await?.into() {
+                return Ok(item);
             }
         }
     }
@@ -281,9 +397,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     /// [`try_recv`]: broadcast::Receiver::try_recv
     pub fn try_recv(&mut self) -> Result<T, broadcast::error::TryRecvError> {
         loop {
-            match self.try_recv_any()? {
-                SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+            if let SubscriptionItem::Item(item) = self.inner.try_recv()?.into() {
+                return Ok(item);
             }
         }
     }
@@ -324,7 +439,7 @@ impl<T: DeserializeOwned> Subscription<T> {
 #[derive(Debug)]
 pub struct SubAnyStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: BroadcastStream<SubscriptionItem<Box<RawValue>>>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -344,7 +459,7 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
     ) -> task::Poll<Option<Self::Item>> {
         loop {
             match ready!(self.inner.poll_next_unpin(cx)) {
-                Some(Ok(value)) => return task::Poll::Ready(Some(value.into())),
+                Some(Ok(item)) => return task::Poll::Ready(Some(item.into_typed())),
                 Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
                     // This is OK.
                     debug!(%err, %self.id, "stream lagged");
@@ -362,7 +477,7 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
 #[derive(Debug)]
 pub struct SubscriptionStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: RawSubscriptionStream,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -408,7 +523,7 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
 #[derive(Debug)]
 pub struct SubResultStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: RawSubscriptionStream,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -30,6 +30,9 @@ pub mod requests;
 /// Types and functions related to the sidecar.
 pub mod sidecar;
 
+/// Types and functions related to validators.
+pub mod validator;
+
 /// Types and functions related to withdrawals.
 pub mod withdrawals;
 
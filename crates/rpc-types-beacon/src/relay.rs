@@ -247,6 +247,9 @@ pub struct ProposerPayloadsDeliveredQuery {
     /// A specific slot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<u64>,
+    /// A starting slot for a list of entries, walking back to older slots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<u64>,
     /// Maximum number of entries (200 max)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u64>,
@@ -274,6 +277,12 @@ impl ProposerPayloadsDeliveredQuery {
         self
     }
 
+    /// Sets the starting slot of the entries
+    pub const fn cursor(mut self, cursor: u64) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
     /// Sets the maximum number of entries (200 max)
     pub const fn limit(mut self, limit: u64) -> Self {
         self.limit = Some(limit);
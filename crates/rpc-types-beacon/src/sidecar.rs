@@ -1,5 +1,7 @@
 use crate::header::Header;
-use alloy_eips::eip4844::{deserialize_blob, Blob, BlobTransactionSidecar, Bytes48};
+use alloy_eips::eip4844::{
+    deserialize_blob, kzg_to_versioned_hash, Blob, BlobTransactionSidecar, Bytes48,
+};
 use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
@@ -33,6 +35,30 @@ impl BeaconBlobBundle {
     pub fn get_blob(&self, index: u64) -> Option<&BlobData> {
         self.data.iter().find(|blob| blob.index == index)
     }
+
+    /// Returns the blob with the given versioned hash.
+    pub fn get_blob_by_versioned_hash(&self, versioned_hash: &B256) -> Option<&BlobData> {
+        self.data.iter().find(|blob| blob.versioned_hash() == *versioned_hash)
+    }
+
+    /// Returns the [`BlobTransactionSidecar`] of a transaction, given its blob versioned hashes.
+    ///
+    /// Returns `None` if any of the blobs is not part of the bundle.
+    pub fn sidecar_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Option<BlobTransactionSidecar> {
+        let mut blobs = Vec::with_capacity(versioned_hashes.len());
+        let mut commitments = Vec::with_capacity(versioned_hashes.len());
+        let mut proofs = Vec::with_capacity(versioned_hashes.len());
+        for versioned_hash in versioned_hashes {
+            let blob = self.get_blob_by_versioned_hash(versioned_hash)?;
+            blobs.push(*blob.blob);
+            commitments.push(blob.kzg_commitment);
+            proofs.push(blob.kzg_proof);
+        }
+        Some(BlobTransactionSidecar { blobs, commitments, proofs })
+    }
 }
 
 /// Yields an iterator for BlobData
@@ -101,6 +127,13 @@ pub struct BlobData {
     pub kzg_commitment_inclusion_proof: Vec<B256>,
 }
 
+impl BlobData {
+    /// Returns the versioned hash of the blob, as committed to by blob transactions.
+    pub fn versioned_hash(&self) -> B256 {
+        kzg_to_versioned_hash(self.kzg_commitment.as_slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,4 +148,17 @@ mod tests {
         assert_eq!(json, serde_json::to_value(resp.clone()).unwrap());
         assert_eq!(6, resp.data.len());
     }
+
+    #[test]
+    fn sidecar_for_versioned_hashes() {
+        let s = include_str!("examples/sidecar.json");
+        let resp: BeaconBlobBundle = serde_json::from_str(s).unwrap();
+
+        let hashes = [resp.data[3].versioned_hash(), resp.data[1].versioned_hash()];
+        let sidecar = resp.sidecar_for_versioned_hashes(&hashes).unwrap();
+        assert_eq!(sidecar.versioned_hashes().collect::<Vec<_>>(), hashes);
+        assert_eq!(sidecar.proofs, vec![resp.data[3].kzg_proof, resp.data[1].kzg_proof]);
+
+        assert!(resp.sidecar_for_versioned_hashes(&[hashes[0], B256::ZERO]).is_none());
+    }
 }
@@ -0,0 +1,152 @@
+//! Beacon validator types.
+//!
+//! See also <https://ethereum.github.io/beacon-APIs/#/Beacon/getStateValidators>
+
+use crate::BlsPublicKey;
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+/// The response to a request for the validators of a state: `getStateValidators`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ValidatorsResponse {
+    /// True if the response references an unverified execution payload. Optimistic information may
+    /// be invalidated at a later time. If the field is not present, assume the False value.
+    pub execution_optimistic: bool,
+    /// True if the response references the finalized history of the chain, as determined by fork
+    /// choice. If the field is not present, additional calls are necessary to compare the epoch of
+    /// the requested information with the finalized checkpoint.
+    pub finalized: bool,
+    /// Container for the validator data.
+    pub data: Vec<ValidatorData>,
+}
+
+/// Container type for a validator, with its index, balance and status.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorData {
+    /// Index of the validator in the validator registry.
+    #[serde_as(as = "DisplayFromStr")]
+    pub index: u64,
+    /// Current balance of the validator, in gwei.
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: u64,
+    /// Status of the validator.
+    pub status: ValidatorStatus,
+    /// The `Validator` object from the CL spec.
+    pub validator: Validator,
+}
+
+/// A validator, as defined in the CL spec.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validator {
+    /// The BLS public key of the validator.
+    pub pubkey: BlsPublicKey,
+    /// Root of withdrawal credentials.
+    pub withdrawal_credentials: B256,
+    /// Balance at stake, in gwei.
+    #[serde_as(as = "DisplayFromStr")]
+    pub effective_balance: u64,
+    /// Whether the validator has been slashed.
+    pub slashed: bool,
+    /// When criteria for activation were met.
+    #[serde_as(as = "DisplayFromStr")]
+    pub activation_eligibility_epoch: u64,
+    /// Epoch when the validator activated.
+    #[serde_as(as = "DisplayFromStr")]
+    pub activation_epoch: u64,
+    /// Epoch when the validator exited.
+    #[serde_as(as = "DisplayFromStr")]
+    pub exit_epoch: u64,
+    /// When the validator can withdraw funds.
+    #[serde_as(as = "DisplayFromStr")]
+    pub withdrawable_epoch: u64,
+}
+
+/// Status of a validator.
+///
+/// See also <https://hackmd.io/ofFJ5gOmQpu1jjHilHbdQQ>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    /// The validator is waiting for its deposit to be processed.
+    PendingInitialized,
+    /// The validator is waiting to be activated.
+    PendingQueued,
+    /// The validator is active.
+    ActiveOngoing,
+    /// The validator is active, and has initiated an exit.
+    ActiveExiting,
+    /// The validator is active, and has been slashed.
+    ActiveSlashed,
+    /// The validator has exited without being slashed.
+    ExitedUnslashed,
+    /// The validator has exited after being slashed.
+    ExitedSlashed,
+    /// The validator can withdraw its balance.
+    WithdrawalPossible,
+    /// The validator has withdrawn its balance.
+    WithdrawalDone,
+}
+
+impl ValidatorStatus {
+    /// Returns the identifier value of the status, as used in queries.
+    pub const fn query_value(&self) -> &'static str {
+        match self {
+            Self::PendingInitialized => "pending_initialized",
+            Self::PendingQueued => "pending_queued",
+            Self::ActiveOngoing => "active_ongoing",
+            Self::ActiveExiting => "active_exiting",
+            Self::ActiveSlashed => "active_slashed",
+            Self::ExitedUnslashed => "exited_unslashed",
+            Self::ExitedSlashed => "exited_slashed",
+            Self::WithdrawalPossible => "withdrawal_possible",
+            Self::WithdrawalDone => "withdrawal_done",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use similar_asserts::assert_eq;
+
+    #[test]
+    fn serde_validators_response() {
+        let s = r#"{
+            "execution_optimistic": false,
+            "finalized": true,
+            "data": [
+                {
+                    "index": "1",
+                    "balance": "32000000000",
+                    "status": "active_ongoing",
+                    "validator": {
+                        "pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+                        "withdrawal_credentials": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                        "effective_balance": "32000000000",
+                        "slashed": false,
+                        "activation_eligibility_epoch": "0",
+                        "activation_epoch": "0",
+                        "exit_epoch": "18446744073709551615",
+                        "withdrawable_epoch": "18446744073709551615"
+                    }
+                }
+            ]
+        }"#;
+        let resp: ValidatorsResponse = serde_json::from_str(s).unwrap();
+        assert_eq!(resp.data.len(), 1);
+        assert_eq!(resp.data[0].status, ValidatorStatus::ActiveOngoing);
+        assert_eq!(resp.data[0].validator.exit_epoch, u64::MAX);
+
+        let json: serde_json::Value = serde_json::from_str(s).unwrap();
+        assert_eq!(json, serde_json::to_value(resp).unwrap());
+    }
+
+    #[test]
+    fn validator_status_query_value() {
+        let status = ValidatorStatus::WithdrawalPossible;
+        assert_eq!(serde_json::to_value(status).unwrap(), status.query_value());
+    }
+}
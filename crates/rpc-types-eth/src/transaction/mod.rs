@@ -2,7 +2,7 @@
 
 use alloy_consensus::{
     Signed, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant, TxEip7702, TxEnvelope, TxLegacy,
-    Typed2718,
+    TxSponsored, Typed2718,
 };
 use alloy_eips::{eip2718::Encodable2718, eip7702::SignedAuthorization};
 use alloy_network_primitives::TransactionResponse;
@@ -99,6 +99,20 @@ where
     }
 }
 
+impl Transaction {
+    /// Returns the address paying for the gas of this transaction.
+    ///
+    /// For sponsored transactions, the gas payer is recovered from the payer signature. For any
+    /// other transaction, this is the sender.
+    #[cfg(feature = "k256")]
+    pub fn payer(&self) -> Result<Address, alloy_primitives::SignatureError> {
+        match &self.inner {
+            TxEnvelope::Sponsored(tx) => tx.tx().recover_payer(self.from),
+            _ => Ok(self.from),
+        }
+    }
+}
+
 impl<T> Transaction<T>
 where
     T: Into<TransactionRequest>,
@@ -202,6 +216,20 @@ impl TryFrom<Transaction> for Signed<TxEip7702> {
     }
 }
 
+impl TryFrom<Transaction> for Signed<TxSponsored> {
+    type Error = ConversionError;
+
+    fn try_from(tx: Transaction) -> Result<Self, Self::Error> {
+        match tx.inner {
+            TxEnvelope::Sponsored(tx) => Ok(tx),
+            _ => Err(ConversionError::Custom(format!(
+                "expected Sponsored, got {}",
+                tx.inner.tx_type()
+            ))),
+        }
+    }
+}
+
 impl From<Transaction> for TxEnvelope {
     fn from(tx: Transaction) -> Self {
         tx.inner
@@ -459,4 +487,25 @@ mod tests {
         let tx = serde_json::from_str::<Transaction>(raw).unwrap();
         assert!(tx.inner.is_eip7702());
     }
+
+    #[test]
+    fn deserialize_sponsored() {
+        let raw = r#"{"blockHash":"0x1d3fc86e4f3b8e6ad7b6e2b8b0be2c30a5b1a1c0f5d6f1e4a3a7c5a1ab0f0b5c","blockNumber":"0x2168c4c","from":"0x75bbaf493a22aeb569799d8f136a57c1169f6755","gas":"0x1aa12","gasPrice":"0x4a817c800","maxFeePerGas":"0x4a817c800","maxPriorityFeePerGas":"0x4a817c800","hash":"0x421f2f71c76e0fc45c11339ec0453ae08f2b2bf2ae54c8c87f68a9bc486880d4","input":"0x095ea7b30000000000000000000000008a28c188a067dfa6aaec36e2b67b34d2c3042df90fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff","nonce":"0x0","to":"0x0b7007c13325c48911f73a2dad5fa5dcbf808adc","transactionIndex":"0x0","value":"0x0","type":"0x64","chainId":"0x7e4","expiredTime":"0x66d29dd2","payerV":"0x1","payerR":"0xa874e9dbb63262b7c3500c3130480c4877cebe91203098b3febc701db4940ff8","payerS":"0x5dc5f279f16ae525781b938a71935525f5baa42a7e2207a02f45a0d84e723575","v":"0x1","r":"0x697557c9fff6a7a781c4d96c9f9fa7ce6f6e3f8bb37d02544925b9d4e4ae83f7","s":"0x16cb0fcfcfbfd2d05bde7aac1a7a9b28faa0434851d6a7257f53f6936d72e0b5"}"#;
+        let tx = serde_json::from_str::<Transaction>(raw).unwrap();
+        assert!(tx.inner.is_sponsored());
+        assert_eq!(tx.tx_hash(), *tx.inner.tx_hash());
+
+        #[cfg(feature = "k256")]
+        assert_eq!(
+            tx.payer().unwrap(),
+            alloy_primitives::address!("fda1d16c49421735db06a568bc7ac2c6d07357c1")
+        );
+
+        let request = tx.clone().into_request();
+        assert_eq!(request.expired_time, Some(0x66d29dd2));
+        assert_eq!(request.payer_v, Some(U256::from(1)));
+
+        let signed = Signed::<TxSponsored>::try_from(tx).unwrap();
+        assert_eq!(signed.tx().expired_time, 0x66d29dd2);
+    }
 }
@@ -81,7 +81,7 @@ impl TransactionReceipt {
             | ReceiptEnvelope::Eip2930(receipt)
             | ReceiptEnvelope::Eip4844(receipt)
             | ReceiptEnvelope::Eip7702(receipt)
-            | ReceiptEnvelope::Legacy(receipt) 
+            | ReceiptEnvelope::Legacy(receipt)
             | ReceiptEnvelope::Sponsored(receipt) => receipt.receipt.status.coerce_status(),
         }
     }
@@ -248,6 +248,22 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_sponsored_receipt() {
+        let json_str = r#"{"transactionHash":"0x421f2f71c76e0fc45c11339ec0453ae08f2b2bf2ae54c8c87f68a9bc486880d4","blockHash":"0x1d3fc86e4f3b8e6ad7b6e2b8b0be2c30a5b1a1c0f5d6f1e4a3a7c5a1ab0f0b5c","blockNumber":"0x2168c4c","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","gasUsed":"0xb4a6","contractAddress":null,"cumulativeGasUsed":"0xb4a6","transactionIndex":"0x0","from":"0x75bbaf493a22aeb569799d8f136a57c1169f6755","to":"0x0b7007c13325c48911f73a2dad5fa5dcbf808adc","type":"0x64","effectiveGasPrice":"0x4a817c800","logs":[],"status":"0x1"}"#;
+
+        let receipt: TransactionReceipt = serde_json::from_str(json_str).unwrap();
+        assert_eq!(receipt.transaction_type(), TxType::Sponsored);
+        assert!(matches!(receipt.inner, ReceiptEnvelope::Sponsored(_)));
+        assert!(receipt.status());
+
+        assert_eq!(
+            serde_json::to_value(&receipt).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json_str).unwrap()
+        );
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn deserialize_pre_eip658_receipt() {
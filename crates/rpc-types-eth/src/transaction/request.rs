@@ -3,11 +3,15 @@
 use crate::{transaction::AccessList, BlobTransactionSidecar, Transaction, TransactionTrait};
 use alloy_consensus::{
     TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant, TxEip4844WithSidecar, TxEip7702, TxEnvelope,
-    TxLegacy, TxType, Typed2718, TypedTransaction,
+    TxLegacy, TxSponsored, TxSponsoredPayer, TxType, Typed2718, TypedTransaction,
 };
 use alloy_eips::eip7702::SignedAuthorization;
-use alloy_network_primitives::{TransactionBuilder4844, TransactionBuilder7702};
-use alloy_primitives::{Address, Bytes, ChainId, TxKind, B256, U256};
+use alloy_network_primitives::{
+    TransactionBuilder4844, TransactionBuilder7702, TransactionBuilderSponsored,
+};
+use alloy_primitives::{
+    Address, Bytes, ChainId, PrimitiveSignature as Signature, TxKind, B256, U256,
+};
 use core::hash::Hash;
 
 use alloc::{
@@ -132,6 +136,25 @@ pub struct TransactionRequest {
     /// Authorization list for for EIP-7702 transactions.
     #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub authorization_list: Option<Vec<SignedAuthorization>>,
+    /// Timestamp after which a sponsored transaction can no longer be included.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::quantity::opt"
+        )
+    )]
+    pub expired_time: Option<u64>,
+    /// The `v` value of the payer signature for sponsored transactions.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub payer_v: Option<U256>,
+    /// The `r` value of the payer signature for sponsored transactions.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub payer_r: Option<U256>,
+    /// The `s` value of the payer signature for sponsored transactions.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub payer_s: Option<U256>,
 }
 
 impl TransactionRequest {
@@ -183,6 +206,10 @@ impl TransactionRequest {
             transaction_type: Some(tx_type),
             sidecar: None,
             authorization_list,
+            expired_time: None,
+            payer_v: None,
+            payer_r: None,
+            payer_s: None,
         }
     }
 
@@ -443,6 +470,35 @@ impl TransactionRequest {
         })
     }
 
+    /// Build a sponsored transaction.
+    ///
+    /// Returns an error if required fields are missing. Use `complete_sponsored` to check if the
+    /// request can be built.
+    fn build_sponsored(self) -> Result<TxSponsored, &'static str> {
+        let checked_to = self.to.ok_or("Missing 'to' field for Sponsored transaction.")?;
+
+        Ok(TxSponsored {
+            chain_id: self.chain_id.unwrap_or(1),
+            nonce: self.nonce.ok_or("Missing 'nonce' field for Sponsored transaction.")?,
+            max_priority_fee_per_gas: self
+                .max_priority_fee_per_gas
+                .ok_or("Missing 'max_priority_fee_per_gas' field for Sponsored transaction.")?,
+            max_fee_per_gas: self
+                .max_fee_per_gas
+                .ok_or("Missing 'max_fee_per_gas' field for Sponsored transaction.")?,
+            gas_limit: self.gas.ok_or("Missing 'gas_limit' field for Sponsored transaction.")?,
+            to: checked_to,
+            value: self.value.unwrap_or_default(),
+            input: self.input.into_input().unwrap_or_default(),
+            expired_time: self
+                .expired_time
+                .ok_or("Missing 'expired_time' field for Sponsored transaction.")?,
+            payer_v: self.payer_v.ok_or("Missing 'payer_v' field for Sponsored transaction.")?,
+            payer_r: self.payer_r.ok_or("Missing 'payer_r' field for Sponsored transaction.")?,
+            payer_s: self.payer_s.ok_or("Missing 'payer_s' field for Sponsored transaction.")?,
+        })
+    }
+
     fn check_reqd_fields(&self) -> Vec<&'static str> {
         let mut missing = Vec::with_capacity(12);
         if self.nonce.is_none() {
@@ -472,6 +528,29 @@ impl TransactionRequest {
         }
     }
 
+    fn check_sponsored_fields(&self, missing: &mut Vec<&'static str>) {
+        if self.expired_time.is_none() {
+            missing.push("expired_time");
+        }
+        if self.payer_v.is_none() {
+            missing.push("payer_v");
+        }
+        if self.payer_r.is_none() {
+            missing.push("payer_r");
+        }
+        if self.payer_s.is_none() {
+            missing.push("payer_s");
+        }
+    }
+
+    /// Clears the sponsored transaction fields.
+    fn clear_sponsored_fields(&mut self) {
+        self.expired_time = None;
+        self.payer_v = None;
+        self.payer_r = None;
+        self.payer_s = None;
+    }
+
     /// Trim field conflicts, based on the preferred type
     ///
     /// This is used to ensure that the request will not be rejected by the
@@ -487,6 +566,7 @@ impl TransactionRequest {
                 self.sidecar = None;
                 self.access_list = None;
                 self.authorization_list = None;
+                self.clear_sponsored_fields();
             }
             TxType::Eip2930 => {
                 self.max_fee_per_gas = None;
@@ -495,6 +575,7 @@ impl TransactionRequest {
                 self.blob_versioned_hashes = None;
                 self.sidecar = None;
                 self.authorization_list = None;
+                self.clear_sponsored_fields();
             }
             TxType::Eip1559 => {
                 self.gas_price = None;
@@ -502,23 +583,27 @@ impl TransactionRequest {
                 self.blob_versioned_hashes = None;
                 self.sidecar = None;
                 self.authorization_list = None;
+                self.clear_sponsored_fields();
             }
             TxType::Eip4844 => {
                 self.gas_price = None;
                 self.authorization_list = None;
+                self.clear_sponsored_fields();
             }
             TxType::Eip7702 => {
                 self.gas_price = None;
                 self.max_fee_per_blob_gas = None;
                 self.blob_versioned_hashes = None;
                 self.sidecar = None;
+                self.clear_sponsored_fields();
             }
             TxType::Sponsored => {
-           
+                self.gas_price = None;
                 self.max_fee_per_blob_gas = None;
                 self.blob_versioned_hashes = None;
                 self.sidecar = None;
                 self.access_list = None;
+                self.authorization_list = None;
             }
         }
     }
@@ -526,13 +611,19 @@ impl TransactionRequest {
     /// Check this builder's preferred type, based on the fields that are set.
     ///
     /// Types are preferred as follows:
+    /// - Sponsored if transaction_type is 100, or expired_time or the payer signature is set
     /// - EIP-7702 if authorization_list is set
     /// - EIP-4844 if sidecar or max_blob_fee_per_gas is set
     /// - EIP-2930 if access_list is set
     /// - Legacy if gas_price is set and access_list is unset
     /// - EIP-1559 in all other cases
     pub const fn preferred_type(&self) -> TxType {
-        if self.authorization_list.is_some() {
+        if matches!(self.transaction_type, Some(ty) if ty == TxType::Sponsored as u8)
+            || self.expired_time.is_some()
+            || self.payer_v.is_some()
+        {
+            TxType::Sponsored
+        } else if self.authorization_list.is_some() {
             TxType::Eip7702
         } else if self.sidecar.is_some() || self.max_fee_per_blob_gas.is_some() {
             TxType::Eip4844
@@ -559,7 +650,7 @@ impl TransactionRequest {
             TxType::Eip1559 => self.complete_1559(),
             TxType::Eip4844 => self.complete_4844(),
             TxType::Eip7702 => self.complete_7702(),
-            TxType::Sponsored => todo!(),
+            TxType::Sponsored => self.complete_sponsored(),
         } {
             Err((pref, missing))
         } else {
@@ -640,6 +731,22 @@ impl TransactionRequest {
         }
     }
 
+    /// Check if all necessary keys are present to build a sponsored transaction,
+    /// returning a list of keys that are missing.
+    ///
+    /// **NOTE:** the payer signature must be present, as it is covered by the sender's signature.
+    pub fn complete_sponsored(&self) -> Result<(), Vec<&'static str>> {
+        let mut missing = self.check_reqd_fields();
+        self.check_1559_fields(&mut missing);
+        self.check_sponsored_fields(&mut missing);
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
     /// Check if all necessary keys are present to build a legacy transaction,
     /// returning a list of keys that are missing.
     pub fn complete_legacy(&self) -> Result<(), Vec<&'static str>> {
@@ -663,7 +770,7 @@ impl TransactionRequest {
             TxType::Eip1559 => self.complete_1559().ok(),
             TxType::Eip4844 => self.complete_4844().ok(),
             TxType::Eip7702 => self.complete_7702().ok(),
-            TxType::Sponsored => todo!(),
+            TxType::Sponsored => self.complete_sponsored().ok(),
         }?;
         Some(pref)
     }
@@ -684,7 +791,7 @@ impl TransactionRequest {
             // `sidecar` is a hard requirement since this must be a _sendable_ transaction.
             TxType::Eip4844 => self.build_4844_with_sidecar().expect("checked)").into(),
             TxType::Eip7702 => self.build_7702().expect("checked)").into(),
-            TxType::Sponsored => todo!(),
+            TxType::Sponsored => self.build_sponsored().expect("checked)").into(),
         })
     }
 
@@ -706,7 +813,7 @@ impl TransactionRequest {
             TxType::Eip1559 => self.clone().build_1559().map(Into::into),
             TxType::Eip4844 => self.clone().build_4844_variant().map(Into::into),
             TxType::Eip7702 => self.clone().build_7702().map(Into::into),
-            TxType::Sponsored => todo!(),
+            TxType::Sponsored => self.clone().build_sponsored().map(Into::into),
         }
         .map_err(|msg| self.into_tx_err(msg))
     }
@@ -746,6 +853,57 @@ impl TransactionBuilder7702 for TransactionRequest {
     }
 }
 
+impl TransactionBuilderSponsored for TransactionRequest {
+    fn expired_time(&self) -> Option<u64> {
+        self.expired_time
+    }
+
+    fn set_expired_time(&mut self, expired_time: u64) {
+        self.expired_time = Some(expired_time);
+    }
+
+    fn payer_signature(&self) -> Option<Signature> {
+        let (v, r, s) = (self.payer_v?, self.payer_r?, self.payer_s?);
+        let y_parity = match u64::try_from(v) {
+            Ok(0) => false,
+            Ok(1) => true,
+            _ => return None,
+        };
+        Some(Signature::new(r, s, y_parity))
+    }
+
+    fn set_payer_signature(&mut self, signature: Signature) {
+        self.payer_v = Some(U256::from(signature.v() as u8));
+        self.payer_r = Some(signature.r());
+        self.payer_s = Some(signature.s());
+    }
+
+    fn payer_signing_tx(&self) -> Result<TxSponsoredPayer, Vec<&'static str>> {
+        let mut missing = self.check_reqd_fields();
+        self.check_1559_fields(&mut missing);
+        if self.expired_time.is_none() {
+            missing.push("expired_time");
+        }
+        let Some(sender) = self.from else {
+            missing.push("from");
+            return Err(missing);
+        };
+        if !missing.is_empty() {
+            return Err(missing);
+        }
+
+        let tx = Self {
+            payer_v: Some(U256::ZERO),
+            payer_r: Some(U256::ZERO),
+            payer_s: Some(U256::ZERO),
+            ..self.clone()
+        }
+        .build_sponsored()
+        .expect("checked");
+        Ok(tx.payer_signing_tx(sender))
+    }
+}
+
 impl From<Transaction> for TransactionRequest {
     fn from(tx: Transaction) -> Self {
         tx.into_request()
@@ -903,6 +1061,42 @@ impl From<TxEip7702> for TransactionRequest {
     }
 }
 
+impl From<TxSponsored> for TransactionRequest {
+    fn from(tx: TxSponsored) -> Self {
+        let ty = tx.ty();
+        let TxSponsored {
+            chain_id,
+            nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            to,
+            value,
+            input,
+            expired_time,
+            payer_v,
+            payer_r,
+            payer_s,
+        } = tx;
+        Self {
+            to: if let TxKind::Call(to) = to { Some(to.into()) } else { None },
+            gas: Some(gas_limit),
+            max_fee_per_gas: Some(max_fee_per_gas),
+            max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+            value: Some(value),
+            input: input.into(),
+            nonce: Some(nonce),
+            chain_id: Some(chain_id),
+            transaction_type: Some(ty),
+            expired_time: Some(expired_time),
+            payer_v: Some(payer_v),
+            payer_r: Some(payer_r),
+            payer_s: Some(payer_s),
+            ..Default::default()
+        }
+    }
+}
+
 impl From<TypedTransaction> for TransactionRequest {
     fn from(tx: TypedTransaction) -> Self {
         match tx {
@@ -911,7 +1105,7 @@ impl From<TypedTransaction> for TransactionRequest {
             TypedTransaction::Eip1559(tx) => tx.into(),
             TypedTransaction::Eip4844(tx) => tx.into(),
             TypedTransaction::Eip7702(tx) => tx.into(),
-            TypedTransaction::Sponsored(_) => todo!(),
+            TypedTransaction::Sponsored(tx) => tx.into(),
         }
     }
 }
@@ -1004,7 +1198,23 @@ impl From<TxEnvelope> for TransactionRequest {
                     tx.strip_signature().into()
                 }
             }
-            TxEnvelope::Sponsored(tx) => todo!(),
+            TxEnvelope::Sponsored(tx) => {
+                #[cfg(feature = "k256")]
+                {
+                    let from = tx.recover_signer().ok();
+                    let tx: Self = tx.strip_signature().into();
+                    if let Some(from) = from {
+                        tx.from(from)
+                    } else {
+                        tx
+                    }
+                }
+
+                #[cfg(not(feature = "k256"))]
+                {
+                    tx.strip_signature().into()
+                }
+            }
         }
     }
 }
@@ -1429,5 +1639,60 @@ mod tests {
                 eip4844_request_incorrect_to.build_consensus_tx();
             assert_matches!(maybe_eip4844_tx, Err(..));
         }
+
+        // Sponsored
+        {
+            // Positive case
+            let sponsored_request = TransactionRequest {
+                to: Some(TxKind::Call(Address::repeat_byte(0xDE))),
+                max_fee_per_gas: Some(1234),
+                max_priority_fee_per_gas: Some(678),
+                nonce: Some(57),
+                gas: Some(123456),
+                expired_time: Some(1_700_000_000),
+                payer_v: Some(U256::from(1)),
+                payer_r: Some(U256::from(2)),
+                payer_s: Some(U256::from(3)),
+                ..Default::default()
+            };
+
+            let maybe_sponsored_tx: Result<TypedTransaction, _> =
+                sponsored_request.clone().build_consensus_tx();
+            assert_matches!(
+                maybe_sponsored_tx,
+                Ok(TypedTransaction::Sponsored(TxSponsored { expired_time: 1_700_000_000, .. }))
+            );
+
+            // Round trip
+            let tx = maybe_sponsored_tx.unwrap();
+            let request: TransactionRequest = tx.into();
+            assert_eq!(request.expired_time, sponsored_request.expired_time);
+            assert_eq!(request.payer_s, sponsored_request.payer_s);
+
+            // Negative case
+            let sponsored_request_missing_payer = TransactionRequest {
+                payer_v: None,
+                payer_r: None,
+                payer_s: None,
+                ..sponsored_request
+            };
+
+            let maybe_sponsored_tx: Result<TypedTransaction, _> =
+                sponsored_request_missing_payer.build_consensus_tx();
+            assert_matches!(maybe_sponsored_tx, Err(..));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_sponsored_request() {
+        let s = r#"{"to":"0x70997970c51812dc3a010c7d01b50e0d17dc79c8","maxFeePerGas":"0x77359401","maxPriorityFeePerGas":"0x1","nonce":"0x0","expiredTime":"0x66d29dd2","payerV":"0x1","payerR":"0x2","payerS":"0x3"}"#;
+        let req = serde_json::from_str::<TransactionRequest>(s).unwrap();
+        assert_eq!(req.preferred_type(), TxType::Sponsored);
+        assert_eq!(req.expired_time, Some(0x66d29dd2));
+        assert_eq!(req.payer_signature().unwrap().r(), U256::from(2));
+
+        let req = TransactionRequest { payer_v: Some(U256::MAX), ..req };
+        assert_eq!(req.payer_signature(), None);
     }
 }
@@ -1,6 +1,6 @@
 use crate::{u256_numeric_string, ConsideredByBuildersAt, SealedByBuildersAt};
 
-use alloy_primitives::U256;
+use alloy_primitives::{B256, U256};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // TODO(@optimiz-r): Revisit after <https://github.com/flashbots/flashbots-docs/issues/424> is closed.
@@ -96,6 +96,19 @@ pub struct StatsSimulated {
     pub sealed_by_builders_at: Vec<SealedByBuildersAt>,
 }
 
+/// Request for `flashbots_getBundleStatsV2`
+///
+/// <https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#flashbots_getbundlestatsv2>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleStatsRequest {
+    /// Hash of the bundle, as returned by `eth_sendBundle`.
+    pub bundle_hash: B256,
+    /// Block number the bundle was targeting.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+}
+
 /// Response for `flashbots_getUserStatsV2` represents stats for a searcher.
 ///
 /// Note: this is V2: <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint#flashbots_getuserstatsv2>
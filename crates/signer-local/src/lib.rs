@@ -290,6 +290,56 @@ mod test {
         assert_eq!(error.to_string(), expected_error.to_string());
     }
 
+    #[tokio::test]
+    async fn signs_sponsored_tx_as_payer() {
+        use alloy_network::{
+            Ethereum, EthereumWallet, Network, NetworkWallet, TransactionBuilder,
+            TransactionBuilderPayer, TransactionBuilderSponsored,
+        };
+
+        let sender = PrivateKeySigner::random();
+        let payer = PrivateKeySigner::random();
+
+        let request = <Ethereum as Network>::TransactionRequest::default()
+            .with_from(sender.address())
+            .with_to(address!("F0109fC8DF283027b6285cc889F5aA624EaC1F55"))
+            .with_chain_id(2021)
+            .with_nonce(0)
+            .with_gas_limit(21_000)
+            .with_max_fee_per_gas(20_000_000_000)
+            .with_max_priority_fee_per_gas(20_000_000_000)
+            .with_expired_time(1_700_000_000);
+
+        // the sender signature covers the payer signature
+        assert_eq!(request.output_tx_type_checked(), None);
+
+        let request = request.with_payer_signer(&payer).await.unwrap();
+        assert!(request.payer_signature().is_some());
+
+        let wallet = EthereumWallet::from(sender.clone());
+        let envelope = NetworkWallet::<Ethereum>::sign_request(&wallet, request).await.unwrap();
+        let signed = envelope.as_sponsored().unwrap();
+        let recovered_sender =
+            signed.signature().recover_address_from_prehash(&signed.signature_hash()).unwrap();
+        assert_eq!(recovered_sender, sender.address());
+
+        let payer_hash = signed.tx().payer_signature_hash(sender.address());
+        let payer_sig = signed.tx().payer_signature().unwrap();
+        assert_eq!(payer_sig.recover_address_from_prehash(&payer_hash).unwrap(), payer.address());
+    }
+
+    #[tokio::test]
+    async fn payer_signing_requires_sender() {
+        use alloy_network::{
+            Ethereum, Network, TransactionBuilderPayer, TransactionBuilderSponsored,
+        };
+
+        let mut request =
+            <Ethereum as Network>::TransactionRequest::default().with_expired_time(1_700_000_000);
+        let error = request.sign_payer(&PrivateKeySigner::random()).await.unwrap_err();
+        assert!(error.to_string().contains("from"));
+    }
+
     // <https://github.com/alloy-rs/core/issues/705>
     #[test]
     fn test_parity() {
@@ -81,16 +81,18 @@ where
 {
     async fn do_hyper(self, req: RequestPacket) -> TransportResult<ResponsePacket> {
         debug!(count = req.len(), "sending request packet to server");
+        let headers = crate::request_headers(&req);
         let ser = req.serialize().map_err(TransportError::ser_err)?;
         // convert the Box<RawValue> into a hyper request<B>
         let body = ser.get().as_bytes().to_owned().into();
 
-        let req = hyper::Request::builder()
+        let mut req = hyper::Request::builder()
             .method(hyper::Method::POST)
             .uri(self.url.as_str())
             .header(header::CONTENT_TYPE, header::HeaderValue::from_static("application/json"))
             .body(body)
             .expect("request parts are invalid");
+        req.headers_mut().extend(headers);
 
         let mut service = self.client.service;
         let resp = service.call(req).await.map_err(TransportErrorKind::custom)?;
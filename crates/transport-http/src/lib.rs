@@ -36,6 +36,34 @@ use core::str::FromStr;
 use std::marker::PhantomData;
 use url::Url;
 
+/// Get the additional HTTP headers of all requests in the packet, attached to
+/// them as an [`http::HeaderMap`] extension.
+///
+/// Headers of a batch are merged. If several requests of the batch set the
+/// same header, all values are kept.
+#[cfg(any(feature = "reqwest", all(not(target_arch = "wasm32"), feature = "hyper")))]
+fn request_headers(req: &alloy_json_rpc::RequestPacket) -> http::HeaderMap {
+    use alloy_json_rpc::RequestPacket;
+    use http::HeaderMap;
+
+    match req {
+        RequestPacket::Single(single) => {
+            single.meta().extensions().get::<HeaderMap>().cloned().unwrap_or_default()
+        }
+        RequestPacket::Batch(batch) => {
+            let mut headers = HeaderMap::new();
+            for req in batch {
+                if let Some(h) = req.meta().extensions().get::<HeaderMap>() {
+                    for (name, value) in h {
+                        headers.append(name, value.clone());
+                    }
+                }
+            }
+            headers
+        }
+    }
+}
+
 /// Connection details for an HTTP transport.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[doc(hidden)]
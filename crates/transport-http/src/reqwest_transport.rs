@@ -1,8 +1,8 @@
 use crate::{Http, HttpConnect};
-use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_json_rpc::{RequestPacket, ResponsePacket, RpcReturn, SerializedRequest};
 use alloy_transport::{
-    utils::guess_local_url, BoxTransport, TransportConnect, TransportError, TransportErrorKind,
-    TransportFut, TransportResult,
+    decode_result_array, utils::guess_local_url, BoxTransport, TransportConnect, TransportError,
+    TransportErrorKind, TransportFut, TransportResult, TransportStream,
 };
 use std::task;
 use tower::Service;
@@ -38,6 +38,7 @@ impl Http<Client> {
         let resp = self
             .client
             .post(self.url)
+            .headers(crate::request_headers(&req))
             .json(&req)
             .send()
             .await
@@ -67,6 +68,46 @@ impl Http<Client> {
         serde_json::from_slice(&body)
             .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
     }
+
+    /// Sends a request whose result is an array, and streams the elements of the result as the
+    /// response body is received, instead of buffering the whole response.
+    ///
+    /// This is meant for very large results, such as `eth_getLogs` over a wide block range. The
+    /// request is sent, and its status checked, before the stream is returned. Errors in the
+    /// response, including error responses, are yielded by the stream. See
+    /// [`ResultArrayDecoder`](alloy_transport::ResultArrayDecoder) for details.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn request_stream<T: RpcReturn>(
+        &self,
+        req: SerializedRequest,
+    ) -> TransportResult<TransportStream<'static, T>> {
+        let req = RequestPacket::Single(req);
+        let resp = self
+            .client
+            .post(self.url.clone())
+            .headers(crate::request_headers(&req))
+            .json(&req)
+            .send()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let status = resp.status();
+
+        debug!(%status, "received response from server, streaming body");
+
+        if status != reqwest::StatusCode::OK {
+            let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+            return Err(TransportErrorKind::http_error(
+                status.as_u16(),
+                String::from_utf8_lossy(&body).into_owned(),
+            ));
+        }
+
+        let chunks = futures::stream::try_unfold(resp, |mut resp| async move {
+            let chunk = resp.chunk().await.map_err(TransportErrorKind::custom)?;
+            Ok(chunk.map(|chunk| (chunk, resp)))
+        });
+        Ok(Box::pin(decode_result_array(chunks)))
+    }
 }
 
 impl Service<RequestPacket> for Http<reqwest::Client> {
@@ -1,3 +1,4 @@
+use alloy_pubsub::ReconnectPolicy;
 use interprocess::local_socket as ls;
 use std::io;
 
@@ -13,6 +14,7 @@ pub(crate) fn to_name(path: &std::ffi::OsStr) -> io::Result<ls::Name<'_>> {
 #[derive(Clone, Debug)]
 pub struct IpcConnect<T> {
     inner: T,
+    reconnect_policy: ReconnectPolicy,
 }
 
 impl<T> IpcConnect<T> {
@@ -22,7 +24,13 @@ impl<T> IpcConnect<T> {
     where
         Self: alloy_pubsub::PubSubConnect,
     {
-        Self { inner }
+        Self { inner, reconnect_policy: ReconnectPolicy::new() }
+    }
+
+    /// Sets the policy followed to reconnect.
+    pub fn with_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
     }
 }
 
@@ -30,7 +38,7 @@ macro_rules! impl_connect {
     ($target:ty => | $inner:ident | $map:expr) => {
         impl From<$target> for IpcConnect<$target> {
             fn from(inner: $target) -> Self {
-                Self { inner }
+                Self::new(inner)
             }
         }
 
@@ -45,6 +53,10 @@ macro_rules! impl_connect {
                 true
             }
 
+            fn reconnect_policy(&self) -> ReconnectPolicy {
+                self.reconnect_policy.clone()
+            }
+
             async fn connect(
                 &self,
             ) -> Result<alloy_pubsub::ConnectionHandle, alloy_transport::TransportError> {
@@ -55,6 +67,32 @@ macro_rules! impl_connect {
                     .await
                     .map_err(alloy_transport::TransportErrorKind::custom)
             }
+
+            fn request_streamer(&self) -> Option<alloy_pubsub::RequestStreamer> {
+                let this = self.clone();
+                Some(alloy_pubsub::RequestStreamer::new(move |req| {
+                    let this = this.clone();
+                    Box::pin(async move { this.request_stream(req).await })
+                }))
+            }
+        }
+
+        impl IpcConnect<$target> {
+            /// Sends a request whose result is an array over a dedicated connection, and streams
+            /// the elements of the result as they are read, instead of buffering the whole
+            /// response.
+            ///
+            /// See [`request_stream`](crate::request_stream) for details.
+            pub async fn request_stream<R: alloy_json_rpc::RpcReturn>(
+                &self,
+                req: alloy_json_rpc::SerializedRequest,
+            ) -> alloy_transport::TransportResult<alloy_transport::TransportStream<'static, R>>
+            {
+                let $inner = &self.inner;
+                let inner = $map;
+                let name = to_name(inner).map_err(alloy_transport::TransportErrorKind::custom)?;
+                crate::request_stream(name, req).await
+            }
         }
     };
 }
@@ -17,7 +17,7 @@ use tokio::{
     io::{AsyncRead, AsyncWriteExt},
     select,
 };
-use tokio_util::io::poll_read_buf;
+use tokio_util::io::{poll_read_buf, ReaderStream};
 
 mod connect;
 pub use connect::IpcConnect;
@@ -96,6 +96,27 @@ impl IpcBackend {
     }
 }
 
+/// Sends a request whose result is an array over a dedicated connection to a local socket, and
+/// streams the elements of the result as they are read.
+///
+/// The response is read with [`ReadJsonStream::into_result_array`], so that very large results,
+/// such as `eth_getLogs` over a wide block range, need not be buffered. The connection is closed
+/// once the response was read, or the stream dropped.
+pub async fn request_stream<R: alloy_json_rpc::RpcReturn>(
+    name: Name<'_>,
+    req: alloy_json_rpc::SerializedRequest,
+) -> alloy_transport::TransportResult<alloy_transport::TransportStream<'static, R>> {
+    use alloy_transport::TransportErrorKind;
+
+    let mut stream = LocalSocketStream::connect(name).await.map_err(TransportErrorKind::custom)?;
+    stream
+        .write_all(req.serialized().get().as_bytes())
+        .await
+        .map_err(TransportErrorKind::custom)?;
+
+    Ok(Box::pin(ReadJsonStream::new(stream).into_result_array()))
+}
+
 /// Default capacity for the IPC buffer.
 const CAPACITY: usize = 4096;
 
@@ -118,6 +139,26 @@ impl<T: AsyncRead> ReadJsonStream<T> {
     }
 }
 
+impl<T: AsyncRead + Send + 'static> ReadJsonStream<T> {
+    /// Converts the stream into a stream of the elements of the array result of the next
+    /// response.
+    ///
+    /// Unlike the items of this stream, which are buffered until they can be deserialized
+    /// entirely, the response is decoded incrementally with a [`ResultArrayDecoder`], so that
+    /// only the current element is buffered. The reader must not receive other messages, such as
+    /// subscription notifications, before the response.
+    ///
+    /// [`ResultArrayDecoder`]: alloy_transport::ResultArrayDecoder
+    pub fn into_result_array<R: alloy_json_rpc::RpcReturn>(
+        self,
+    ) -> impl futures::Stream<Item = alloy_transport::TransportResult<R>> + Send + 'static {
+        let buffered = futures::stream::iter((!self.buf.is_empty()).then(|| Ok(self.buf.freeze())));
+        let chunks = ReaderStream::with_capacity(self.reader, CAPACITY)
+            .map(|chunk| chunk.map_err(alloy_transport::TransportErrorKind::custom));
+        alloy_transport::decode_result_array(buffered.chain(chunks))
+    }
+}
+
 impl<T: AsyncRead> From<T> for ReadJsonStream<T> {
     fn from(reader: T) -> Self {
         Self::new(reader)
@@ -249,6 +290,68 @@ mod tests {
         assert!(obj.is_none());
     }
 
+    #[tokio::test]
+    async fn test_request_stream() {
+        use alloy_json_rpc::{Id, Request};
+        use futures::TryStreamExt;
+        use interprocess::local_socket::ListenerOptions;
+        use tokio::io::AsyncReadExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.ipc");
+        let name = connect::to_name(path.as_os_str()).unwrap();
+        let listener = ListenerOptions::new().name(name).create_tokio().unwrap();
+        tokio::spawn(async move {
+            let mut conn = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = conn.read(&mut buf).await.unwrap();
+            for chunk in
+                [&br#"{"jsonrpc":"2.0","id":0,"result":[{"a":"#[..], b"1},{\"a\"", b":2}]}"]
+            {
+                conn.write_all(chunk).await.unwrap();
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+            // Keep the connection open.
+            std::future::pending::<()>().await;
+        });
+
+        let req = Request::new("eth_getLogs", Id::Number(0), ()).serialize().unwrap();
+        let items: Vec<serde_json::Value> =
+            IpcConnect::new(path).request_stream(req).await.unwrap().try_collect().await.unwrap();
+        assert_eq!(items, [serde_json::json!({"a": 1}), serde_json::json!({"a": 2})]);
+    }
+
+    #[tokio::test]
+    async fn test_frontend_request_stream() {
+        use alloy_json_rpc::{Id, Request};
+        use alloy_pubsub::PubSubConnect;
+        use futures::TryStreamExt;
+        use interprocess::local_socket::ListenerOptions;
+        use tokio::io::AsyncReadExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.ipc");
+        let name = connect::to_name(path.as_os_str()).unwrap();
+        let listener = ListenerOptions::new().name(name).create_tokio().unwrap();
+        tokio::spawn(async move {
+            // The first connection backs the pubsub service, the second one the request.
+            let _service = listener.accept().await.unwrap();
+            let mut conn = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = conn.read(&mut buf).await.unwrap();
+            conn.write_all(br#"{"jsonrpc":"2.0","id":0,"result":[1,2,3]}"#).await.unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        let frontend = IpcConnect::new(path).into_service().await.unwrap();
+        assert!(frontend.supports_request_stream());
+
+        let req = Request::new("eth_getLogs", Id::Number(0), ()).serialize().unwrap();
+        let items: Vec<u64> =
+            frontend.request_stream::<u64>(req).await.unwrap().try_collect().await.unwrap();
+        assert_eq!(items, [1, 2, 3]);
+    }
+
     #[tokio::test]
     async fn test_large_valid() {
         let header = b"{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":\"0x";
@@ -14,7 +14,7 @@ use alloy_pubsub::ConnectionInterface;
 #[cfg(not(target_arch = "wasm32"))]
 mod native;
 #[cfg(not(target_arch = "wasm32"))]
-pub use native::{WebSocketConfig, WsConnect};
+pub use native::{HeaderMap, HeaderName, HeaderValue, WebSocketConfig, WsConnect};
 
 #[cfg(not(target_arch = "wasm32"))]
 use rustls as _;
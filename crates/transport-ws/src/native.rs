@@ -1,7 +1,8 @@
 use crate::WsBackend;
-use alloy_pubsub::PubSubConnect;
+use alloy_pubsub::{PubSubConnect, ReconnectPolicy};
 use alloy_transport::{utils::Spawnable, Authorization, TransportErrorKind, TransportResult};
 use futures::{SinkExt, StreamExt};
+pub use http::{HeaderMap, HeaderName, HeaderValue};
 use serde_json::value::RawValue;
 use std::time::Duration;
 pub use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
@@ -18,7 +19,11 @@ use tokio::time::sleep;
 
 type TungsteniteStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
 
-const KEEPALIVE: u64 = 10;
+/// The default interval after which a ping is sent, if no other message was.
+const KEEPALIVE: Duration = Duration::from_secs(10);
+
+/// The minimum interval after which a ping is sent, to which shorter intervals are raised.
+const MIN_KEEPALIVE: Duration = Duration::from_millis(100);
 
 /// Simple connection details for a websocket connection.
 #[derive(Clone, Debug)]
@@ -27,14 +32,32 @@ pub struct WsConnect {
     pub url: String,
     /// The authorization header to use.
     pub auth: Option<Authorization>,
+    /// Additional headers to send with the upgrade request.
+    pub headers: HeaderMap,
     /// The websocket config.
     pub config: Option<WebSocketConfig>,
+    /// The interval after which a ping is sent, if no other message was.
+    /// Intervals shorter than 100 milliseconds are raised to it.
+    pub keepalive_interval: Duration,
+    /// The time to wait for a message from the server after sending a ping,
+    /// after which the connection is considered dead and is reconnected.
+    pub pong_timeout: Option<Duration>,
+    /// The policy followed to reconnect.
+    pub reconnect_policy: ReconnectPolicy,
 }
 
 impl WsConnect {
     /// Creates a new websocket connection configuration.
     pub fn new<S: Into<String>>(url: S) -> Self {
-        Self { url: url.into(), auth: None, config: None }
+        Self {
+            url: url.into(),
+            auth: None,
+            headers: HeaderMap::new(),
+            config: None,
+            keepalive_interval: KEEPALIVE,
+            pong_timeout: None,
+            reconnect_policy: ReconnectPolicy::new(),
+        }
     }
 
     /// Sets the authorization header.
@@ -43,11 +66,59 @@ impl WsConnect {
         self
     }
 
+    /// Adds a header to send with the upgrade request, e.g. an API key.
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets the additional headers to send with the upgrade request.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
     /// Sets the websocket config.
     pub const fn with_config(mut self, config: WebSocketConfig) -> Self {
         self.config = Some(config);
         self
     }
+
+    /// Sets the maximum size of an incoming message, `None` for no limit.
+    /// Defaults to 64 MiB.
+    pub fn with_max_message_size(mut self, max_message_size: Option<usize>) -> Self {
+        self.config.get_or_insert_with(Default::default).max_message_size = max_message_size;
+        self
+    }
+
+    /// Sets the maximum size of a single incoming frame, `None` for no limit.
+    /// Defaults to 16 MiB.
+    pub fn with_max_frame_size(mut self, max_frame_size: Option<usize>) -> Self {
+        self.config.get_or_insert_with(Default::default).max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Sets the interval after which a ping is sent, if no other message was.
+    /// Defaults to 10 seconds. Intervals shorter than 100 milliseconds,
+    /// including zero, are raised to it.
+    pub const fn with_keepalive_interval(mut self, keepalive_interval: Duration) -> Self {
+        self.keepalive_interval = keepalive_interval;
+        self
+    }
+
+    /// Sets the time to wait for a message from the server after sending a
+    /// ping. If none is received in time, the connection is considered dead,
+    /// and is reconnected. Disabled by default.
+    pub const fn with_pong_timeout(mut self, pong_timeout: Duration) -> Self {
+        self.pong_timeout = Some(pong_timeout);
+        self
+    }
+
+    /// Sets the policy followed to reconnect.
+    pub fn with_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
+    }
 }
 
 impl IntoClientRequest for WsConnect {
@@ -59,6 +130,7 @@ impl IntoClientRequest for WsConnect {
 
             request.headers_mut().insert(http::header::AUTHORIZATION, auth_value);
         }
+        request.headers_mut().extend(self.headers);
 
         request.into_client_request()
     }
@@ -69,6 +141,10 @@ impl PubSubConnect for WsConnect {
         alloy_transport::utils::guess_local_url(&self.url)
     }
 
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.reconnect_policy.clone()
+    }
+
     async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
         let request = self.clone().into_client_request();
         let req = request.map_err(TransportErrorKind::custom)?;
@@ -79,7 +155,7 @@ impl PubSubConnect for WsConnect {
         let (handle, interface) = alloy_pubsub::ConnectionHandle::new();
         let backend = WsBackend { socket, interface };
 
-        backend.spawn();
+        backend.spawn_with_keepalive(self.keepalive_interval, self.pong_timeout);
 
         Ok(handle)
     }
@@ -113,18 +189,37 @@ impl WsBackend<TungsteniteStream> {
     }
 
     /// Spawn a new backend task.
-    pub fn spawn(mut self) {
+    pub fn spawn(self) {
+        self.spawn_with_keepalive(KEEPALIVE, None)
+    }
+
+    /// Spawn a new backend task, sending a ping after `keepalive_interval` without
+    /// other messages sent, and failing if no message is received within
+    /// `pong_timeout` after a ping. Keepalive intervals shorter than 100
+    /// milliseconds are raised to it.
+    pub fn spawn_with_keepalive(
+        mut self,
+        keepalive_interval: Duration,
+        pong_timeout: Option<Duration>,
+    ) {
+        let keepalive_interval = keepalive_interval.max(MIN_KEEPALIVE);
         let fut = async move {
             let mut errored = false;
-            let keepalive = sleep(Duration::from_secs(KEEPALIVE));
+            let keepalive = sleep(keepalive_interval);
             tokio::pin!(keepalive);
+            // The deadline for a message from the server, armed when a ping is
+            // sent.
+            let pong = sleep(Duration::ZERO);
+            tokio::pin!(pong);
+            let mut awaiting_pong = false;
             loop {
                 // We bias the loop as follows
                 // 1. New dispatch to server.
                 // 2. Keepalive.
-                // 3. Response or notification from server.
+                // 3. Pong timeout.
+                // 4. Response or notification from server.
                 // This ensures that keepalive is sent only if no other messages
-                // have been sent in the last 10 seconds. And prioritizes new
+                // have been sent during the keepalive interval. And prioritizes new
                 // dispatches over responses from the server. This will fail if
                 // the client saturates the task with dispatches, but that's
                 // probably not a big deal.
@@ -137,7 +232,7 @@ impl WsBackend<TungsteniteStream> {
                         match inst {
                             Some(msg) => {
                                 // Reset the keepalive timer.
-                                keepalive.set(sleep(Duration::from_secs(KEEPALIVE)));
+                                keepalive.set(sleep(keepalive_interval));
                                 if let Err(err) = self.send(msg).await {
                                     error!(%err, "WS connection error");
                                     errored = true;
@@ -151,19 +246,31 @@ impl WsBackend<TungsteniteStream> {
                         }
                     },
                     // Send a ping to the server, if no other messages have been
-                    // sent in the last 10 seconds.
+                    // sent during the keepalive interval.
                     _ = &mut keepalive => {
                         // Reset the keepalive timer.
-                        keepalive.set(sleep(Duration::from_secs(KEEPALIVE)));
+                        keepalive.set(sleep(keepalive_interval));
                         if let Err(err) = self.socket.send(Message::Ping(vec![])).await {
                             error!(%err, "WS connection error");
                             errored = true;
                             break
                         }
+                        if let (Some(timeout), false) = (pong_timeout, awaiting_pong) {
+                            pong.set(sleep(timeout));
+                            awaiting_pong = true;
+                        }
+                    }
+                    // The server did not answer the ping in time.
+                    _ = &mut pong, if awaiting_pong => {
+                        error!("WS server did not respond to ping");
+                        errored = true;
+                        break
                     }
                     resp = self.socket.next() => {
                         match resp {
                             Some(Ok(item)) => {
+                                // Any message shows that the connection is alive.
+                                awaiting_pong = false;
                                 errored = self.handle(item).is_err();
                                 if errored { break }
                             },
@@ -188,3 +295,55 @@ impl WsBackend<TungsteniteStream> {
         fut.spawn_task()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_request_headers() {
+        let connect = WsConnect::new("ws://localhost:8546")
+            .with_auth(Authorization::bearer("token"))
+            .with_header(HeaderName::from_static("x-api-key"), HeaderValue::from_static("key"))
+            .with_max_message_size(None);
+        assert_eq!(connect.config.unwrap().max_message_size, None);
+        assert_eq!(
+            connect.config.unwrap().max_frame_size,
+            WebSocketConfig::default().max_frame_size
+        );
+
+        let request = connect.into_client_request().unwrap();
+        assert_eq!(request.headers()["x-api-key"], "key");
+        assert_eq!(request.headers()[http::header::AUTHORIZATION], "Bearer token");
+        assert!(request.headers().contains_key(http::header::SEC_WEBSOCKET_KEY));
+    }
+
+    #[tokio::test]
+    async fn pong_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            // Never read from the socket, so that pings are not answered.
+            let _socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        // The zero interval is raised to the minimum.
+        let connect = WsConnect::new(url)
+            .with_keepalive_interval(Duration::ZERO)
+            .with_pong_timeout(Duration::from_millis(500));
+        let start = tokio::time::Instant::now();
+        let handle = connect.connect().await.unwrap();
+
+        // The first ping is sent after the keepalive interval, and the backend
+        // fails once the pong timeout elapsed without a message.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(handle.is_connected());
+        while handle.is_connected() {
+            assert!(start.elapsed() < Duration::from_secs(5), "backend did not time out");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(start.elapsed() >= MIN_KEEPALIVE + Duration::from_millis(500));
+    }
+}
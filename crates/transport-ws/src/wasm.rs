@@ -1,5 +1,5 @@
 use super::WsBackend;
-use alloy_pubsub::PubSubConnect;
+use alloy_pubsub::{PubSubConnect, ReconnectPolicy};
 use alloy_transport::{utils::Spawnable, TransportErrorKind, TransportResult};
 use futures::{
     sink::SinkExt,
@@ -13,12 +13,20 @@ use ws_stream_wasm::{WsErr, WsMessage, WsMeta, WsStream};
 pub struct WsConnect {
     /// The URL to connect to.
     pub url: String,
+    /// The policy followed to reconnect.
+    pub reconnect_policy: ReconnectPolicy,
 }
 
 impl WsConnect {
     /// Creates a new websocket connection configuration.
     pub fn new<S: Into<String>>(url: S) -> Self {
-        Self { url: url.into() }
+        Self { url: url.into(), reconnect_policy: ReconnectPolicy::new() }
+    }
+
+    /// Sets the policy followed to reconnect.
+    pub fn with_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
     }
 }
 
@@ -27,6 +35,10 @@ impl PubSubConnect for WsConnect {
         alloy_transport::utils::guess_local_url(&self.url)
     }
 
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.reconnect_policy.clone()
+    }
+
     async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
         let socket =
             WsMeta::connect(&self.url, None).await.map_err(TransportErrorKind::custom)?.1.fuse();
@@ -0,0 +1,330 @@
+use crate::{utils::Spawnable, TransportError, TransportErrorKind, TransportFut};
+use alloy_json_rpc::{Id, RequestPacket, Response, ResponsePacket, RpcError, SerializedRequest};
+use futures::channel::oneshot;
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::sleep;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+
+/// The default time to wait for more requests before dispatching a batch.
+const DEFAULT_WAIT: Duration = Duration::from_millis(1);
+
+/// The default maximum number of requests in a batch.
+const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+type Waiter = oneshot::Sender<Result<Response, TransportError>>;
+
+/// A Transport Layer that is responsible for coalescing concurrent requests into batches.
+///
+/// Single requests issued within `wait` of each other are collected, and sent as one
+/// [`RequestPacket::Batch`] once the delay elapsed or `max_batch_size` requests were collected.
+/// Each response is then routed back to its caller by [`Id`].
+///
+/// Requests which are already batched, subscription requests, and requests carrying
+/// [extensions], such as the HTTP headers of signed Flashbots requests, are sent as is.
+///
+/// Batches are dispatched from spawned tasks, so the layer must be used within a runtime. The
+/// requests of a batch must have distinct IDs, which is the case for the requests of a single
+/// `RpcClient`.
+///
+/// [extensions]: alloy_json_rpc::RequestMeta::extensions
+#[derive(Debug, Clone, Copy)]
+pub struct BatchLayer {
+    wait: Duration,
+    max_batch_size: usize,
+}
+
+impl Default for BatchLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchLayer {
+    /// Creates a new batch layer, waiting 1ms for up to 100 requests.
+    pub const fn new() -> Self {
+        Self { wait: DEFAULT_WAIT, max_batch_size: DEFAULT_MAX_BATCH_SIZE }
+    }
+
+    /// Sets how long to wait for more requests before dispatching a batch.
+    pub const fn with_wait(mut self, wait: Duration) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Sets the maximum number of requests in a batch.
+    pub const fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+}
+
+impl<S> Layer<S> for BatchLayer {
+    type Service = BatchService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BatchService {
+            inner,
+            wait: self.wait,
+            max_batch_size: self.max_batch_size.max(1),
+            pending: Default::default(),
+        }
+    }
+}
+
+/// The requests collected for the next batch.
+#[derive(Debug, Default)]
+struct Pending {
+    requests: Vec<(SerializedRequest, Waiter)>,
+    /// Incremented every time the pending requests are taken, so that a delayed dispatch does not
+    /// take the requests of a later batch.
+    generation: u64,
+}
+
+impl Pending {
+    fn take(&mut self) -> Vec<(SerializedRequest, Waiter)> {
+        self.generation += 1;
+        mem::take(&mut self.requests)
+    }
+}
+
+/// A Tower Service used by the [BatchLayer] that is responsible for coalescing concurrent
+/// requests into batches.
+#[derive(Debug, Clone)]
+pub struct BatchService<S> {
+    /// The inner service
+    inner: S,
+    wait: Duration,
+    max_batch_size: usize,
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl<S> BatchService<S>
+where
+    S: Service<RequestPacket, Future = TransportFut<'static>, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+{
+    /// Spawns a task sending the requests, and routing the responses to their waiters.
+    fn dispatch(&self, requests: Vec<(SerializedRequest, Waiter)>) {
+        let mut inner = self.inner.clone();
+        async move {
+            let (requests, waiters): (Vec<_>, HashMap<_, _>) = requests
+                .into_iter()
+                .map(|(req, waiter)| {
+                    let id = req.id().clone();
+                    (req, (id, waiter))
+                })
+                .unzip();
+            trace!(len = requests.len(), "dispatching batch");
+
+            let packet = if requests.len() == 1 {
+                RequestPacket::Single(requests.into_iter().next().unwrap())
+            } else {
+                RequestPacket::Batch(requests)
+            };
+            route(inner.call(packet).await, waiters);
+        }
+        .spawn_task();
+    }
+}
+
+/// Sends each response to the waiter of its request, failing the requests left without one.
+fn route(res: Result<ResponsePacket, TransportError>, mut waiters: HashMap<Id, Waiter>) {
+    let responses = match res {
+        Ok(ResponsePacket::Single(response)) => vec![response],
+        Ok(ResponsePacket::Batch(responses)) => responses,
+        Err(err) => {
+            for (_, waiter) in waiters {
+                let _ = waiter.send(Err(clone_error(&err)));
+            }
+            return;
+        }
+    };
+
+    for response in responses {
+        match waiters.remove(&response.id) {
+            Some(waiter) => {
+                let _ = waiter.send(Ok(response));
+            }
+            // An error without a matching ID, e.g. if the server does not support batches, fails
+            // the whole batch.
+            None => match response.payload.as_error() {
+                Some(err) => {
+                    for (_, waiter) in waiters.drain() {
+                        let _ = waiter.send(Err(RpcError::ErrorResp(err.clone())));
+                    }
+                }
+                None => trace!(id = %response.id, "received response for unknown request"),
+            },
+        }
+    }
+
+    for (id, waiter) in waiters {
+        let _ = waiter.send(Err(TransportErrorKind::missing_batch_response(id)));
+    }
+}
+
+/// Copies an error for each request of a failed batch, preserving its kind where possible.
+pub(super) fn clone_error(err: &TransportError) -> TransportError {
+    match err {
+        RpcError::ErrorResp(payload) => RpcError::ErrorResp(payload.clone()),
+        RpcError::NullResp => RpcError::NullResp,
+        RpcError::Transport(TransportErrorKind::HttpError(err)) => {
+            TransportErrorKind::http_error(err.status, err.body.clone())
+        }
+        RpcError::Transport(TransportErrorKind::BackendGone) => TransportErrorKind::backend_gone(),
+        RpcError::Transport(TransportErrorKind::PubsubUnavailable) => {
+            TransportErrorKind::pubsub_unavailable()
+        }
+        err => TransportErrorKind::custom_str(&err.to_string()),
+    }
+}
+
+impl<S> Service<RequestPacket> for BatchService<S>
+where
+    S: Service<RequestPacket, Future = TransportFut<'static>, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let request = match request {
+            RequestPacket::Single(req)
+                if !req.is_subscription() && req.meta().extensions().is_empty() =>
+            {
+                req
+            }
+            request => return self.inner.call(request),
+        };
+
+        let (tx, rx) = oneshot::channel();
+        let mut pending = self.pending.lock().unwrap();
+
+        // Requests of a batch must have distinct IDs to be routed back.
+        if pending.requests.iter().any(|(req, _)| req.id() == request.id()) {
+            self.dispatch(pending.take());
+        }
+
+        pending.requests.push((request, tx));
+        if pending.requests.len() >= self.max_batch_size {
+            self.dispatch(pending.take());
+        } else if pending.requests.len() == 1 {
+            // First request of the batch, dispatch it once the wait is over, unless it was
+            // already dispatched by then.
+            let this = self.clone();
+            let generation = pending.generation;
+            async move {
+                sleep(this.wait).await;
+                let mut pending = this.pending.lock().unwrap();
+                if pending.generation == generation {
+                    this.dispatch(pending.take());
+                }
+            }
+            .spawn_task();
+        }
+        drop(pending);
+
+        Box::pin(async move {
+            let response = rx.await.map_err(|_| TransportErrorKind::backend_gone())??;
+            Ok(ResponsePacket::Single(response))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockTransport};
+    use alloy_json_rpc::Request;
+    use futures::future::join_all;
+
+    /// A transport answering each request with its method, and answering batches out of order.
+    fn mock_transport() -> MockTransport {
+        MockTransport::new(|req| MockResponse::success(req.method())).with_reversed_batches()
+    }
+
+    fn request(method: &'static str, id: u64) -> RequestPacket {
+        Request::new(method, Id::Number(id), ()).serialize().unwrap().into()
+    }
+
+    fn result(res: ResponsePacket) -> String {
+        let ResponsePacket::Single(res) = res else { unreachable!() };
+        res.try_success_as::<String>().unwrap().unwrap()
+    }
+
+    fn packet_sizes(transport: &MockTransport) -> Vec<usize> {
+        transport.packets().iter().map(|packet| packet.len()).collect()
+    }
+
+    #[tokio::test]
+    async fn coalesces_concurrent_requests() {
+        let transport = mock_transport();
+        let mut service = BatchLayer::new().with_max_batch_size(3).layer(transport.clone());
+
+        let calls: Vec<_> = (0..5)
+            .map(|id| service.call(request(["a", "b", "c", "d", "e"][id], id as u64)))
+            .collect();
+        let results: Vec<_> =
+            join_all(calls).await.into_iter().map(|res| result(res.unwrap())).collect();
+
+        assert_eq!(results, ["a", "b", "c", "d", "e"]);
+        assert_eq!(packet_sizes(&transport), [3, 2]);
+    }
+
+    #[tokio::test]
+    async fn sends_lone_requests_as_is() {
+        let transport = mock_transport();
+        let mut service = BatchLayer::new().layer(transport.clone());
+
+        assert_eq!(result(service.call(request("a", 0)).await.unwrap()), "a");
+
+        // Duplicate IDs are split across batches.
+        let calls = [service.call(request("b", 1)), service.call(request("c", 1))];
+        let results: Vec<_> =
+            join_all(calls).await.into_iter().map(|res| result(res.unwrap())).collect();
+        assert_eq!(results, ["b", "c"]);
+
+        let packets = transport.packets();
+        assert!(packets.iter().all(|packet| matches!(packet, RequestPacket::Single(_))));
+        assert_eq!(packets.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn fails_missing_responses() {
+        let transport = MockTransport::new(|req| match req.method() {
+            "b" => MockResponse::Missing,
+            method => MockResponse::success(method),
+        })
+        .with_reversed_batches();
+        let mut service = BatchLayer::new().layer(transport.clone());
+
+        let calls = [service.call(request("a", 0)), service.call(request("b", 1))];
+        let [a, b] = <[_; 2]>::try_from(join_all(calls).await).unwrap();
+        assert_eq!(result(a.unwrap()), "a");
+        assert!(matches!(
+            b.unwrap_err(),
+            RpcError::Transport(TransportErrorKind::MissingBatchResponse(Id::Number(1)))
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    layers::batch::clone_error, utils::Spawnable, TransportError, TransportErrorKind, TransportFut,
+};
+use alloy_json_rpc::{Id, RequestPacket, Response, ResponsePacket, SerializedRequest};
+use alloy_primitives::B256;
+use futures::channel::oneshot;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+type Waiter = (Id, oneshot::Sender<Result<Response, TransportError>>);
+
+/// The waiters of the in-flight requests, by method and params hash.
+type InFlight = HashMap<(String, B256), Vec<Waiter>>;
+
+/// The read-only methods deduplicated by default.
+const DEFAULT_METHODS: &[&str] = &[
+    "eth_blockNumber",
+    "eth_chainId",
+    "eth_gasPrice",
+    "eth_maxPriorityFeePerGas",
+    "eth_blobBaseFee",
+    "eth_feeHistory",
+    "eth_call",
+    "eth_estimateGas",
+    "eth_getBalance",
+    "eth_getCode",
+    "eth_getStorageAt",
+    "eth_getProof",
+    "eth_getTransactionCount",
+    "eth_getBlockByNumber",
+    "eth_getBlockByHash",
+    "eth_getBlockReceipts",
+    "eth_getTransactionByHash",
+    "eth_getTransactionReceipt",
+    "eth_getLogs",
+    "net_version",
+];
+
+/// A Transport Layer that is responsible for deduplicating identical in-flight requests.
+///
+/// Single requests are identified by method and [params hash]. While a request is in flight,
+/// identical requests wait for its response instead of being sent, and receive a copy of it with
+/// their own ID.
+///
+/// Only the configured methods are deduplicated, which default to common read-only methods.
+/// Methods changing or creating state, such as `eth_sendTransaction` or `eth_newFilter`, must not
+/// be added, as each call is expected to take effect. Batches, subscription requests, and
+/// requests carrying [extensions], such as HTTP headers, are sent as is.
+///
+/// Requests are sent from spawned tasks, so that the response reaches every waiter even if the
+/// first caller is dropped, and the layer must be used within a runtime.
+///
+/// [params hash]: SerializedRequest::params_hash
+/// [extensions]: alloy_json_rpc::RequestMeta::extensions
+#[derive(Debug, Clone)]
+pub struct DedupLayer {
+    /// The deduplicated methods.
+    methods: Arc<HashSet<Cow<'static, str>>>,
+}
+
+impl Default for DedupLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DedupLayer {
+    /// Creates a new dedup layer, deduplicating common read-only methods.
+    pub fn new() -> Self {
+        Self::with_methods(DEFAULT_METHODS.iter().copied())
+    }
+
+    /// Creates a new dedup layer, deduplicating only the given methods.
+    pub fn with_methods<I, M>(methods: I) -> Self
+    where
+        I: IntoIterator<Item = M>,
+        M: Into<Cow<'static, str>>,
+    {
+        Self { methods: Arc::new(methods.into_iter().map(Into::into).collect()) }
+    }
+
+    /// Also deduplicates the given method.
+    pub fn with_method(mut self, method: impl Into<Cow<'static, str>>) -> Self {
+        Arc::make_mut(&mut self.methods).insert(method.into());
+        self
+    }
+
+    /// Returns whether requests of the given method are deduplicated.
+    pub fn is_deduplicated(&self, method: &str) -> bool {
+        self.methods.contains(method)
+    }
+}
+
+impl<S> Layer<S> for DedupLayer {
+    type Service = DedupService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DedupService { inner, methods: self.methods.clone(), in_flight: Default::default() }
+    }
+}
+
+/// A Tower Service used by the [DedupLayer] that is responsible for deduplicating identical
+/// in-flight requests.
+#[derive(Debug, Clone)]
+pub struct DedupService<S> {
+    /// The inner service
+    inner: S,
+    /// The deduplicated methods.
+    methods: Arc<HashSet<Cow<'static, str>>>,
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+impl<S> DedupService<S> {
+    /// Returns the number of distinct requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+}
+
+impl<S> DedupService<S>
+where
+    S: Service<RequestPacket, Future = TransportFut<'static>, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+{
+    /// Spawns a task sending the request, and sending its response to the waiters.
+    fn dispatch(&self, key: (String, B256), request: SerializedRequest) {
+        let mut inner = self.inner.clone();
+        let in_flight = self.in_flight.clone();
+        async move {
+            let res = match inner.call(RequestPacket::Single(request)).await {
+                Ok(ResponsePacket::Single(response)) => Ok(response),
+                Ok(ResponsePacket::Batch(_)) => {
+                    Err(TransportErrorKind::custom_str("received a batch response to a request"))
+                }
+                Err(err) => Err(err),
+            };
+
+            let waiters = in_flight.lock().unwrap().remove(&key).unwrap_or_default();
+            trace!(method = %key.0, waiters = waiters.len(), "fanning out response");
+            for (id, waiter) in waiters {
+                let res = match &res {
+                    Ok(response) => Ok(Response { id, payload: response.payload.clone() }),
+                    Err(err) => Err(clone_error(err)),
+                };
+                let _ = waiter.send(res);
+            }
+        }
+        .spawn_task();
+    }
+}
+
+impl<S> Service<RequestPacket> for DedupService<S>
+where
+    S: Service<RequestPacket, Future = TransportFut<'static>, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let request = match request {
+            RequestPacket::Single(req)
+                if self.methods.contains(req.method())
+                    && !req.is_subscription()
+                    && req.meta().extensions().is_empty() =>
+            {
+                req
+            }
+            request => return self.inner.call(request),
+        };
+
+        let key = (request.method().to_string(), request.params_hash());
+        let (tx, rx) = oneshot::channel();
+        let mut in_flight = self.in_flight.lock().unwrap();
+        match in_flight.get_mut(&key) {
+            Some(waiters) => {
+                trace!(method = %key.0, "deduplicating in-flight request");
+                waiters.push((request.id().clone(), tx));
+            }
+            None => {
+                in_flight.insert(key.clone(), vec![(request.id().clone(), tx)]);
+                self.dispatch(key, request);
+            }
+        }
+        drop(in_flight);
+
+        Box::pin(async move {
+            let response = rx.await.map_err(|_| TransportErrorKind::backend_gone())??;
+            Ok(ResponsePacket::Single(response))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockTransport};
+    use alloy_json_rpc::Request;
+    use futures::future::join_all;
+
+    /// A transport answering each request with its method after a delay.
+    fn mock_transport() -> MockTransport {
+        MockTransport::new(|req| MockResponse::success(req.method()))
+            .with_delay(std::time::Duration::from_millis(10))
+    }
+
+    fn methods(transport: &MockTransport) -> Vec<String> {
+        transport.requests().iter().map(|req| req.method().to_string()).collect()
+    }
+
+    fn layer() -> DedupLayer {
+        DedupLayer::with_methods(["a", "b"])
+    }
+
+    fn request(method: &'static str, param: u64, id: u64) -> RequestPacket {
+        Request::new(method, Id::Number(id), [param]).serialize().unwrap().into()
+    }
+
+    #[tokio::test]
+    async fn deduplicates_identical_requests() {
+        let transport = mock_transport();
+        let mut service = layer().layer(transport.clone());
+
+        let calls = [
+            service.call(request("a", 1, 0)),
+            service.call(request("a", 1, 1)),
+            service.call(request("a", 2, 2)),
+            service.call(request("b", 1, 3)),
+            service.call(request("a", 1, 4)),
+        ];
+        assert_eq!(service.in_flight(), 3);
+
+        let responses = join_all(calls).await;
+        for (i, response) in responses.into_iter().enumerate() {
+            let ResponsePacket::Single(response) = response.unwrap() else { unreachable!() };
+            assert_eq!(response.id, Id::Number(i as u64));
+        }
+        assert_eq!(methods(&transport), ["a", "a", "b"]);
+        assert_eq!(service.in_flight(), 0);
+
+        // Once the response is received, identical requests are sent again.
+        service.call(request("a", 1, 5)).await.unwrap();
+        assert_eq!(transport.calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn sends_other_methods() {
+        let transport = mock_transport();
+        let mut service = layer().layer(transport.clone());
+
+        let calls = [service.call(request("c", 1, 0)), service.call(request("c", 1, 1))];
+        assert_eq!(service.in_flight(), 0);
+        for response in join_all(calls).await {
+            response.unwrap();
+        }
+        assert_eq!(methods(&transport), ["c", "c"]);
+    }
+
+    #[test]
+    fn configures_methods() {
+        let layer = DedupLayer::new();
+        assert!(layer.is_deduplicated("eth_call"));
+        assert!(!layer.is_deduplicated("eth_sendTransaction"));
+        assert!(!layer.is_deduplicated("eth_newFilter"));
+
+        let layer = layer.with_method("eth_newFilter");
+        assert!(layer.is_deduplicated("eth_newFilter"));
+        assert!(!DedupLayer::with_methods(["a"]).is_deduplicated("eth_call"));
+    }
+}
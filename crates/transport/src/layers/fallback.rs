@@ -0,0 +1,546 @@
+use crate::{
+    error::{RpcErrorExt, TransportError, TransportErrorKind},
+    layers::RetryPolicy,
+    Transport, TransportFut,
+};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+use tracing::{debug, trace};
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// The number of most recent requests used to score a transport.
+const SAMPLE_WINDOW: usize = 50;
+
+/// The weight of the success rate in the score of a transport, the rest being the latency.
+const SUCCESS_RATE_WEIGHT: f64 = 0.7;
+
+/// A [RetryPolicy] failing over to the next transport on any transport-level error, and on
+/// retryable error responses such as rate limits.
+///
+/// Other error responses, such as execution reverts, are returned to the caller as they would be
+/// the same on any endpoint.
+#[derive(Debug, Copy, Clone, Default)]
+#[non_exhaustive]
+pub struct FailoverPolicy;
+
+impl RetryPolicy for FailoverPolicy {
+    fn should_retry(&self, error: &TransportError) -> bool {
+        match error {
+            TransportError::ErrorResp(_) => error.is_retryable(),
+            TransportError::SerError(_) => false,
+            _ => true,
+        }
+    }
+
+    fn backoff_hint(&self, error: &TransportError) -> Option<Duration> {
+        error.backoff_hint()
+    }
+}
+
+/// Quorum configuration of a [FallbackLayer].
+///
+/// In quorum mode, read requests are sent concurrently to the best ranked endpoints, and only
+/// resolve once `required` of them returned the same response. Requests which change the state of
+/// the node, such as `eth_sendRawTransaction` or filter and subscription methods, and batches are
+/// not subject to the quorum.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Quorum {
+    /// The number of endpoints which must agree on the response.
+    required: usize,
+    /// The number of endpoints the request is sent to, all of them if `None`.
+    endpoints: Option<usize>,
+}
+
+impl Quorum {
+    /// Creates a new quorum requiring `required` endpoints to agree, sending requests to all of
+    /// the endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `required` is zero.
+    pub const fn new(required: usize) -> Self {
+        assert!(required > 0, "quorum must require at least one endpoint");
+        Self { required, endpoints: None }
+    }
+
+    /// Sends requests only to the `endpoints` best ranked endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `endpoints` is less than the number of required endpoints.
+    pub const fn with_endpoints(mut self, endpoints: usize) -> Self {
+        assert!(endpoints >= self.required, "quorum requires more endpoints than it is sent to");
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Returns the number of endpoints which must agree on the response.
+    pub const fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Returns the number of endpoints the request is sent to, all of them if `None`.
+    pub const fn endpoints(&self) -> Option<usize> {
+        self.endpoints
+    }
+}
+
+/// A Transport Layer spreading requests over several transports.
+///
+/// Transports are ranked by the latency and error rate observed over their most recent requests.
+/// Each request is sent to the best ranked transport, failing over to the next one when the
+/// [RetryPolicy] allows it, and returning the last error once all transports failed. Optionally,
+/// read requests can require a [Quorum] of transports to agree on the response.
+///
+/// Unlike most layers, it wraps a [`Vec`] of transports:
+///
+/// ```no_run
+/// # fn example(transports: Vec<alloy_transport::BoxTransport>) {
+/// use alloy_transport::layers::{FallbackLayer, Quorum};
+/// use tower::Layer;
+///
+/// let transport = FallbackLayer::new().with_quorum(Quorum::new(2)).layer(transports);
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FallbackLayer<P: RetryPolicy = FailoverPolicy> {
+    /// The [RetryPolicy] deciding whether to fail over on an error.
+    policy: P,
+    /// The quorum of read requests, if any.
+    quorum: Option<Quorum>,
+}
+
+impl FallbackLayer {
+    /// Creates a new fallback layer with the default [FailoverPolicy], and no quorum.
+    pub const fn new() -> Self {
+        Self { policy: FailoverPolicy, quorum: None }
+    }
+}
+
+impl<P: RetryPolicy> FallbackLayer<P> {
+    /// Sets the [RetryPolicy] deciding whether to fail over on an error.
+    pub fn with_policy<Q: RetryPolicy>(self, policy: Q) -> FallbackLayer<Q> {
+        FallbackLayer { policy, quorum: self.quorum }
+    }
+
+    /// Requires a [Quorum] of transports to agree on the response of read requests.
+    ///
+    /// Layering fewer transports than the quorum requires panics.
+    pub const fn with_quorum(mut self, quorum: Quorum) -> Self {
+        self.quorum = Some(quorum);
+        self
+    }
+}
+
+impl<S, P> Layer<Vec<S>> for FallbackLayer<P>
+where
+    S: Transport + Clone,
+    P: RetryPolicy + Clone,
+{
+    type Service = FallbackService<S, P>;
+
+    fn layer(&self, transports: Vec<S>) -> Self::Service {
+        if let Some(quorum) = self.quorum {
+            assert!(
+                quorum.required <= transports.len(),
+                "quorum requires more endpoints than there are transports"
+            );
+        }
+        FallbackService {
+            transports: transports.into_iter().map(ScoredTransport::new).collect(),
+            policy: self.policy.clone(),
+            quorum: self.quorum,
+        }
+    }
+}
+
+/// A Tower Service used by the [FallbackLayer] that is responsible for spreading requests over
+/// several transports.
+#[derive(Debug, Clone)]
+pub struct FallbackService<S, P: RetryPolicy = FailoverPolicy> {
+    /// The transports, in their original order.
+    transports: Arc<[ScoredTransport<S>]>,
+    /// The [RetryPolicy] to use.
+    policy: P,
+    /// The quorum of read requests, if any.
+    quorum: Option<Quorum>,
+}
+
+impl<S, P: RetryPolicy> FallbackService<S, P> {
+    /// Returns the number of transports.
+    pub fn len(&self) -> usize {
+        self.transports.len()
+    }
+
+    /// Returns true if there are no transports.
+    pub fn is_empty(&self) -> bool {
+        self.transports.is_empty()
+    }
+
+    /// Returns the indices of the transports, from the best to the worst ranked.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut scores: Vec<_> =
+            self.transports.iter().map(|transport| transport.score()).enumerate().collect();
+        // Stable sort, so that transports with the same score keep their original order.
+        scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        scores.into_iter().map(|(index, _)| index).collect()
+    }
+}
+
+impl<S, P> FallbackService<S, P>
+where
+    S: Transport + Clone,
+    P: RetryPolicy + Clone + 'static,
+{
+    /// Sends the request to each transport in order of ranking, until one succeeds or the policy
+    /// does not allow failing over.
+    async fn fallback(self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let mut last_err = None;
+        for index in self.ranking() {
+            let err = match self.transports[index].call(request.clone()).await {
+                Ok(res) => {
+                    let err = res
+                        .iter_errors()
+                        .map(|err| TransportError::ErrorResp(err.clone()))
+                        .find(|err| self.policy.should_retry(err));
+                    // Error responses which would be the same on any transport are returned as is.
+                    match err {
+                        Some(err) => err,
+                        None => return Ok(res),
+                    }
+                }
+                Err(err) if self.policy.should_retry(&err) => err,
+                Err(err) => return Err(err),
+            };
+
+            debug!(%err, index, "request failed, falling back to the next transport");
+            last_err = Some(err);
+        }
+        Err(last_err.unwrap_or_else(|| TransportErrorKind::custom_str("no transports available")))
+    }
+
+    /// Sends the request concurrently to the best ranked transports, until enough of them agree on
+    /// the response.
+    async fn quorum(
+        self,
+        request: RequestPacket,
+        quorum: Quorum,
+    ) -> Result<ResponsePacket, TransportError> {
+        let ranking = self.ranking();
+        let endpoints = quorum.endpoints.unwrap_or(ranking.len()).min(ranking.len());
+        let mut pending: FuturesUnordered<_> = ranking[..endpoints]
+            .iter()
+            .map(|&index| self.transports[index].call(request.clone()))
+            .collect();
+
+        // Distinct responses received so far, with the number of transports which returned them.
+        let mut votes: Vec<(serde_json::Value, usize)> = Vec::new();
+        let mut last_err = None;
+        while let Some(res) = pending.next().await {
+            let res = match res {
+                Ok(res) => res,
+                Err(err) => {
+                    trace!(%err, "transport failed to answer quorum request");
+                    last_err = Some(err);
+                    continue;
+                }
+            };
+            if let Some(err) = res.as_error() {
+                let err = TransportError::ErrorResp(err.clone());
+                if self.policy.should_retry(&err) {
+                    last_err = Some(err);
+                    continue;
+                }
+            }
+
+            let ResponsePacket::Single(response) = &res else { return Ok(res) };
+            let value = serde_json::to_value(response).map_err(TransportError::ser_err)?;
+            let count = match votes.iter_mut().find(|(vote, _)| *vote == value) {
+                Some((_, count)) => {
+                    *count += 1;
+                    *count
+                }
+                None => {
+                    votes.push((value, 1));
+                    1
+                }
+            };
+            if count >= quorum.required {
+                return Ok(res);
+            }
+        }
+
+        let best = votes.iter().map(|(_, count)| *count).max().unwrap_or_default();
+        let mut msg = format!(
+            "quorum not reached: {best} of {} required endpoints agreed, out of {endpoints}",
+            quorum.required,
+        );
+        if let Some(err) = last_err {
+            msg = format!("{msg}, last error: {err}");
+        }
+        Err(TransportErrorKind::custom_str(&msg))
+    }
+}
+
+impl<S, P> Service<RequestPacket> for FallbackService<S, P>
+where
+    S: Transport + Clone,
+    P: RetryPolicy + Clone + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Transports are cloned for each request, and are always ready.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            match this.quorum {
+                Some(quorum) if is_quorum_request(&request) => this.quorum(request, quorum).await,
+                _ => this.fallback(request).await,
+            }
+        })
+    }
+}
+
+/// Returns true if the request is a single read request, which can be subject to a quorum.
+fn is_quorum_request(request: &RequestPacket) -> bool {
+    const STATEFUL_PREFIXES: &[&str] = &[
+        "eth_send",
+        "eth_sign",
+        "eth_cancel",
+        "eth_subscribe",
+        "eth_unsubscribe",
+        "eth_getFilter",
+        "personal_",
+        "wallet_",
+        "mev_",
+        "admin_",
+        "miner_",
+        "anvil_",
+        "evm_",
+        "hardhat_",
+    ];
+
+    let RequestPacket::Single(request) = request else { return false };
+    let method = request.method();
+    !method.ends_with("Filter")
+        && !STATEFUL_PREFIXES.iter().any(|prefix| method.starts_with(prefix))
+}
+
+/// A transport along with the statistics of its most recent requests.
+#[derive(Debug)]
+struct ScoredTransport<S> {
+    transport: S,
+    samples: Arc<Mutex<VecDeque<Sample>>>,
+}
+
+/// The outcome of a request.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    latency: Duration,
+    success: bool,
+}
+
+impl<S> ScoredTransport<S> {
+    fn new(transport: S) -> Self {
+        Self { transport, samples: Default::default() }
+    }
+
+    /// Returns the score of the transport, between 0 and 1, higher is better.
+    ///
+    /// Transports without samples get the best score, so that they are tried first.
+    fn score(&self) -> f64 {
+        let samples = self.samples.lock().unwrap();
+        if samples.is_empty() {
+            return 1.0;
+        }
+
+        let successes = samples.iter().filter(|sample| sample.success).count();
+        let success_rate = successes as f64 / samples.len() as f64;
+        let latency = samples.iter().map(|sample| sample.latency).sum::<Duration>().as_secs_f64()
+            / samples.len() as f64;
+        success_rate * SUCCESS_RATE_WEIGHT + (1.0 - SUCCESS_RATE_WEIGHT) / (1.0 + latency)
+    }
+
+    fn record(&self, sample: Sample) {
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() == SAMPLE_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+}
+
+impl<S: Transport + Clone> ScoredTransport<S> {
+    /// Sends the request, recording its latency and outcome.
+    ///
+    /// Error responses count as successes, as the transport itself worked, unless they are
+    /// retryable such as rate limits. Requests dropped before completing, such as the slowest ones
+    /// of a quorum, count as timeouts.
+    async fn call(&self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let mut transport = self.transport.clone();
+        let mut sample = PendingSample { transport: self, start: Instant::now(), success: false };
+        let res = transport.call(request).await;
+        sample.success =
+            res.as_ref().is_ok_and(|res| !res.iter_errors().any(|err| err.is_retry_err()));
+        res
+    }
+}
+
+/// A request in flight, recording its sample once completed or dropped.
+struct PendingSample<'a, S> {
+    transport: &'a ScoredTransport<S>,
+    start: Instant,
+    success: bool,
+}
+
+impl<S> Drop for PendingSample<'_, S> {
+    fn drop(&mut self) {
+        self.transport.record(Sample { latency: self.start.elapsed(), success: self.success });
+    }
+}
+
+impl<S: Clone> Clone for ScoredTransport<S> {
+    fn clone(&self) -> Self {
+        Self { transport: self.transport.clone(), samples: self.samples.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_json_rpc::{Id, Request};
+
+    fn request(method: &'static str) -> RequestPacket {
+        Request::new(method, Id::Number(1), ()).serialize().unwrap().into()
+    }
+
+    fn result(res: ResponsePacket) -> String {
+        let ResponsePacket::Single(res) = res else { unreachable!() };
+        res.try_success_as::<serde_json::Value>().unwrap().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn fails_over_and_ranks_transports() {
+        let down = MockTransport::with_error("connection refused");
+        let up = MockTransport::with_result("0x1");
+        let mut service = FallbackLayer::new().layer(vec![down.clone(), up.clone()]);
+
+        let res = service.call(request("eth_chainId")).await.unwrap();
+        assert_eq!(result(res), "\"0x1\"");
+        assert_eq!((down.calls(), up.calls()), (1, 1));
+
+        // The failing transport is now ranked last, and no longer called first.
+        assert_eq!(service.ranking(), [1, 0]);
+        service.call(request("eth_chainId")).await.unwrap();
+        assert_eq!((down.calls(), up.calls()), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn ranks_by_latency() {
+        let slow = MockTransport::with_result(&1).with_delay(Duration::from_millis(200));
+        let fast = MockTransport::with_result(&1);
+        let service = FallbackLayer::new().with_quorum(Quorum::new(2)).layer(vec![slow, fast]);
+
+        service.clone().call(request("eth_blockNumber")).await.unwrap();
+        assert_eq!(service.ranking(), [1, 0]);
+    }
+
+    #[tokio::test]
+    async fn returns_last_error() {
+        let mut service = FallbackLayer::new()
+            .layer(vec![MockTransport::with_error("first"), MockTransport::with_error("second")]);
+
+        let err = service.call(request("eth_chainId")).await.unwrap_err();
+        assert_eq!(err.to_string(), "second");
+    }
+
+    #[tokio::test]
+    async fn quorum() {
+        let transports = vec![
+            MockTransport::with_result("0x2"),
+            MockTransport::with_error("connection refused"),
+            MockTransport::with_result("0x1").with_delay(Duration::from_millis(10)),
+            MockTransport::with_result("0x1").with_delay(Duration::from_millis(20)),
+        ];
+        let mut service = FallbackLayer::new().with_quorum(Quorum::new(2)).layer(transports);
+
+        let res = service.call(request("eth_getBalance")).await.unwrap();
+        assert_eq!(result(res), "\"0x1\"");
+
+        let err = service.clone().with_quorum(Quorum::new(3)).call(request("eth_getBalance")).await;
+        assert!(err.unwrap_err().to_string().starts_with("quorum not reached: 2 of 3"));
+
+        // Quorum is not applied to requests changing the state of the node.
+        let res = service.call(request("eth_sendRawTransaction")).await.unwrap();
+        assert_eq!(result(res), "\"0x2\"");
+    }
+
+    #[tokio::test]
+    async fn records_dropped_requests() {
+        let slow = MockTransport::with_result(&1).with_delay(Duration::from_millis(200));
+        let transports =
+            vec![slow.clone(), MockTransport::with_result(&1), MockTransport::with_result(&1)];
+        let service = FallbackLayer::new().with_quorum(Quorum::new(2)).layer(transports);
+
+        // The slowest transport is dropped once the quorum is reached, which counts as a timeout.
+        service.clone().call(request("eth_blockNumber")).await.unwrap();
+        assert_eq!(slow.calls(), 1);
+        assert_eq!(service.ranking().last(), Some(&0));
+    }
+
+    #[test]
+    #[should_panic = "quorum must require at least one endpoint"]
+    fn rejects_empty_quorum() {
+        let _ = Quorum::new(std::hint::black_box(0));
+    }
+
+    #[test]
+    #[should_panic = "quorum requires more endpoints than it is sent to"]
+    fn rejects_quorum_over_endpoints() {
+        let _ = Quorum::new(2).with_endpoints(std::hint::black_box(1));
+    }
+
+    #[test]
+    #[should_panic = "quorum requires more endpoints than there are transports"]
+    fn rejects_quorum_over_transports() {
+        let _ = FallbackLayer::new()
+            .with_quorum(Quorum::new(2))
+            .layer(vec![MockTransport::with_result(&1)]);
+    }
+
+    #[test]
+    fn quorum_requests() {
+        assert!(is_quorum_request(&request("eth_call")));
+        assert!(is_quorum_request(&request("eth_getLogs")));
+        assert!(!is_quorum_request(&request("eth_sendBundle")));
+        assert!(!is_quorum_request(&request("eth_newBlockFilter")));
+        assert!(!is_quorum_request(&request("eth_getFilterChanges")));
+        assert!(!is_quorum_request(&request("eth_subscribe")));
+    }
+
+    impl<S, P: RetryPolicy> FallbackService<S, P> {
+        const fn with_quorum(mut self, quorum: Quorum) -> Self {
+            self.quorum = Some(quorum);
+            self
+        }
+    }
+}
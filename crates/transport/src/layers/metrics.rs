@@ -0,0 +1,413 @@
+use crate::{TransportError, TransportFut};
+use alloy_json_rpc::{Id, RequestPacket, Response, ResponsePacket, RpcError};
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+use tracing::{field, info_span, Instrument, Span};
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// The outcome of a JSON-RPC request, as recorded by a [`MetricsSink`].
+///
+/// Errors are classified by [`RpcError`] variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestOutcome {
+    /// The server returned a successful response.
+    Success,
+    /// The server returned an error response.
+    ErrorResp,
+    /// The server returned a null response.
+    NullResp,
+    /// The request used an unsupported feature.
+    UnsupportedFeature,
+    /// The request was misused locally.
+    LocalUsageError,
+    /// The request could not be serialized.
+    SerError,
+    /// The response could not be deserialized.
+    DeserError,
+    /// The transport failed, or the response to the request was missing from a batch.
+    Transport,
+}
+
+impl RequestOutcome {
+    /// Classifies an error by its variant.
+    pub const fn from_error<E>(err: &RpcError<E>) -> Self {
+        match err {
+            RpcError::ErrorResp(_) => Self::ErrorResp,
+            RpcError::NullResp => Self::NullResp,
+            RpcError::UnsupportedFeature(_) => Self::UnsupportedFeature,
+            RpcError::LocalUsageError(_) => Self::LocalUsageError,
+            RpcError::SerError(_) => Self::SerError,
+            RpcError::DeserError { .. } => Self::DeserError,
+            RpcError::Transport(_) => Self::Transport,
+        }
+    }
+
+    /// Returns `true` if the request succeeded.
+    pub const fn is_success(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+
+    /// Returns the outcome as a `snake_case` label.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::ErrorResp => "error_resp",
+            Self::NullResp => "null_resp",
+            Self::UnsupportedFeature => "unsupported_feature",
+            Self::LocalUsageError => "local_usage_error",
+            Self::SerError => "ser_error",
+            Self::DeserError => "deser_error",
+            Self::Transport => "transport",
+        }
+    }
+}
+
+impl fmt::Display for RequestOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The metrics of a single JSON-RPC request.
+///
+/// The requests of a batch are recorded separately, with the latency of the whole batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestMetrics<'a> {
+    /// The method of the request.
+    pub method: &'a str,
+    /// The endpoint the request was sent to, if configured on the layer.
+    pub endpoint: Option<&'a str>,
+    /// The outcome of the request.
+    pub outcome: RequestOutcome,
+    /// The time between sending the request and receiving its response.
+    pub latency: Duration,
+    /// The size of the serialized request, in bytes.
+    pub request_size: usize,
+    /// The size of the serialized result, in bytes. This is zero if the request failed.
+    pub response_size: usize,
+}
+
+/// A sink receiving the metrics of the requests sent through a [`MetricsLayer`].
+///
+/// Implementations are expected to aggregate the metrics, e.g. into per-method counters and
+/// latency histograms. The sink is called on the completion of every request, so it should
+/// return quickly.
+pub trait MetricsSink: Send + Sync + 'static {
+    /// Records the metrics of a completed request.
+    fn record(&self, metrics: &RequestMetrics<'_>);
+}
+
+impl<T: MetricsSink + ?Sized> MetricsSink for Arc<T> {
+    fn record(&self, metrics: &RequestMetrics<'_>) {
+        (**self).record(metrics)
+    }
+}
+
+/// A [`MetricsSink`] recording to the global recorder of the [`metrics`] crate.
+///
+/// The following metrics are recorded, labeled by `method`, and by `endpoint` if configured:
+/// - `alloy_rpc_requests_total`: counter of requests.
+/// - `alloy_rpc_errors_total`: counter of failed requests, additionally labeled by `kind`, the
+///   [`RequestOutcome`] of the request.
+/// - `alloy_rpc_request_duration_seconds`: histogram of request latencies.
+/// - `alloy_rpc_request_size_bytes`: histogram of request sizes.
+/// - `alloy_rpc_response_size_bytes`: histogram of response sizes.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct MetricsRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsSink for MetricsRecorder {
+    fn record(&self, metrics: &RequestMetrics<'_>) {
+        use metrics::{counter, histogram, Label};
+
+        let mut labels = vec![Label::new("method", metrics.method.to_owned())];
+        if let Some(endpoint) = metrics.endpoint {
+            labels.push(Label::new("endpoint", endpoint.to_owned()));
+        }
+
+        counter!("alloy_rpc_requests_total", labels.iter()).increment(1);
+        if !metrics.outcome.is_success() {
+            let mut labels = labels.clone();
+            labels.push(Label::new("kind", metrics.outcome.as_str()));
+            counter!("alloy_rpc_errors_total", labels).increment(1);
+        }
+        histogram!("alloy_rpc_request_duration_seconds", labels.iter())
+            .record(metrics.latency.as_secs_f64());
+        histogram!("alloy_rpc_request_size_bytes", labels.iter())
+            .record(metrics.request_size as f64);
+        histogram!("alloy_rpc_response_size_bytes", labels).record(metrics.response_size as f64);
+    }
+}
+
+/// A Transport Layer that is responsible for recording metrics and tracing spans of requests.
+///
+/// Each request is recorded to the [`MetricsSink`] once its response is received, and is sent
+/// within an `rpc.request` span with OpenTelemetry style attributes: `rpc.system`, `rpc.method`,
+/// `rpc.jsonrpc.request_id`, `rpc.batch_size` and `server.address`. Batches are recorded under
+/// the `batch` method in the span.
+#[derive(Debug)]
+pub struct MetricsLayer<M> {
+    sink: Arc<M>,
+    endpoint: Option<Arc<str>>,
+}
+
+impl<M> Clone for MetricsLayer<M> {
+    fn clone(&self) -> Self {
+        Self { sink: self.sink.clone(), endpoint: self.endpoint.clone() }
+    }
+}
+
+impl<M: MetricsSink> MetricsLayer<M> {
+    /// Creates a new metrics layer recording to the given sink.
+    pub fn new(sink: M) -> Self {
+        Self { sink: Arc::new(sink), endpoint: None }
+    }
+
+    /// Sets the endpoint the requests are sent to, to label the metrics and spans.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into().into());
+        self
+    }
+
+    /// Returns the sink.
+    pub fn sink(&self) -> &M {
+        &self.sink
+    }
+}
+
+impl<S, M> Layer<S> for MetricsLayer<M> {
+    type Service = MetricsService<S, M>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService { inner, sink: self.sink.clone(), endpoint: self.endpoint.clone() }
+    }
+}
+
+/// A Tower Service used by the [MetricsLayer] that is responsible for recording metrics and
+/// tracing spans of requests.
+#[derive(Debug)]
+pub struct MetricsService<S, M> {
+    /// The inner service
+    inner: S,
+    sink: Arc<M>,
+    endpoint: Option<Arc<str>>,
+}
+
+impl<S: Clone, M> Clone for MetricsService<S, M> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), sink: self.sink.clone(), endpoint: self.endpoint.clone() }
+    }
+}
+
+/// The method, ID and size of a request, kept to record its metrics.
+struct RequestInfo {
+    method: String,
+    id: Id,
+    size: usize,
+}
+
+impl<S, M> MetricsService<S, M>
+where
+    M: MetricsSink,
+{
+    /// Records the metrics of each request of a packet.
+    fn record(
+        sink: &M,
+        endpoint: Option<&str>,
+        requests: &[RequestInfo],
+        res: &Result<ResponsePacket, TransportError>,
+        latency: Duration,
+    ) {
+        let responses: &[Response] = match res {
+            Ok(ResponsePacket::Single(response)) => std::slice::from_ref(response),
+            Ok(ResponsePacket::Batch(responses)) => responses,
+            Err(_) => &[],
+        };
+
+        for request in requests {
+            let response = responses.iter().find(|response| response.id == request.id);
+            let (outcome, response_size) = match (res, response) {
+                (Err(err), _) => (RequestOutcome::from_error(err), 0),
+                (Ok(_), Some(response)) => {
+                    response.payload.as_success().map_or((RequestOutcome::ErrorResp, 0), |result| {
+                        (RequestOutcome::Success, result.get().len())
+                    })
+                }
+                (Ok(_), None) => (RequestOutcome::Transport, 0),
+            };
+            sink.record(&RequestMetrics {
+                method: &request.method,
+                endpoint,
+                outcome,
+                latency,
+                request_size: request.size,
+                response_size,
+            });
+        }
+    }
+
+    /// Creates the span of a request packet.
+    fn span(&self, requests: &[RequestInfo]) -> Span {
+        let span = info_span!(
+            "rpc.request",
+            rpc.system = "jsonrpc",
+            rpc.method = field::Empty,
+            rpc.jsonrpc.request_id = field::Empty,
+            rpc.batch_size = field::Empty,
+            server.address = field::Empty,
+        );
+        match requests {
+            [request] => {
+                span.record("rpc.method", request.method.as_str());
+                span.record("rpc.jsonrpc.request_id", field::display(&request.id));
+            }
+            requests => {
+                span.record("rpc.method", "batch");
+                span.record("rpc.batch_size", requests.len());
+            }
+        }
+        if let Some(endpoint) = &self.endpoint {
+            span.record("server.address", &**endpoint);
+        }
+        span
+    }
+}
+
+impl<S, M> Service<RequestPacket> for MetricsService<S, M>
+where
+    S: Service<RequestPacket, Future = TransportFut<'static>, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    M: MetricsSink,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let info = |req: &alloy_json_rpc::SerializedRequest| RequestInfo {
+            method: req.method().to_owned(),
+            id: req.id().clone(),
+            size: req.serialized().get().len(),
+        };
+        let requests: Vec<_> = match &request {
+            RequestPacket::Single(req) => vec![info(req)],
+            RequestPacket::Batch(reqs) => reqs.iter().map(info).collect(),
+        };
+        let span = self.span(&requests);
+
+        let sink = self.sink.clone();
+        let endpoint = self.endpoint.clone();
+        let fut = span.in_scope(|| self.inner.call(request));
+        Box::pin(
+            async move {
+                let start = Instant::now();
+                let res = fut.await;
+                Self::record(&sink, endpoint.as_deref(), &requests, &res, start.elapsed());
+                res
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{MockResponse, MockTransport},
+        TransportErrorKind,
+    };
+    use alloy_json_rpc::Request;
+    use std::sync::Mutex;
+
+    /// The method, endpoint, outcome, request size and response size of a recorded request.
+    type Record = (String, Option<String>, RequestOutcome, usize, usize);
+
+    /// A sink collecting the recorded requests.
+    #[derive(Default)]
+    struct Recorded(Mutex<Vec<Record>>);
+
+    impl MetricsSink for Recorded {
+        fn record(&self, metrics: &RequestMetrics<'_>) {
+            self.0.lock().unwrap().push((
+                metrics.method.to_string(),
+                metrics.endpoint.map(str::to_string),
+                metrics.outcome,
+                metrics.request_size,
+                metrics.response_size,
+            ));
+        }
+    }
+
+    /// A transport answering `fail` requests with an error response, dropping the responses to
+    /// `drop` requests, and failing on `down` requests.
+    fn mock_transport() -> MockTransport {
+        MockTransport::new(|req| match req.method() {
+            "fail" => MockResponse::error(3, "reverted"),
+            "drop" => MockResponse::Missing,
+            "down" => MockResponse::Fail(TransportErrorKind::backend_gone()),
+            _ => MockResponse::success("0x1234"),
+        })
+    }
+
+    fn request(method: impl Into<String>, id: u64) -> alloy_json_rpc::SerializedRequest {
+        Request::new(method.into(), Id::Number(id), ()).serialize().unwrap()
+    }
+
+    #[tokio::test]
+    async fn records_each_request() {
+        let layer = MetricsLayer::new(Recorded::default()).with_endpoint("localhost:8545");
+        let mut service = layer.layer(mock_transport());
+
+        service.call(request("eth_chainId", 0).into()).await.unwrap();
+        service.call(request("down", 1).into()).await.unwrap_err();
+        service
+            .call(RequestPacket::Batch(vec![
+                request("eth_blockNumber", 2),
+                request("fail", 3),
+                request("drop", 4),
+            ]))
+            .await
+            .unwrap();
+
+        let recorded = layer.sink().0.lock().unwrap();
+        assert!(recorded.iter().all(|(method, endpoint, _, request_size, _)| {
+            endpoint.as_deref() == Some("localhost:8545")
+                && *request_size == request(method.clone(), 0).serialized().get().len()
+        }));
+        let outcomes: Vec<_> = recorded
+            .iter()
+            .map(|(method, _, outcome, _, response_size)| {
+                (method.as_str(), *outcome, *response_size)
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                ("eth_chainId", RequestOutcome::Success, 8),
+                ("down", RequestOutcome::Transport, 0),
+                ("eth_blockNumber", RequestOutcome::Success, 8),
+                ("fail", RequestOutcome::ErrorResp, 0),
+                ("drop", RequestOutcome::Transport, 0),
+            ]
+        );
+    }
+}
@@ -1,6 +1,35 @@
 //! Module for housing transport layers.
 
+mod batch;
+mod dedup;
+mod fallback;
+mod metrics;
+mod rate_limit;
+mod record;
 mod retry;
 
+/// BatchLayer
+pub use batch::{BatchLayer, BatchService};
+
+/// DedupLayer
+pub use dedup::{DedupLayer, DedupService};
+
+/// FallbackLayer
+pub use fallback::{FailoverPolicy, FallbackLayer, FallbackService, Quorum};
+
+/// MetricsLayer
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsRecorder;
+pub use metrics::{MetricsLayer, MetricsService, MetricsSink, RequestMetrics, RequestOutcome};
+
+/// RateLimitLayer
+pub use rate_limit::{
+    ComputeUnitCosts, ComputeUnitUsage, ComputeUnitsExceeded, OverBudget, RateLimitLayer,
+    RateLimitService,
+};
+
+/// RecordLayer
+pub use record::{RecordLayer, RecordService, RecordedExchange, ReplayTransport};
+
 /// RetryBackoffLayer
 pub use retry::{RateLimitRetryPolicy, RetryBackoffLayer, RetryBackoffService, RetryPolicy};
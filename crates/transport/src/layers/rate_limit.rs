@@ -0,0 +1,429 @@
+use crate::{TransportError, TransportErrorKind, TransportFut};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::{std::Instant, tokio::sleep_until};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{sleep_until, Instant};
+
+/// The compute unit cost of each JSON-RPC method, as billed by a provider.
+///
+/// Methods without a configured cost are charged the default cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputeUnitCosts {
+    /// The cost of methods without a configured cost.
+    default_cost: u64,
+    /// The cost of each method.
+    costs: HashMap<Cow<'static, str>, u64>,
+}
+
+impl Default for ComputeUnitCosts {
+    /// Charges one compute unit per request, which turns the budget into a request rate limit.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl ComputeUnitCosts {
+    /// Creates a new table charging `default_cost` for every method.
+    pub fn new(default_cost: u64) -> Self {
+        Self { default_cost, costs: HashMap::new() }
+    }
+
+    /// Sets the cost of the given method.
+    pub fn with_cost(mut self, method: impl Into<Cow<'static, str>>, cost: u64) -> Self {
+        self.costs.insert(method.into(), cost);
+        self
+    }
+
+    /// Sets the cost of the given methods.
+    pub fn with_costs(mut self, costs: impl IntoIterator<Item = (&'static str, u64)>) -> Self {
+        self.costs.extend(costs.into_iter().map(|(method, cost)| (Cow::Borrowed(method), cost)));
+        self
+    }
+
+    /// Returns the cost of the given method.
+    pub fn cost(&self, method: &str) -> u64 {
+        self.costs.get(method).copied().unwrap_or(self.default_cost)
+    }
+
+    /// Returns the cost of a request packet, summing the cost of each request of a batch.
+    pub fn packet_cost(&self, packet: &RequestPacket) -> u64 {
+        match packet {
+            RequestPacket::Single(req) => self.cost(req.method()),
+            RequestPacket::Batch(reqs) => reqs.iter().map(|req| self.cost(req.method())).sum(),
+        }
+    }
+
+    /// The compute unit costs of [Alchemy](https://docs.alchemy.com/reference/compute-unit-costs).
+    pub fn alchemy() -> Self {
+        Self::new(26).with_costs([
+            ("net_version", 0),
+            ("eth_chainId", 0),
+            ("eth_syncing", 0),
+            ("eth_protocolVersion", 0),
+            ("net_listening", 0),
+            ("eth_blockNumber", 10),
+            ("eth_feeHistory", 10),
+            ("eth_maxPriorityFeePerGas", 10),
+            ("eth_subscribe", 10),
+            ("eth_unsubscribe", 10),
+            ("eth_getTransactionReceipt", 15),
+            ("eth_getBlockByHash", 16),
+            ("eth_getBlockByNumber", 16),
+            ("eth_getStorageAt", 17),
+            ("eth_getTransactionByHash", 17),
+            ("eth_getBalance", 19),
+            ("eth_gasPrice", 19),
+            ("eth_newFilter", 20),
+            ("eth_newBlockFilter", 20),
+            ("eth_getFilterChanges", 20),
+            ("eth_call", 26),
+            ("eth_getCode", 26),
+            ("eth_getTransactionCount", 26),
+            ("eth_getLogs", 75),
+            ("eth_estimateGas", 87),
+            ("eth_getBlockReceipts", 500),
+            ("eth_sendRawTransaction", 250),
+            ("trace_block", 24),
+            ("trace_transaction", 26),
+            ("trace_call", 75),
+            ("trace_replayTransaction", 2983),
+            ("debug_traceTransaction", 309),
+            ("debug_traceCall", 309),
+        ])
+    }
+
+    /// The credit costs of [Infura](https://docs.metamask.io/services/get-started/pricing/credit-cost/).
+    pub fn infura() -> Self {
+        Self::new(80).with_costs([
+            ("net_version", 5),
+            ("net_listening", 5),
+            ("eth_chainId", 5),
+            ("eth_syncing", 5),
+            ("eth_subscribe", 5),
+            ("eth_unsubscribe", 10),
+            ("eth_getLogs", 255),
+            ("eth_estimateGas", 300),
+            ("eth_sendRawTransaction", 720),
+            ("eth_getBlockReceipts", 1000),
+            ("trace_block", 300),
+            ("trace_transaction", 300),
+            ("trace_call", 300),
+            ("debug_traceTransaction", 1000),
+            ("debug_traceCall", 1000),
+        ])
+    }
+}
+
+/// What to do with a request exceeding the available compute units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverBudget {
+    /// Wait until enough compute units are available. Requests are served in the order they were
+    /// issued.
+    #[default]
+    Wait,
+    /// Fail the request with a [`ComputeUnitsExceeded`] error.
+    Reject,
+}
+
+/// The error returned when a request is rejected for exceeding the available compute units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("request costs {cost} compute units, but only {available} are available")]
+pub struct ComputeUnitsExceeded {
+    /// The cost of the request.
+    pub cost: u64,
+    /// The compute units available when the request was issued.
+    pub available: u64,
+}
+
+/// A snapshot of the compute unit usage of a [RateLimitLayer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeUnitUsage {
+    /// The compute units currently available.
+    pub available: u64,
+    /// The maximum number of compute units available at once.
+    pub capacity: u64,
+    /// The compute units refilled every second.
+    pub compute_units_per_second: u64,
+    /// The total compute units consumed by the requests sent so far.
+    pub consumed: u64,
+    /// The number of requests currently waiting for compute units.
+    pub queued: usize,
+}
+
+/// A token bucket of compute units.
+#[derive(Debug)]
+struct Bucket {
+    capacity: u64,
+    compute_units_per_second: u64,
+    /// The compute units available as of `updated`. Negative when waiting requests have reserved
+    /// compute units ahead of time.
+    available: f64,
+    updated: Instant,
+    consumed: u64,
+    queued: usize,
+}
+
+impl Bucket {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let refilled = (now - self.updated).as_secs_f64() * self.compute_units_per_second as f64;
+        self.available = (self.available + refilled).min(self.capacity as f64);
+        self.updated = now;
+    }
+
+    /// Reserves the compute units of a request, returning when it can be sent if it has to wait.
+    fn acquire(
+        &mut self,
+        cost: u64,
+        policy: OverBudget,
+    ) -> Result<Option<Instant>, ComputeUnitsExceeded> {
+        self.refill();
+        if policy == OverBudget::Reject && self.available < cost as f64 {
+            let available = self.available.max(0.0) as u64;
+            return Err(ComputeUnitsExceeded { cost, available });
+        }
+
+        self.available -= cost as f64;
+        self.consumed += cost;
+        if self.available >= 0.0 {
+            return Ok(None);
+        }
+        self.queued += 1;
+        let wait = Duration::from_secs_f64(-self.available / self.compute_units_per_second as f64);
+        Ok(Some(self.updated + wait))
+    }
+
+    fn usage(&mut self) -> ComputeUnitUsage {
+        self.refill();
+        ComputeUnitUsage {
+            available: self.available.max(0.0) as u64,
+            capacity: self.capacity,
+            compute_units_per_second: self.compute_units_per_second,
+            consumed: self.consumed,
+            queued: self.queued,
+        }
+    }
+}
+
+/// A Transport Layer that is responsible for metering outgoing requests against a budget of
+/// compute units, before the provider throttles them.
+///
+/// The budget is a token bucket refilled at `compute_units_per_second`, holding up to one second
+/// of compute units by default. Each request is charged the cost of its method, according to a
+/// [ComputeUnitCosts] table, and waits or is rejected when the bucket runs out, according to
+/// [OverBudget].
+///
+/// All the services built by the same layer, and their clones, share the same budget.
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    costs: Arc<ComputeUnitCosts>,
+    policy: OverBudget,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl RateLimitLayer {
+    /// Creates a new rate limit layer with the given budget, charging one compute unit per
+    /// request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `compute_units_per_second` is zero.
+    pub fn new(compute_units_per_second: u64) -> Self {
+        assert_ne!(compute_units_per_second, 0, "compute units per second must not be zero");
+        let bucket = Bucket {
+            capacity: compute_units_per_second,
+            compute_units_per_second,
+            available: compute_units_per_second as f64,
+            updated: Instant::now(),
+            consumed: 0,
+            queued: 0,
+        };
+        Self {
+            costs: Default::default(),
+            policy: OverBudget::default(),
+            bucket: Arc::new(Mutex::new(bucket)),
+        }
+    }
+
+    /// Sets the compute unit cost of each method.
+    pub fn with_costs(mut self, costs: ComputeUnitCosts) -> Self {
+        self.costs = Arc::new(costs);
+        self
+    }
+
+    /// Sets what to do with requests exceeding the available compute units.
+    pub const fn with_policy(mut self, policy: OverBudget) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the maximum number of compute units available at once, allowing bursts above the
+    /// budget per second.
+    pub fn with_capacity(self, capacity: u64) -> Self {
+        {
+            let mut bucket = self.bucket.lock().unwrap();
+            bucket.capacity = capacity;
+            bucket.available = capacity as f64;
+        }
+        self
+    }
+
+    /// Returns a snapshot of the current compute unit usage.
+    pub fn usage(&self) -> ComputeUnitUsage {
+        self.bucket.lock().unwrap().usage()
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            costs: self.costs.clone(),
+            policy: self.policy,
+            bucket: self.bucket.clone(),
+        }
+    }
+}
+
+/// A Tower Service used by the [RateLimitLayer] that is responsible for metering requests against
+/// a budget of compute units.
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    /// The inner service
+    inner: S,
+    costs: Arc<ComputeUnitCosts>,
+    policy: OverBudget,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl<S> RateLimitService<S> {
+    /// Returns a snapshot of the current compute unit usage.
+    pub fn usage(&self) -> ComputeUnitUsage {
+        self.bucket.lock().unwrap().usage()
+    }
+}
+
+impl<S> Service<RequestPacket> for RateLimitService<S>
+where
+    S: Service<RequestPacket, Future = TransportFut<'static>, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, inner);
+
+        let cost = self.costs.packet_cost(&request);
+        let acquired = self.bucket.lock().unwrap().acquire(cost, self.policy);
+        // Created right away, so that the request leaves the queue even if never polled.
+        let queued = matches!(acquired, Ok(Some(_))).then(|| QueueGuard(self.bucket.clone()));
+        Box::pin(async move {
+            if let Some(deadline) = acquired.map_err(TransportErrorKind::custom)? {
+                trace!(cost, "waiting for compute units");
+                sleep_until(deadline).await;
+            }
+            drop(queued);
+            inner.call(request).await
+        })
+    }
+}
+
+/// Removes a waiting request from the queue once dropped.
+struct QueueGuard(Arc<Mutex<Bucket>>);
+
+impl Drop for QueueGuard {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().queued -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_json_rpc::{Id, Request};
+
+    fn request(method: &'static str) -> RequestPacket {
+        Request::new(method, Id::Number(1), ()).serialize().unwrap().into()
+    }
+
+    #[test]
+    fn packet_costs() {
+        let costs = ComputeUnitCosts::alchemy();
+        assert_eq!(costs.packet_cost(&request("eth_blockNumber")), 10);
+        assert_eq!(costs.packet_cost(&request("eth_unknown")), 26);
+
+        let batch = RequestPacket::Batch(vec![
+            Request::new("eth_getLogs", Id::Number(1), ()).serialize().unwrap(),
+            Request::new("eth_chainId", Id::Number(2), ()).serialize().unwrap(),
+        ]);
+        assert_eq!(costs.packet_cost(&batch), 75);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_compute_units() {
+        let layer = RateLimitLayer::new(100)
+            .with_costs(ComputeUnitCosts::new(10).with_cost("eth_getLogs", 60));
+        let mut service = layer.layer(MockTransport::with_result("0x1"));
+
+        let start = Instant::now();
+        service.call(request("eth_getLogs")).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // 20 compute units short, refilled in 200ms.
+        let first = service.call(request("eth_getLogs"));
+        // Queued behind the first request, 30 compute units short.
+        let second = service.call(request("eth_blockNumber"));
+        let usage = layer.usage();
+        assert_eq!((usage.consumed, usage.queued), (130, 2));
+
+        first.await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+        assert_eq!(layer.usage().queued, 1);
+        second.await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+
+        let usage = layer.usage();
+        assert_eq!((usage.available, usage.queued), (0, 0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rejects_over_budget() {
+        let layer = RateLimitLayer::new(10)
+            .with_capacity(20)
+            .with_costs(ComputeUnitCosts::new(15))
+            .with_policy(OverBudget::Reject);
+        let mut service = layer.layer(MockTransport::with_result("0x1"));
+
+        service.call(request("eth_call")).await.unwrap();
+        let err = service.call(request("eth_call")).await.unwrap_err();
+        assert_eq!(err.to_string(), ComputeUnitsExceeded { cost: 15, available: 5 }.to_string());
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        service.call(request("eth_call")).await.unwrap();
+        assert_eq!(layer.usage().consumed, 30);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{TransportError, TransportErrorKind, TransportFut};
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::{trace, warn};
+
+/// A request and its response, as recorded by a [`RecordLayer`] and served by a
+/// [`ReplayTransport`].
+///
+/// Recordings are stored as JSON lines, one exchange per line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedExchange {
+    /// The method of the request.
+    pub method: String,
+    /// The hash of the params of the request, see [`SerializedRequest::params_hash`].
+    pub params_hash: B256,
+    /// The serialized request.
+    pub request: Box<RawValue>,
+    /// The response to the request.
+    pub response: Response,
+}
+
+impl RecordedExchange {
+    /// Creates a new exchange from a request and its response.
+    pub fn new(request: &SerializedRequest, response: Response) -> Self {
+        Self {
+            method: request.method().to_string(),
+            params_hash: request.params_hash(),
+            request: request.serialized().to_owned(),
+            response,
+        }
+    }
+}
+
+/// A Transport Layer that is responsible for recording the exchanges with a transport to a file.
+///
+/// Every request which receives a response, including an error response, is appended to the file
+/// as a [`RecordedExchange`], so that it can be served by a [`ReplayTransport`]. The requests of
+/// a batch are recorded separately. Requests failing at the transport level are not recorded.
+///
+/// Exchanges are written as they complete, with blocking writes, so this is meant for tests.
+#[derive(Debug, Clone)]
+pub struct RecordLayer {
+    file: Arc<Mutex<File>>,
+}
+
+impl RecordLayer {
+    /// Creates a new record layer writing to the file at the given path, truncating it if it
+    /// exists.
+    pub fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_file(File::create(path)?))
+    }
+
+    /// Creates a new record layer writing to the given file.
+    pub fn from_file(file: File) -> Self {
+        Self { file: Arc::new(Mutex::new(file)) }
+    }
+}
+
+impl<S> Layer<S> for RecordLayer {
+    type Service = RecordService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RecordService { inner, file: self.file.clone() }
+    }
+}
+
+/// A Tower Service used by the [RecordLayer] that is responsible for recording the exchanges with
+/// a transport to a file.
+#[derive(Debug, Clone)]
+pub struct RecordService<S> {
+    /// The inner service
+    inner: S,
+    file: Arc<Mutex<File>>,
+}
+
+/// Appends the exchanges of a request packet to the file.
+fn record(file: &Mutex<File>, requests: &[SerializedRequest], responses: &ResponsePacket) {
+    let responses = match responses {
+        ResponsePacket::Single(response) => std::slice::from_ref(response),
+        ResponsePacket::Batch(responses) => responses,
+    };
+
+    let mut lines = String::new();
+    for request in requests {
+        let Some(response) = responses.iter().find(|response| &response.id == request.id()) else {
+            continue;
+        };
+        let exchange = RecordedExchange::new(request, response.clone());
+        match serde_json::to_string(&exchange) {
+            Ok(line) => {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            Err(err) => warn!(%err, method = request.method(), "failed to serialize exchange"),
+        }
+    }
+
+    if let Err(err) = file.lock().unwrap().write_all(lines.as_bytes()) {
+        warn!(%err, "failed to record exchanges");
+    }
+}
+
+impl<S> Service<RequestPacket> for RecordService<S>
+where
+    S: Service<RequestPacket, Future = TransportFut<'static>, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let requests = match &request {
+            RequestPacket::Single(req) => vec![req.clone()],
+            RequestPacket::Batch(reqs) => reqs.clone(),
+        };
+        let file = self.file.clone();
+        let fut = self.inner.call(request);
+        Box::pin(async move {
+            let res = fut.await;
+            if let Ok(responses) = &res {
+                record(&file, &requests, responses);
+            }
+            res
+        })
+    }
+}
+
+/// A transport serving the responses of recorded exchanges, e.g. written by a [`RecordLayer`].
+///
+/// Requests are matched to the recorded exchanges by method and [params hash], and the response
+/// is returned with the ID of the request. The responses recorded for the same request are served
+/// in order, the last one being repeated once the others were served, so that a recording of
+/// polling requests such as `eth_blockNumber` replays deterministically.
+///
+/// Requests without a recorded response fail with a transport error.
+///
+/// [params hash]: SerializedRequest::params_hash
+#[derive(Debug, Clone, Default)]
+pub struct ReplayTransport {
+    responses: Arc<Mutex<RecordedResponses>>,
+}
+
+/// The recorded responses, by method and params hash.
+type RecordedResponses = HashMap<(String, B256), VecDeque<Response>>;
+
+impl ReplayTransport {
+    /// Creates a new transport serving the given exchanges.
+    pub fn new(exchanges: impl IntoIterator<Item = RecordedExchange>) -> Self {
+        let mut responses = RecordedResponses::new();
+        for exchange in exchanges {
+            responses
+                .entry((exchange.method, exchange.params_hash))
+                .or_default()
+                .push_back(exchange.response);
+        }
+        Self { responses: Arc::new(Mutex::new(responses)) }
+    }
+
+    /// Creates a new transport serving the exchanges recorded in the file at the given path.
+    pub fn from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Creates a new transport serving the exchanges read from the given reader, as JSON lines.
+    pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+        let mut exchanges = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            exchanges.push(serde_json::from_str(&line)?);
+        }
+        Ok(Self::new(exchanges))
+    }
+
+    /// Returns the next recorded response to the given request.
+    fn respond(&self, request: &SerializedRequest) -> Result<Response, TransportError> {
+        let key = (request.method().to_string(), request.params_hash());
+        let mut responses = self.responses.lock().unwrap();
+        let recorded = responses.get_mut(&key).filter(|recorded| !recorded.is_empty());
+        let Some(recorded) = recorded else {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "no recorded response for {} with params hash {}",
+                key.0, key.1
+            )));
+        };
+
+        let mut response =
+            if recorded.len() > 1 { recorded.pop_front().unwrap() } else { recorded[0].clone() };
+        trace!(method = request.method(), "replaying recorded response");
+        response.id = request.id().clone();
+        Ok(response)
+    }
+}
+
+impl Service<RequestPacket> for ReplayTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let res = match request {
+            RequestPacket::Single(req) => self.respond(&req).map(ResponsePacket::Single),
+            RequestPacket::Batch(reqs) => reqs
+                .iter()
+                .map(|req| self.respond(req))
+                .collect::<Result<_, _>>()
+                .map(ResponsePacket::Batch),
+        };
+        Box::pin(async move { res })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockTransport};
+    use alloy_json_rpc::{Id, Request};
+
+    /// A transport answering each request with its params, and the number of requests so far.
+    fn mock_transport() -> MockTransport {
+        let counter = Mutex::new(0);
+        MockTransport::new(move |req| {
+            let mut counter = counter.lock().unwrap();
+            *counter += 1;
+            MockResponse::raw(format!("[{},{}]", req.params().unwrap(), counter))
+        })
+    }
+
+    fn request(method: &'static str, param: u64, id: u64) -> SerializedRequest {
+        Request::new(method, Id::Number(id), [param]).serialize().unwrap()
+    }
+
+    fn result(res: Result<ResponsePacket, TransportError>) -> String {
+        let ResponsePacket::Single(res) = res.unwrap() else { unreachable!() };
+        res.payload.as_success().unwrap().get().to_string()
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut service = RecordLayer::new(file.path()).unwrap().layer(mock_transport());
+        service.call(request("a", 1, 0).into()).await.unwrap();
+        service.call(request("a", 1, 1).into()).await.unwrap();
+        service
+            .call(RequestPacket::Batch(vec![request("a", 2, 2), request("b", 1, 3)]))
+            .await
+            .unwrap();
+
+        let mut replay = ReplayTransport::from_path(file.path()).unwrap();
+
+        // Same request, served in order, then repeated.
+        assert_eq!(result(replay.call(request("a", 1, 7).into()).await), "[[1],1]");
+        assert_eq!(result(replay.call(request("a", 1, 8).into()).await), "[[1],2]");
+        assert_eq!(result(replay.call(request("a", 1, 9).into()).await), "[[1],2]");
+
+        let ResponsePacket::Batch(responses) = replay
+            .call(RequestPacket::Batch(vec![request("b", 1, 10), request("a", 2, 11)]))
+            .await
+            .unwrap()
+        else {
+            unreachable!()
+        };
+        assert_eq!(responses[0].id, Id::Number(10));
+        assert_eq!(responses[0].payload.as_success().unwrap().get(), "[[1],4]");
+        assert_eq!(responses[1].payload.as_success().unwrap().get(), "[[2],3]");
+
+        assert!(replay.call(request("b", 2, 12).into()).await.is_err());
+    }
+}
@@ -23,11 +23,17 @@ pub use error::{HttpError, TransportError, TransportResult};
 mod r#trait;
 pub use r#trait::Transport;
 
+mod stream;
+pub use stream::{decode_result_array, ResultArrayDecoder, TransportStream};
+
 pub use alloy_json_rpc::{RpcError, RpcResult};
 pub use futures_utils_wasm::{impl_future, BoxFuture};
 
 pub mod layers;
 
+#[cfg(all(any(test, feature = "test-utils"), not(target_arch = "wasm32")))]
+pub mod mock;
+
 /// Misc. utilities for building transports.
 pub mod utils;
 
@@ -0,0 +1,195 @@
+//! A mock transport, answering requests with a handler, for tests.
+
+use crate::{TransportError, TransportErrorKind, TransportFut};
+use alloy_json_rpc::{
+    ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest,
+};
+use serde::Serialize;
+use serde_json::value::RawValue;
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::Service;
+
+/// The answer of a [`MockTransport`] to a request.
+#[derive(Debug)]
+pub enum MockResponse {
+    /// A successful response, with the given JSON result.
+    Success(Box<RawValue>),
+    /// An error response.
+    Error(ErrorPayload),
+    /// No response. Single requests fail with a missing response, and batch responses leave the
+    /// request out.
+    Missing,
+    /// A transport error, failing the whole request packet.
+    Fail(TransportError),
+}
+
+impl MockResponse {
+    /// Creates a successful response with the given result.
+    pub fn success<T: Serialize + ?Sized>(result: &T) -> Self {
+        Self::Success(serde_json::value::to_raw_value(result).unwrap())
+    }
+
+    /// Creates a successful response with the given JSON result.
+    pub fn raw(result: impl Into<String>) -> Self {
+        Self::Success(RawValue::from_string(result.into()).unwrap())
+    }
+
+    /// Creates an error response with the given code and message.
+    pub fn error(code: i64, message: impl Into<String>) -> Self {
+        Self::Error(ErrorPayload { code, message: message.into().into(), data: None })
+    }
+}
+
+/// A handler answering the requests of a [`MockTransport`].
+type Handler = dyn Fn(&SerializedRequest) -> MockResponse + Send + Sync;
+
+/// A transport answering each request with a handler, and recording the request packets it
+/// received.
+///
+/// Handlers are called in the order the requests are sent, and the responses are returned after
+/// an optional delay.
+///
+/// ```
+/// use alloy_transport::mock::{MockResponse, MockTransport};
+///
+/// let transport = MockTransport::new(|req| match req.method() {
+///     "eth_chainId" => MockResponse::success("0x1"),
+///     _ => MockResponse::error(-32601, "method not found"),
+/// });
+/// ```
+#[derive(Clone)]
+pub struct MockTransport {
+    handler: Arc<Handler>,
+    packets: Arc<Mutex<Vec<RequestPacket>>>,
+    delay: Duration,
+    reversed: bool,
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("packets", &self.packets)
+            .field("delay", &self.delay)
+            .field("reversed", &self.reversed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockTransport {
+    /// Creates a new transport answering each request with the handler.
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&SerializedRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        Self {
+            handler: Arc::new(handler),
+            packets: Default::default(),
+            delay: Duration::ZERO,
+            reversed: false,
+        }
+    }
+
+    /// Creates a new transport answering every request with the given result.
+    pub fn with_result<T: Serialize + ?Sized>(result: &T) -> Self {
+        let result = serde_json::value::to_raw_value(result).unwrap();
+        Self::new(move |_| MockResponse::Success(result.clone()))
+    }
+
+    /// Creates a new transport failing every request with the given message.
+    pub fn with_error(message: &'static str) -> Self {
+        Self::new(move |_| MockResponse::Fail(TransportErrorKind::custom_str(message)))
+    }
+
+    /// Returns the responses after the given delay.
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Answers batches in reverse order.
+    pub const fn with_reversed_batches(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
+    /// Returns the request packets received so far.
+    pub fn packets(&self) -> Vec<RequestPacket> {
+        self.packets.lock().unwrap().clone()
+    }
+
+    /// Returns the requests received so far, including those of batches.
+    pub fn requests(&self) -> Vec<SerializedRequest> {
+        let packets = self.packets.lock().unwrap();
+        packets
+            .iter()
+            .flat_map(|packet| match packet {
+                RequestPacket::Single(req) => std::slice::from_ref(req),
+                RequestPacket::Batch(reqs) => reqs.as_slice(),
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the number of request packets received so far.
+    pub fn calls(&self) -> usize {
+        self.packets.lock().unwrap().len()
+    }
+
+    /// Answers a request, if it is not left out.
+    fn respond(&self, req: &SerializedRequest) -> Result<Option<Response>, TransportError> {
+        let payload = match (self.handler)(req) {
+            MockResponse::Success(result) => ResponsePayload::Success(result),
+            MockResponse::Error(err) => ResponsePayload::Failure(err),
+            MockResponse::Missing => return Ok(None),
+            MockResponse::Fail(err) => return Err(err),
+        };
+        Ok(Some(Response { id: req.id().clone(), payload }))
+    }
+
+    /// Answers a request packet.
+    fn respond_packet(&self, packet: &RequestPacket) -> Result<ResponsePacket, TransportError> {
+        match packet {
+            RequestPacket::Single(req) => self
+                .respond(req)?
+                .map(ResponsePacket::Single)
+                .ok_or_else(|| TransportErrorKind::missing_batch_response(req.id().clone())),
+            RequestPacket::Batch(reqs) => {
+                let mut responses = Vec::with_capacity(reqs.len());
+                for req in reqs {
+                    responses.extend(self.respond(req)?);
+                }
+                if self.reversed {
+                    responses.reverse();
+                }
+                Ok(ResponsePacket::Batch(responses))
+            }
+        }
+    }
+}
+
+impl Service<RequestPacket> for MockTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let res = self.respond_packet(&req);
+        self.packets.lock().unwrap().push(req);
+        let delay = self.delay;
+        Box::pin(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            res
+        })
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{TransportError, TransportErrorKind, TransportResult};
+use alloy_json_rpc::{Response, ResponsePayload, RpcError};
+use futures::{Stream, StreamExt};
+use serde::de::{DeserializeOwned, Error as _};
+use std::{collections::VecDeque, pin::Pin};
+
+/// Pin-boxed stream of transport results.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportStream<'a, T> = Pin<Box<dyn Stream<Item = TransportResult<T>> + Send + 'a>>;
+
+/// Pin-boxed stream of transport results.
+#[cfg(target_arch = "wasm32")]
+pub type TransportStream<'a, T> = Pin<Box<dyn Stream<Item = TransportResult<T>> + 'a>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    /// Reading the response, outside of the result array.
+    Response,
+    /// Reading the elements of the result array.
+    Elements,
+    /// The response was read entirely.
+    Done,
+}
+
+/// An incremental decoder for JSON-RPC responses whose result is an array.
+///
+/// The response is fed to the decoder in chunks of arbitrary size, and the elements of the
+/// result array are deserialized as soon as they are complete, so that very large responses,
+/// such as the result of `eth_getLogs` over a wide range, need not be buffered entirely. Only
+/// the current element, and the response without the elements, are kept in memory.
+///
+/// Once the response was fed entirely, [`finish`] checks it, returning the error of an error
+/// response.
+///
+/// [`finish`]: ResultArrayDecoder::finish
+#[derive(Debug)]
+pub struct ResultArrayDecoder {
+    /// The response, with the elements of the result array removed.
+    response: Vec<u8>,
+    /// The bytes of the current element of the result array.
+    element: Vec<u8>,
+    phase: Phase,
+    /// The nesting depth in the response.
+    depth: usize,
+    /// The nesting depth in the current element.
+    element_depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// The offset in `response` of the last string of the top-level object.
+    key_start: usize,
+    /// Whether the value of the `result` key is expected next.
+    expect_result: bool,
+    /// Whether the result array was found.
+    found: bool,
+}
+
+impl Default for ResultArrayDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResultArrayDecoder {
+    /// Creates a new decoder.
+    pub const fn new() -> Self {
+        Self {
+            response: Vec::new(),
+            element: Vec::new(),
+            phase: Phase::Response,
+            depth: 0,
+            element_depth: 0,
+            in_string: false,
+            escaped: false,
+            key_start: 0,
+            expect_result: false,
+            found: false,
+        }
+    }
+
+    /// Returns true if the response was read entirely. Any data fed afterwards is ignored.
+    pub fn is_done(&self) -> bool {
+        self.phase == Phase::Done
+    }
+
+    /// Feeds the next chunk of the response, returning the elements of the result array which
+    /// were completed by it.
+    pub fn decode<T: DeserializeOwned>(&mut self, chunk: &[u8]) -> TransportResult<Vec<T>> {
+        let mut items = Vec::new();
+        for &b in chunk {
+            match self.phase {
+                Phase::Response => self.response_byte(b),
+                Phase::Elements => self.element_byte(b, &mut items)?,
+                Phase::Done => break,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Checks the response once it was fed entirely.
+    ///
+    /// Returns the error of an error response, [`RpcError::NullResp`] if the result is null, and
+    /// an error if the response is incomplete or its result is not an array.
+    pub fn finish(self) -> TransportResult<()> {
+        if !self.is_done() {
+            return Err(TransportErrorKind::custom_str("response ended before it was complete"));
+        }
+
+        let response: Response = serde_json::from_slice(&self.response).map_err(|err| {
+            TransportError::deser_err(err, String::from_utf8_lossy(&self.response))
+        })?;
+        match response.payload {
+            ResponsePayload::Failure(err) => Err(RpcError::ErrorResp(err)),
+            ResponsePayload::Success(_) if self.found => Ok(()),
+            ResponsePayload::Success(result) if result.get() == "null" => Err(RpcError::NullResp),
+            ResponsePayload::Success(result) => Err(TransportError::deser_err(
+                serde_json::Error::custom("expected an array result"),
+                result.get(),
+            )),
+        }
+    }
+
+    /// Returns true if the byte is part of a string, updating the string state.
+    fn string_byte(&mut self, b: u8) -> bool {
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if b == b'\\' {
+                self.escaped = true;
+            } else if b == b'"' {
+                self.in_string = false;
+            }
+            return true;
+        }
+        if b == b'"' {
+            self.in_string = true;
+            return true;
+        }
+        false
+    }
+
+    fn response_byte(&mut self, b: u8) {
+        self.response.push(b);
+        let string_start = !self.in_string && b == b'"';
+        if self.string_byte(b) {
+            if string_start {
+                if self.depth == 1 {
+                    self.key_start = self.response.len() - 1;
+                }
+                self.expect_result = false;
+            }
+            return;
+        }
+
+        match b {
+            b':' if self.depth == 1 => {
+                let key = &self.response[self.key_start..self.response.len() - 1];
+                self.expect_result = key.trim_ascii_end() == br#""result""#;
+            }
+            b'[' if self.depth == 1 && self.expect_result && !self.found => {
+                // Keep an empty array in the response, and read the elements separately.
+                self.response.push(b']');
+                self.found = true;
+                self.expect_result = false;
+                self.phase = Phase::Elements;
+            }
+            b'{' | b'[' => {
+                self.depth += 1;
+                self.expect_result = false;
+            }
+            b'}' | b']' => {
+                self.depth = self.depth.saturating_sub(1);
+                if self.depth == 0 {
+                    self.phase = Phase::Done;
+                }
+            }
+            b if b.is_ascii_whitespace() => {}
+            _ => self.expect_result = false,
+        }
+    }
+
+    fn element_byte<T: DeserializeOwned>(
+        &mut self,
+        b: u8,
+        items: &mut Vec<T>,
+    ) -> TransportResult<()> {
+        if self.string_byte(b) {
+            self.element.push(b);
+            return Ok(());
+        }
+
+        match b {
+            b'{' | b'[' => {
+                self.element_depth += 1;
+                self.element.push(b);
+            }
+            b'}' | b']' if self.element_depth > 0 => {
+                self.element_depth -= 1;
+                self.element.push(b);
+            }
+            b']' => {
+                if !self.element.is_empty() {
+                    items.push(self.take_element()?);
+                }
+                self.phase = Phase::Response;
+            }
+            b',' if self.element_depth == 0 => items.push(self.take_element()?),
+            b if b.is_ascii_whitespace() && self.element.is_empty() => {}
+            b => self.element.push(b),
+        }
+        Ok(())
+    }
+
+    /// Deserializes the current element.
+    fn take_element<T: DeserializeOwned>(&mut self) -> TransportResult<T> {
+        let item = serde_json::from_slice(&self.element)
+            .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&self.element)));
+        self.element.clear();
+        item
+    }
+}
+
+/// Decodes the elements of the array result of a JSON-RPC response, read from a stream of
+/// chunks, with a [`ResultArrayDecoder`].
+///
+/// The stream of chunks is not polled once the response was read entirely, so that it can be
+/// read from a connection which stays open. The last item is an error if the response is an
+/// error response, or is invalid.
+pub fn decode_result_array<T, S, B>(chunks: S) -> impl Stream<Item = TransportResult<T>>
+where
+    T: DeserializeOwned,
+    S: Stream<Item = TransportResult<B>>,
+    B: AsRef<[u8]>,
+{
+    let state = (ResultArrayDecoder::new(), Box::pin(chunks), VecDeque::new());
+    futures::stream::try_unfold(state, |(mut decoder, mut chunks, mut items)| async move {
+        loop {
+            if let Some(item) = items.pop_front() {
+                return Ok(Some((item, (decoder, chunks, items))));
+            }
+            if decoder.is_done() {
+                break;
+            }
+            match chunks.next().await {
+                Some(chunk) => items.extend(decoder.decode(chunk?.as_ref())?),
+                None => break,
+            }
+        }
+        decoder.finish()?;
+        Ok(None)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+
+    async fn decode<T: DeserializeOwned>(
+        response: &str,
+        chunk_size: usize,
+    ) -> TransportResult<Vec<T>> {
+        let chunks = response.as_bytes().chunks(chunk_size).map(Ok).collect::<Vec<_>>();
+        decode_result_array(futures::stream::iter(chunks)).try_collect().await
+    }
+
+    #[tokio::test]
+    async fn decodes_array_result() {
+        let response = r#" {"jsonrpc": "2.0", "id": 1, "result" : [
+            {"a": "]}\"[,", "b": [1, {"c": 2}]},
+            {"a": "result", "b": []}
+        ]}
+"#;
+        for chunk_size in [1, 2, 7, response.len()] {
+            let items: Vec<serde_json::Value> = decode(response, chunk_size).await.unwrap();
+            assert_eq!(
+                items,
+                [
+                    serde_json::json!({"a": "]}\"[,", "b": [1, {"c": 2}]}),
+                    serde_json::json!({"a": "result", "b": []}),
+                ]
+            );
+        }
+
+        let items: Vec<u64> = decode(r#"{"id":1,"result":[],"jsonrpc":"2.0"}"#, 3).await.unwrap();
+        assert!(items.is_empty());
+
+        // Data after the response is ignored.
+        let items: Vec<u64> =
+            decode("{\"id\":1,\"jsonrpc\":\"2.0\",\"result\":[1,2]}\n{", 5).await.unwrap();
+        assert_eq!(items, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn fails_invalid_responses() {
+        let err = decode::<u64>(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"query returned more than 10000 results","data":{"result":[1]}}}"#,
+            4,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.as_error_resp().unwrap().code, -32005);
+
+        let err = decode::<u64>(r#"{"jsonrpc":"2.0","id":1,"result":null}"#, 4).await.unwrap_err();
+        assert!(err.is_null_resp());
+
+        let err = decode::<u64>(r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#, 4).await.unwrap_err();
+        assert!(err.is_deser_error());
+
+        let err =
+            decode::<u64>(r#"{"jsonrpc":"2.0","id":1,"result":[1,"a"]}"#, 4).await.unwrap_err();
+        assert!(err.is_deser_error());
+
+        let err = decode::<u64>(r#"{"jsonrpc":"2.0","id":1,"result":[1,2"#, 4).await.unwrap_err();
+        assert!(err.is_transport_error());
+    }
+}